@@ -0,0 +1,75 @@
+use fuels::prelude::*;
+
+pub mod orders;
+pub mod types;
+
+// Rust bindings for the contracts and scripts of this workspace,
+// shared by the tools and the test harness
+abigen!(
+    Contract(
+        name = "MockToken",
+        abi = "./contracts/mocks/mock_token/out/debug/mock_token-abi.json"
+    ),
+    Contract(
+        name = "MockSwaylend",
+        abi = "./contracts/mocks/mock_swaylend/out/debug/mock_swaylend-abi.json"
+    ),
+    Contract(
+        name = "MockBasicLender",
+        abi = "./contracts/mocks/mock_basic_lender/out/debug/mock_basic_lender-abi.json"
+    ),
+    Contract(
+        name = "Logger",
+        abi = "./contracts/logger/out/debug/logger-abi.json"
+    ),
+    Contract(
+        name = "OneDeltaOrders",
+        abi = "./contracts/one_delta_orders/out/debug/one_delta_orders-abi.json"
+    ),
+    Contract(
+        name = "OrderRouter",
+        abi = "./contracts/order_router/out/debug/order_router-abi.json"
+    ),
+    Contract(
+        name = "SwaylendMarket",
+        abi = "./fixtures/swaylend/market-abi.json"
+    ),
+    Contract(
+        name = "MiraAMM",
+        abi = "./fixtures/mira-amm/mira_amm_contract-abi.json"
+    ),
+    Contract(
+        name = "AccountLogic",
+        abi = "./contracts/account_logic/out/debug/account_logic-abi.json"
+    ),
+    Contract(
+        name = "AccountProxy",
+        abi = "./contracts/account_proxy/out/debug/account_proxy-abi.json"
+    ),
+    Contract(
+        name = "AccountFactory",
+        abi = "./contracts/account_factory/out/debug/account_factory-abi.json"
+    ),
+    Contract(
+        name = "Beacon",
+        abi = "./contracts/beacon/out/debug/beacon-abi.json"
+    ),
+    Script(
+        name = "AddLiquidityScript",
+        abi = "./scripts/add_liquidity_script/out/debug/add_liquidity_script-abi.json"
+    ),
+    // 1delta script
+    Script(
+        name = "BatchSwapExactInScript",
+        abi = "./scripts/batch_swap_exact_in_script/out/debug/batch_swap_exact_in_script-abi.json"
+    ),
+    Script(
+        name = "BatchSwapExactOutScript",
+        abi =
+            "./scripts/batch_swap_exact_out_script/out/debug/batch_swap_exact_out_script-abi.json"
+    ),
+    Script(
+        name = "ComposerScript",
+        abi = "./scripts/composer_script/out/debug/composer_script-abi.json"
+    ),
+);
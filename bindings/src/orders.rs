@@ -0,0 +1,24 @@
+use fuels::types::{Bits256, ContractId};
+use sha2::{Digest, Sha256};
+
+use crate::Order;
+
+// the packed order as signed by the maker, see `compute_order_hash`
+pub fn pack_order(order: &Order, verifying_contract: ContractId) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(192);
+    encoded.extend_from_slice(&*verifying_contract);
+    encoded.extend_from_slice(&order.maker_asset.0);
+    encoded.extend_from_slice(&order.taker_asset.0);
+    encoded.extend_from_slice(&order.maker_amount.to_be_bytes());
+    encoded.extend_from_slice(&order.taker_amount.to_be_bytes());
+    encoded.extend_from_slice(&order.maker.0);
+    encoded.extend_from_slice(&order.nonce.to_be_bytes());
+    encoded.extend_from_slice(&order.maker_traits.to_be_bytes());
+    encoded.extend_from_slice(&order.maker_receiver.0);
+    encoded
+}
+
+pub fn get_order_hash(order: &Order, verifying_contract: ContractId) -> Bits256 {
+    let hash: [u8; 32] = Sha256::digest(pack_order(order, verifying_contract)).into();
+    Bits256(hash)
+}
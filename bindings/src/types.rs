@@ -0,0 +1,109 @@
+use fuels::types::{AssetId, Bits256, Bytes, B512, U256};
+
+use crate::Order;
+pub type PoolId = (AssetId, AssetId, bool);
+
+// versioned step data, see the executor
+pub const STEP_DATA_VERSION_1: u8 = 1;
+pub const STEP_FLAG_STABLE: u8 = 1;
+pub const STEP_FLAG_DEX_ADDRESS: u8 = 2;
+pub const STEP_FLAG_MINIMUM_OUT: u8 = 4;
+pub const STEP_FLAG_MAXIMUM_PRICE: u8 = 8;
+// maximum prices are input per output scaled by 1e18
+pub const STEP_PRICE_SCALE: u64 = 1_000_000_000_000_000_000;
+
+pub fn encode_mira_params(fee: u64, is_stable: bool) -> Bytes {
+    let feeb: [u8; 2] = (fee as u16).to_be_bytes();
+
+    let x: [u8; 3] = if is_stable {
+        [
+            feeb[0], feeb[1], 1,
+        ]
+    } else {
+        [
+            feeb[0], feeb[1], 0,
+        ]
+    };
+
+    Bytes(x.to_vec())
+}
+
+
+pub fn encode_mira_params_with_dex_address(fee: u64, is_stable: bool, address: Bits256) -> Bytes {
+    let feeb: [u8; 2] = (fee as u16).to_be_bytes();
+
+    let mut x = Vec::with_capacity(35);
+    x.extend_from_slice(&feeb);
+    x.push(if is_stable { 1 } else { 0 });
+    x.extend_from_slice(&address.0); // Convert Bits256 to a byte slice
+
+    Bytes(x)
+}
+
+// the step data for 1delta orders as expected by the executor's `to_order`
+// the assets are omitted as they are given by the swap step
+pub fn encode_order_params(order: &Order, signature: B512) -> Bytes {
+    let mut x = Vec::with_capacity(160);
+    x.extend_from_slice(&order.maker_amount.to_be_bytes());
+    x.extend_from_slice(&order.taker_amount.to_be_bytes());
+    x.extend_from_slice(&order.maker.0);
+    x.extend_from_slice(&order.nonce.to_be_bytes());
+    x.extend_from_slice(&order.maker_traits.to_be_bytes());
+    x.extend_from_slice(&order.maker_receiver.0);
+    x.extend_from_slice(&signature.bytes[0].0);
+    x.extend_from_slice(&signature.bytes[1].0);
+
+    Bytes(x)
+}
+
+// versioned Mira step data, forks are given with their address
+pub fn encode_mira_params_v1(fee: u64, is_stable: bool, dex_address: Option<Bits256>) -> Bytes {
+    let mut flags = 0u8;
+    if is_stable {
+        flags |= STEP_FLAG_STABLE;
+    }
+    if dex_address.is_some() {
+        flags |= STEP_FLAG_DEX_ADDRESS;
+    }
+
+    let mut x = Vec::with_capacity(36);
+    x.push(STEP_DATA_VERSION_1);
+    x.push(flags);
+    x.extend_from_slice(&(fee as u16).to_be_bytes());
+    if let Some(address) = dex_address {
+        x.extend_from_slice(&address.0);
+    }
+
+    Bytes(x)
+}
+
+// versioned 1delta order step data
+pub fn encode_order_params_v1(order: &Order, signature: B512) -> Bytes {
+    let mut x = Vec::with_capacity(162);
+    x.push(STEP_DATA_VERSION_1);
+    x.push(0);
+    x.extend_from_slice(&encode_order_params(order, signature).0);
+
+    Bytes(x)
+}
+
+// adds the limit fields to versioned step data, they are the last fields
+pub fn with_step_limits(
+    data: Bytes,
+    minimum_out: Option<u64>,
+    maximum_price: Option<U256>,
+) -> Bytes {
+    let mut x = data.0;
+    if let Some(amount) = minimum_out {
+        x[1] |= STEP_FLAG_MINIMUM_OUT;
+        x.extend_from_slice(&amount.to_be_bytes());
+    }
+    if let Some(price) = maximum_price {
+        x[1] |= STEP_FLAG_MAXIMUM_PRICE;
+        let mut price_bytes = [0u8; 32];
+        price.to_big_endian(&mut price_bytes);
+        x.extend_from_slice(&price_bytes);
+    }
+
+    Bytes(x)
+}
@@ -3,16 +3,39 @@ contract;
 use standards::src12::BytecodeRoot;
 use std::{
     auth::msg_sender,
+    b512::B512,
+    block::height,
     call_frames::msg_asset_id,
     context::{
         msg_amount,
         this_balance,
     },
+    ecr::ec_recover_address,
     external::bytecode_root,
     storage::storage_vec::*,
     storage::storage_vec::*,
 };
-use account_utils::{AccountLogic, structs::Action};
+use account_utils::{
+    AccountLogic,
+    intents::compute_intent_hash,
+    NonceValidation,
+    OperatorValidation,
+    OwnershipValidation,
+    operators::{
+        is_action_allowed,
+        is_operator_step,
+        OperatorPermissions,
+    },
+    owners::{
+        compute_proposal_hash,
+        OwnerSet,
+        Proposal,
+    },
+    structs::{
+        Action,
+        TransferAction,
+    },
+};
 
 const ZERO_ID = Identity::Address(Address::from(b256::zero()));
 const ZERO_CONTRACT_ID = ContractId::from(b256::zero());
@@ -30,12 +53,37 @@ configurable {
 /// following contracts.
 /// As a workaround, we leave the lengths consistent and just change and index to
 /// a zero contractId
+/// Shared accounts are owned by the factory in `contract_to_owner` and list
+/// the contract for every member in `owner_to_contracts`
 storage {
     /// map a registered contract to an owner and the index in the owner's list
     contract_to_owner: StorageMap<ContractId, (Identity, u64)> = StorageMap {},
     /// maps owner to an indexed map u64->ContractId
     /// these indexes can have zeroes as values if a user transfers a contract
     owner_to_contracts: StorageMap<Identity, StorageVec<ContractId>> = StorageMap {},
+    /// operator approvals per (contract, owner, operator)
+    /// keyed by the owner so that approvals do not survive an ownership transfer
+    operators: StorageMap<(ContractId, Identity, Identity), OperatorPermissions> = StorageMap {},
+    /// remaining operator amounts per (approval_id, asset)
+    operator_limits: StorageMap<(u64, AssetId), u64> = StorageMap {},
+    /// lending markets an operator can use per (approval_id, market)
+    operator_markets: StorageMap<(u64, ContractId), bool> = StorageMap {},
+    /// number of operator approvals, used as approval id
+    operator_approvals: u64 = 0,
+    /// used or cancelled intent nonces per contract
+    used_nonces: StorageMap<(ContractId, u64), bool> = StorageMap {},
+    /// members of shared accounts
+    owner_sets: StorageMap<ContractId, StorageVec<Identity>> = StorageMap {},
+    /// approval threshold of shared accounts, zero for single owner accounts
+    owner_thresholds: StorageMap<ContractId, u64> = StorageMap {},
+    /// maps (contract, member) to the index in the member's list
+    member_index: StorageMap<(ContractId, Identity), u64> = StorageMap {},
+    /// incremented on owner set changes to invalidate pending approvals
+    owner_set_epochs: StorageMap<ContractId, u64> = StorageMap {},
+    /// approvals per (contract, epoch, proposal hash, member)
+    proposal_approvals: StorageMap<(ContractId, u64, b256, Identity), bool> = StorageMap {},
+    /// approval count per (contract, epoch, proposal hash)
+    proposal_approval_counts: StorageMap<(ContractId, u64, b256), u64> = StorageMap {},
 }
 
 abi ExecutionValidation {
@@ -54,6 +102,15 @@ impl ExecutionValidation for Contract {
     }
 }
 
+impl OwnershipValidation for Contract {
+    #[storage(read)]
+    fn have_same_owner(_contract: ContractId, _other: ContractId) -> bool {
+        let owner = get_contract_owner(_contract);
+        // shared accounts are owned by the factory and never match
+        owner != ZERO_ID && owner != Identity::ContractId(ContractId::this()) && owner == get_contract_owner(_other)
+    }
+}
+
 abi ContractTransfer {
     /// Allow an owner to transfer the account
     /// to another Identity.
@@ -125,6 +182,415 @@ impl ContractTransfer for Contract {
     }
 }
 
+abi OperatorRegistry {
+    /// Allow an owner to approve an operator for an account
+    /// until the block height `expiry` for the action flags in `allowed_actions`
+    /// `limits` are the amounts per asset the operator can use in total
+    /// `markets` are the lending markets of lending actions and flash loops
+    /// a new approval replaces the previous one including its limits and markets
+    #[storage(read, write)]
+    fn approve_operator(
+        _contract: ContractId,
+        _operator: Identity,
+        expiry: u32,
+        allowed_actions: u64,
+        limits: Vec<(AssetId, u64)>,
+        markets: Vec<ContractId>,
+    );
+
+    /// Allow an owner to revoke an operator
+    #[storage(read, write)]
+    fn revoke_operator(_contract: ContractId, _operator: Identity);
+
+    /// Get the approval of an operator by the current owner
+    #[storage(read)]
+    fn get_operator(_contract: ContractId, _operator: Identity) -> Option<OperatorPermissions>;
+
+    /// Get the remaining amount of an asset for an operator
+    #[storage(read)]
+    fn get_operator_limit(_contract: ContractId, _operator: Identity, _asset: AssetId) -> u64;
+
+    /// Get whether an operator can use a lending market
+    #[storage(read)]
+    fn is_operator_market(_contract: ContractId, _operator: Identity, _market: ContractId) -> bool;
+}
+
+impl OperatorRegistry for Contract {
+    #[storage(read, write)]
+    fn approve_operator(
+        _contract: ContractId,
+        _operator: Identity,
+        expiry: u32,
+        allowed_actions: u64,
+        limits: Vec<(AssetId, u64)>,
+        markets: Vec<ContractId>,
+    ) {
+        let owner = get_contract_owner(_contract);
+        require(owner != ZERO_ID && owner == msg_sender().unwrap(), "Not owner");
+        require(_operator != owner && _operator != ZERO_ID, "Invalid operator");
+
+        // a fresh approval id invalidates the limits of a previous approval
+        let approval_id = storage.operator_approvals.read() + 1;
+        storage.operator_approvals.write(approval_id);
+
+        storage.operators.insert(
+            (_contract, owner, _operator),
+            OperatorPermissions {
+                expiry,
+                allowed_actions,
+                approval_id,
+            },
+        );
+
+        let mut i = 0;
+        while i < limits.len() {
+            let (asset, limit) = limits.get(i).unwrap();
+            storage.operator_limits.insert((approval_id, asset), limit);
+            i += 1;
+        }
+
+        let mut j = 0;
+        while j < markets.len() {
+            storage.operator_markets.insert((approval_id, markets.get(j).unwrap()), true);
+            j += 1;
+        }
+    }
+
+    #[storage(read, write)]
+    fn revoke_operator(_contract: ContractId, _operator: Identity) {
+        let owner = get_contract_owner(_contract);
+        require(owner != ZERO_ID && owner == msg_sender().unwrap(), "Not owner");
+        let _ = storage.operators.remove((_contract, owner, _operator));
+    }
+
+    #[storage(read)]
+    fn get_operator(_contract: ContractId, _operator: Identity) -> Option<OperatorPermissions> {
+        storage
+            .operators
+            .get((_contract, get_contract_owner(_contract), _operator))
+            .try_read()
+    }
+
+    #[storage(read)]
+    fn get_operator_limit(_contract: ContractId, _operator: Identity, _asset: AssetId) -> u64 {
+        match storage
+            .operators
+            .get((_contract, get_contract_owner(_contract), _operator))
+            .try_read()
+        {
+            Some(permissions) => storage.operator_limits.get((permissions.approval_id, _asset)).try_read().unwrap_or(0),
+            None => 0,
+        }
+    }
+
+    #[storage(read)]
+    fn is_operator_market(_contract: ContractId, _operator: Identity, _market: ContractId) -> bool {
+        match storage
+            .operators
+            .get((_contract, get_contract_owner(_contract), _operator))
+            .try_read()
+        {
+            Some(permissions) => storage.operator_markets.get((permissions.approval_id, _market)).try_read().unwrap_or(false),
+            None => false,
+        }
+    }
+}
+
+impl OperatorValidation for Contract {
+    #[storage(read, write)]
+    fn use_operator(
+        _contract: ContractId,
+        _operator: Identity,
+        actions: Vec<Action>,
+        dex_contracts: Vec<ContractId>,
+    ) {
+        // only the account itself consumes its operators' limits
+        let account = Identity::ContractId(_contract);
+        require(msg_sender().unwrap() == account, "Unauthorized");
+
+        let owner = get_contract_owner(_contract);
+        let permissions = storage.operators.get((_contract, owner, _operator)).try_read();
+        require(permissions.is_some(), "Unauthorized");
+        let permissions = permissions.unwrap();
+        require(height() < permissions.expiry, "Operator expired");
+
+        let mut i = 0;
+        while i < actions.len() {
+            let action = actions.get(i).unwrap();
+            require(
+                is_action_allowed(permissions.allowed_actions, action),
+                "Action not allowed",
+            );
+            match action {
+                Action::Swap(swap_path_list) => {
+                    let mut j = 0;
+                    while j < swap_path_list.paths.len() {
+                        let path = swap_path_list.paths.get(j).unwrap();
+                        let steps = path.steps;
+                        spend_operator_limit(permissions.approval_id, steps.get(0).unwrap().asset_in, path.amount_in);
+                        // the slippage is bounded by the operator
+                        require(path.min_amount_out != 0, "Undefined minimum");
+
+                        // intermediate steps pay to the next DEX, the last one to the account
+                        // and only the DEXs of the account are called
+                        let mut k = 0;
+                        while k < steps.len() {
+                            let step = steps.get(k).unwrap();
+                            require(is_operator_step(step), "Invalid DEX");
+                            require(
+                                step.receiver == account || (k + 1 < steps.len() && is_dex(step.receiver, dex_contracts)),
+                                "Invalid receiver",
+                            );
+                            k += 1;
+                        }
+                        j += 1;
+                    }
+                },
+                Action::Lending(lender_action) => {
+                    // received amounts are not known upfront
+                    require(lender_action.amount_type_id == 1, "Undefined amount");
+                    require_operator_market(permissions.approval_id, lender_action.market);
+                    spend_operator_limit(
+                        permissions.approval_id,
+                        lender_action
+                            .asset,
+                        lender_action
+                            .amount_in,
+                    );
+                },
+                Action::Transfer(TransferAction {
+                    asset,
+                    amount,
+                    receiver,
+                }) => {
+                    require(receiver == owner, "Invalid receiver");
+                    spend_operator_limit(permissions.approval_id, asset, amount);
+                },
+                Action::AssertHealth(_) => {},
+                Action::FlashLoop(flash_loop) => {
+                    require(flash_loop.amount != 0, "Undefined amount");
+                    require_operator_market(permissions.approval_id, flash_loop.market);
+                    // the swap input is paid from the position
+                    let asset_in = if flash_loop.open {
+                        flash_loop.base_asset
+                    } else {
+                        flash_loop.collateral_asset
+                    };
+                    spend_operator_limit(permissions.approval_id, asset_in, flash_loop.maximum_in);
+                },
+                // migrations are rejected by `is_action_allowed`
+                Action::Migrate(_) => {},
+                Action::AddLiquidity(add_liquidity) => {
+                    require(add_liquidity.receiver == account, "Invalid receiver");
+                    spend_operator_limit(
+                        permissions.approval_id,
+                        add_liquidity
+                            .pool_id
+                            .0,
+                        add_liquidity
+                            .amount_0_desired,
+                    );
+                    spend_operator_limit(
+                        permissions.approval_id,
+                        add_liquidity
+                            .pool_id
+                            .1,
+                        add_liquidity
+                            .amount_1_desired,
+                    );
+                },
+                // the pool assets are returned to the account
+                Action::RemoveLiquidity(remove_liquidity) => {
+                    require(remove_liquidity.receiver == account, "Invalid receiver");
+                    require(remove_liquidity.liquidity != 0, "Undefined amount");
+                },
+            }
+            i += 1;
+        }
+    }
+}
+
+abi IntentNonces {
+    /// Allow an owner to cancel a signed intent
+    #[storage(read, write)]
+    fn cancel_nonce(_contract: ContractId, _nonce: u64);
+
+    #[storage(read)]
+    fn is_nonce_used(_contract: ContractId, _nonce: u64) -> bool;
+}
+
+impl IntentNonces for Contract {
+    #[storage(read, write)]
+    fn cancel_nonce(_contract: ContractId, _nonce: u64) {
+        let owner = get_contract_owner(_contract);
+        require(owner != ZERO_ID && owner == msg_sender().unwrap(), "Not owner");
+        storage.used_nonces.insert((_contract, _nonce), true);
+    }
+
+    #[storage(read)]
+    fn is_nonce_used(_contract: ContractId, _nonce: u64) -> bool {
+        storage.used_nonces.get((_contract, _nonce)).try_read().unwrap_or(false)
+    }
+}
+
+impl NonceValidation for Contract {
+    #[storage(read, write)]
+    fn use_nonce(_contract: ContractId, _nonce: u64) {
+        // only the account itself consumes its nonces
+        require(msg_sender().unwrap() == Identity::ContractId(_contract), "Unauthorized");
+        use_nonce_internal(_contract, _nonce);
+    }
+}
+
+abi SharedAccount {
+    /// Allow an owner to share the account with an owner set
+    /// the account is then owned by the factory on behalf of the owners
+    /// and changes of the owner set need a proposal
+    #[storage(read, write)]
+    fn set_owners(_contract: ContractId, owner_set: OwnerSet);
+
+    /// Approve the proposal with the hash `compute_proposal_hash(proposal, nonce, contract)`
+    #[storage(read, write)]
+    fn approve_proposal(_contract: ContractId, _proposal_hash: b256);
+
+    /// Execute a proposal that reached the threshold, callable by anyone
+    #[storage(read, write)]
+    fn execute_proposal(_contract: ContractId, proposal: Proposal, nonce: u64);
+
+    /// Compose with the signatures of a threshold of owners
+    /// over the intent hash of `execute_signed`
+    #[storage(read, write)]
+    fn compose_signed(
+        _contract: ContractId,
+        actions: Vec<Action>,
+        nonce: u64,
+        deadline: u32,
+        signatures: Vec<B512>,
+    );
+
+    #[storage(read)]
+    fn get_owner_set(_contract: ContractId) -> OwnerSet;
+
+    #[storage(read)]
+    fn get_proposal_approvals(_contract: ContractId, _proposal_hash: b256) -> u64;
+}
+
+impl SharedAccount for Contract {
+    #[storage(read, write)]
+    fn set_owners(_contract: ContractId, owner_set: OwnerSet) {
+        let (owner, from_contract_id) = get_contract_owner_and_id(_contract);
+        // shared accounts are owned by the factory, so only single owners pass
+        require(owner != ZERO_ID && owner == msg_sender().unwrap(), "Not owner");
+
+        // remove it from owner list by setting this one to zero
+        storage
+            .owner_to_contracts
+            .get(owner)
+            .set(from_contract_id, ZERO_CONTRACT_ID);
+
+        storage
+            .contract_to_owner
+            .insert(_contract, (Identity::ContractId(ContractId::this()), 0));
+
+        write_owner_set(_contract, owner_set);
+    }
+
+    #[storage(read, write)]
+    fn approve_proposal(_contract: ContractId, _proposal_hash: b256) {
+        let sender = msg_sender().unwrap();
+        require(is_member(_contract, sender), "Not owner");
+
+        let epoch = storage.owner_set_epochs.get(_contract).try_read().unwrap_or(0);
+        require(
+            !storage
+                .proposal_approvals
+                .get((_contract, epoch, _proposal_hash, sender))
+                .try_read()
+                .unwrap_or(false),
+            "Already approved",
+        );
+        storage
+            .proposal_approvals
+            .insert((_contract, epoch, _proposal_hash, sender), true);
+
+        let approvals = get_approvals(_contract, epoch, _proposal_hash);
+        storage
+            .proposal_approval_counts
+            .insert((_contract, epoch, _proposal_hash), approvals + 1);
+    }
+
+    #[storage(read, write)]
+    fn execute_proposal(_contract: ContractId, proposal: Proposal, nonce: u64) {
+        let threshold = get_threshold(_contract);
+        require(threshold != 0, "Not shared");
+
+        let proposal_hash = compute_proposal_hash(proposal, nonce, _contract.bits());
+        let epoch = storage.owner_set_epochs.get(_contract).try_read().unwrap_or(0);
+        require(
+            get_approvals(_contract, epoch, proposal_hash) >= threshold,
+            "Insufficient approvals",
+        );
+
+        use_nonce_internal(_contract, nonce);
+
+        match proposal {
+            // the factory can call the account
+            Proposal::Compose(actions) => abi(AccountLogic, _contract.bits()).compose(actions),
+            Proposal::SetOwners(owner_set) => write_owner_set(_contract, owner_set),
+        }
+    }
+
+    #[storage(read, write)]
+    fn compose_signed(
+        _contract: ContractId,
+        actions: Vec<Action>,
+        nonce: u64,
+        deadline: u32,
+        signatures: Vec<B512>,
+    ) {
+        require(deadline >= height(), "Deadline passed");
+        let threshold = get_threshold(_contract);
+        require(threshold != 0, "Not shared");
+
+        let intent_hash = compute_intent_hash(actions, nonce, deadline, _contract.bits());
+
+        // every signature has to be from a different member
+        let mut signers: Vec<Identity> = Vec::new();
+        let mut i = 0;
+        while i < signatures.len() {
+            let result_address = ec_recover_address(signatures.get(i).unwrap(), intent_hash);
+            require(result_address.is_ok(), "Invalid signature");
+            let signer = Identity::Address(result_address.unwrap());
+            require(
+                is_member(_contract, signer) && !contains(signers, signer),
+                "Invalid signature",
+            );
+            signers.push(signer);
+            i += 1;
+        }
+        require(signers.len() >= threshold, "Insufficient signatures");
+
+        use_nonce_internal(_contract, nonce);
+
+        // the factory can call the account
+        abi(AccountLogic, _contract.bits()).compose(actions);
+    }
+
+    #[storage(read)]
+    fn get_owner_set(_contract: ContractId) -> OwnerSet {
+        OwnerSet {
+            owners: storage.owner_sets.get(_contract).load_vec(),
+            threshold: get_threshold(_contract),
+        }
+    }
+
+    #[storage(read)]
+    fn get_proposal_approvals(_contract: ContractId, _proposal_hash: b256) -> u64 {
+        let epoch = storage.owner_set_epochs.get(_contract).try_read().unwrap_or(0);
+        get_approvals(_contract, epoch, _proposal_hash)
+    }
+}
+
 abi RegisterAndCall {
     /// register a contract for an entity
     /// optionally actions can be performed and
@@ -204,6 +670,128 @@ fn register_contract_internal(child_contract: ContractId, _for: Identity) {
     storage.owner_to_contracts.get(_for).push(child_contract);
 }
 
+/// marks an intent or proposal nonce as used
+#[storage(read, write)]
+fn use_nonce_internal(_contract: ContractId, _nonce: u64) {
+    require(
+        !storage
+            .used_nonces
+            .get((_contract, _nonce))
+            .try_read()
+            .unwrap_or(false),
+        "Nonce used",
+    );
+    storage.used_nonces.insert((_contract, _nonce), true);
+}
+
+/// replaces the members of a shared account
+/// the previous members' list entries are set to zero like on transfers
+#[storage(read, write)]
+fn write_owner_set(_contract: ContractId, owner_set: OwnerSet) {
+    require(
+        owner_set.threshold != 0 && owner_set.threshold <= owner_set.owners.len(),
+        "Invalid threshold",
+    );
+
+    let mut i = 0;
+    while i < storage.owner_sets.get(_contract).len() {
+        let member = storage.owner_sets.get(_contract).get(i).unwrap().read();
+        let index = storage.member_index.get((_contract, member)).read();
+        storage
+            .owner_to_contracts
+            .get(member)
+            .set(index, ZERO_CONTRACT_ID);
+        let _ = storage.member_index.remove((_contract, member));
+        i += 1;
+    }
+    storage.owner_sets.get(_contract).clear();
+
+    let mut i = 0;
+    while i < owner_set.owners.len() {
+        let member = owner_set.owners.get(i).unwrap();
+        require(
+            member != ZERO_ID && member != Identity::ContractId(ContractId::this()) && !is_member(_contract, member),
+            "Invalid owner",
+        );
+
+        // the next index is the array length
+        let index = storage.owner_to_contracts.get(member).len();
+        storage.owner_to_contracts.get(member).push(_contract);
+        storage.member_index.insert((_contract, member), index);
+        storage.owner_sets.get(_contract).push(member);
+        i += 1;
+    }
+    storage.owner_thresholds.insert(_contract, owner_set.threshold);
+
+    // approvals of the previous owner set are void
+    let epoch = storage.owner_set_epochs.get(_contract).try_read().unwrap_or(0);
+    storage.owner_set_epochs.insert(_contract, epoch + 1);
+}
+
+#[storage(read)]
+fn is_member(_contract: ContractId, _member: Identity) -> bool {
+    storage.member_index.get((_contract, _member)).try_read().is_some()
+}
+
+#[storage(read)]
+fn get_threshold(_contract: ContractId) -> u64 {
+    storage.owner_thresholds.get(_contract).try_read().unwrap_or(0)
+}
+
+#[storage(read)]
+fn get_approvals(_contract: ContractId, _epoch: u64, _proposal_hash: b256) -> u64 {
+    storage
+        .proposal_approval_counts
+        .get((_contract, _epoch, _proposal_hash))
+        .try_read()
+        .unwrap_or(0)
+}
+
+fn contains(identities: Vec<Identity>, identity: Identity) -> bool {
+    let mut i = 0;
+    while i < identities.len() {
+        if identities.get(i).unwrap() == identity {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
+/// consumes `amount` of the operator limit of `asset`
+/// zero and max amounts resolve to balances and cannot be limited
+#[storage(read, write)]
+fn spend_operator_limit(approval_id: u64, asset: AssetId, amount: u64) {
+    require(amount != 0 && amount != u64::max(), "Undefined amount");
+    let limit = storage.operator_limits.get((approval_id, asset)).try_read().unwrap_or(0);
+    require(limit >= amount, "Operator limit exceeded");
+    storage.operator_limits.insert((approval_id, asset), limit - amount);
+}
+
+/// reverts if the approval does not include the lending market
+#[storage(read)]
+fn require_operator_market(approval_id: u64, market: ContractId) {
+    require(
+        storage
+            .operator_markets
+            .get((approval_id, market))
+            .try_read()
+            .unwrap_or(false),
+        "Invalid market",
+    );
+}
+
+fn is_dex(receiver: Identity, dex_contracts: Vec<ContractId>) -> bool {
+    let mut i = 0;
+    while i < dex_contracts.len() {
+        if receiver == Identity::ContractId(dex_contracts.get(i).unwrap()) {
+            return true;
+        }
+        i += 1;
+    }
+    false
+}
+
 // minimum wrapper
 fn _min(a: u64, b: u64) -> u64 {
     u64::min(a, b)
@@ -1,34 +1,38 @@
 contract;
 
-use interfaces::mira_amm::MiraAMM;
-use executor::{BatchSwapStep, execute_exact_in, get_dex_input_receiver};
-use std::{asset::transfer, auth::msg_sender, context::this_balance, revert::revert};
-use market_abi::Market;
+use composer::{
+    execute_actions,
+    flash::{
+        PositionMigration,
+        receive_migration,
+        settle_flash_swap,
+    },
+    structs::MigrateAction,
+};
+use interfaces::callee::IBaseCallee;
+use std::{
+    auth::msg_sender,
+    b512::B512,
+    block::height,
+    bytes::Bytes,
+    call_frames::msg_asset_id,
+    context::msg_amount,
+    ecr::{
+        ec_recover_address,
+        EcRecoverError,
+    },
+};
+use order_utils::ISignatureValidator;
 use account_utils::{
     AccountLogic,
     ExecutionValidation,
-    structs::{
-        Action,
-        AmountType,
-        LenderAction,
-        LenderActionType,
-        LenderId,
-        SwapPath,
-        SwapPathList,
-        TransferAction,
-    },
+    intents::compute_intent_hash,
+    NonceValidation,
+    OperatorValidation,
+    OwnershipValidation,
+    structs::Action,
 };
 
-////////////////////////////////////////////////////
-// Error codes
-////////////////////////////////////////////////////
-const EMPTY_PATH_ENTRY: u64 = 100;
-const EMPTY_ACTION_ENTRY: u64 = 101;
-const INVALID_LENDER_ID: u64 = 102;
-const INVALID_ACTION_TYPE: u64 = 103;
-const INVALID_AMOUNT_TYPE: u64 = 104;
-const INVALID_BALANCE: u64 = 105;
-
 ////////////////////////////////////////////////////
 // DEX references
 ////////////////////////////////////////////////////
@@ -42,234 +46,98 @@ impl AccountLogic for Contract {
     #[payable, storage(write)]
     fn compose(actions: Vec<Action>) {
         // validate that only authorized entities can call this contract 
-        require(
-            abi(ExecutionValidation, FACTORY_ID)
-                .can_call(ContractId::this(), msg_sender().unwrap()),
-            "Unauthorized",
-        );
-
-        // use cached amount for split swaps
-        let mut amount_cached = 0u64;
-
-        // start to go through actions
-        let mut j = 0;
-        while j < actions.len() {
-            match actions.get(j) {
-                Some(Action::Swap(swap_path_list)) => {
-                    // start to swap through paths
-                    let mut i = 0;
-                    while i < swap_path_list.paths.len() {
-                        // get current path, input amount, slippage_check, transfer_in flag and path
-                        let (current_amount_in, minimum_out, transfer_in, current_path) = match swap_path_list.paths.get(i) {
-                            Option::Some(SwapPath {
-                                amount_in,
-                                min_amount_out,
-                                transfer_in,
-                                steps,
-                            }) => (amount_in, min_amount_out, transfer_in, steps),
-                            Option::None => revert(EMPTY_PATH_ENTRY),
-                        };
-
-                        // get the amount to be used
-                        // if zero, we use the last cached amount to swap splits
-                        // after a single swap
-                        // if the cached amount is used, we reset it to zero
-                        let mut amount_in_used = if current_amount_in != 0 {
-                            current_amount_in
-                        } else {
-                            // TEMP: make sure that assignment is via values
-                            let am = amount_cached + 0;
-                            // reset amount cached after it was used
-                            amount_cached = 0;
-                            am
-                        };
-
-                        // get path length for iteration
-                        let path_length = current_path.len();
-
-                        // initialize first swap step (from action j)
-                        let mut swap_step = current_path.get(0).unwrap();
-
-                        // transfer to first DEX if needed
-                        if transfer_in {
-                            transfer(
-                                get_dex_input_receiver(
-                                    swap_step
-                                        .dex_id,
-                                    swap_step
-                                        .data,
-                                    MIRA_AMM_CONTRACT_ID,
-                                    ONE_DELTA_ORDERS_CONTRACT_ID,
-                                ),
-                                swap_step
-                                    .asset_in,
-                                amount_in_used,
-                            );
-                        }
-                        // start swapping the path via index k
-                        let mut k = 0;
-                        while true {
-                            //=============================================
-                            //      DEX swap execution  
-                            //=============================================
-
-                            // execute swap
-                            amount_in_used = execute_exact_in(
-                                u64::try_from(amount_in_used)
-                                    .unwrap(),
-                                swap_step,
-                                MIRA_AMM_CONTRACT_ID,
-                                ONE_DELTA_ORDERS_CONTRACT_ID,
-                            );
-
-                            //=============================================
-                            //      DEX swap end  
-                            //=============================================
-
-                            // increment swap step index within path
-                            k += 1;
-
-                            // check if we need to continue
-                            if k < path_length {
-                                // get next swap_step
-                                swap_step = current_path.get(k).unwrap();
-                            } else {
-                                // in this block, we completed a path
-                                // we record / increment the cached amount and check for slippage
-                                // increment cache
-                                amount_cached += amount_in_used;
-                                // check for slippage on path
-                                require(amount_in_used > minimum_out, "Insufficient output amount");
-                                // break and start next path
-                                break;
-                            }
-                        }
-                        // increment path index
-                        i += 1;
-                    }
-                    // increment action index
-                    j += 1;
-                },
-                Some(Action::Lending(LenderAction {
-                    lender_id,
-                    action_id,
-                    asset,
-                    amount_in,
-                    amount_type_id,
-                    data,
-                    market,
-                    additional_params
-                })) => {
-                    let lender = match LenderId::from_u64(lender_id) {
-                        Some(lender) => lender,
-                        None => revert(INVALID_LENDER_ID),
-                    };
-                    let action = match LenderActionType::from_u16(action_id) {
-                        Some(action) => action,
-                        None => revert(INVALID_ACTION_TYPE),
-                    };
-                    let amount_type = match AmountType::from_u8(amount_type_id) {
-                        Some(amount_type) => amount_type,
-                        None => revert(INVALID_AMOUNT_TYPE),
-                    };
-                    let mut amount = match amount_type {
-                        AmountType::Received => {
-                            // TEMP: make sure that assignment is via values
-                            let am = amount_cached + 0;
-                            // reset amount cached after it was used
-                            amount_cached = 0;
-                            am
-                        },
-                        AmountType::Defined => amount_in,
-                    };
-
-                    // increment operation index
-                    j += 1;
+        let caller = msg_sender().unwrap();
+        if !abi(ExecutionValidation, FACTORY_ID).can_call(ContractId::this(), caller) {
+            // approved operators are validated against their permissions
+            let mut dex_contracts = Vec::new();
+            dex_contracts.push(MIRA_AMM_CONTRACT_ID);
+            dex_contracts.push(ONE_DELTA_ORDERS_CONTRACT_ID);
+            abi(OperatorValidation, FACTORY_ID).use_operator(ContractId::this(), caller, actions, dex_contracts);
+        }
 
-                    match lender {
-                        LenderId::SwaylendUSDC => {
-                            // get lending market contract
-                            let swaylend_market = abi(Market, market.into());
+        // this account holds funds and lender positions
+        execute_actions(
+            actions,
+            Identity::ContractId(ContractId::this()),
+            MIRA_AMM_CONTRACT_ID,
+            ONE_DELTA_ORDERS_CONTRACT_ID,
+        );
+    }
 
-                            match action {
-                                LenderActionType::Deposit => {
-                                    swaylend_market
-                                        .supply_collateral {
-                                            asset_id: asset.into(),
-                                            coins: amount,
-                                        }();
-                                },
-                                LenderActionType::Borrow => {
-                                    require(data.is_some(), "price data not defined");
+    #[storage(write)]
+    fn execute_signed(actions: Vec<Action>, nonce: u64, deadline: u32, signature: B512) {
+        require(deadline >= height(), "Deadline passed");
+
+        // the intent has to be signed by the owner
+        let intent_hash = compute_intent_hash(actions, nonce, deadline, ContractId::this().bits());
+        let result_address: Result<Address, EcRecoverError> = ec_recover_address(signature, intent_hash);
+        let is_owner = match result_address {
+            Ok(signer) => abi(ExecutionValidation, FACTORY_ID).can_call(ContractId::this(), Identity::Address(signer)),
+            Err(_) => false,
+        };
+        require(is_owner, "Invalid signature");
+
+        // replay protection is stored in the factory
+        abi(NonceValidation, FACTORY_ID).use_nonce(ContractId::this(), nonce);
+
+        execute_actions(
+            actions,
+            Identity::ContractId(ContractId::this()),
+            MIRA_AMM_CONTRACT_ID,
+            ONE_DELTA_ORDERS_CONTRACT_ID,
+        );
+    }
+}
 
-                                    // 0 indicates full balance repay
-                                    if amount == 0 {
-                                        let (base_deposit, _) = swaylend_market.get_user_supply_borrow(Identity::ContractId(ContractId::this()));
-                                        let base_deposit_64 = u64::try_from(base_deposit).unwrap();
-                                        if base_deposit_64 == 0u64 {
-                                            revert(INVALID_BALANCE);
-                                        } else {
-                                            amount = base_deposit_64;
-                                        }
-                                    }
+/// Flash swap callback of Mira for flash loops and migrations started in `compose`
+impl IBaseCallee for Contract {
+    #[storage(read, write)]
+    fn hook(sender: Identity, _amount_0: u64, _amount_1: u64, data: Bytes) {
+        settle_flash_swap(
+            sender,
+            data,
+            Identity::ContractId(ContractId::this()),
+            MIRA_AMM_CONTRACT_ID,
+        );
+    }
+}
 
-                                    swaylend_market
-                                        .withdraw_base {
-                                            asset_id: AssetId::base().bits(),
-                                            coins: data.unwrap().update_fee,
-                                        }(amount, data.unwrap());
-                                },
-                                LenderActionType::Withdraw => {
-                                    require(data.is_some(), "price data not defined");
+/// Receives positions migrated from other accounts of the owner
+impl PositionMigration for Contract {
+    #[payable, storage(read)]
+    fn migrate_in(migration: MigrateAction, borrow_amount: u64) {
+        // only accounts of the same owner can move debt to this account
+        let is_same_owner = match msg_sender().unwrap() {
+            Identity::ContractId(source) => abi(OwnershipValidation, FACTORY_ID).have_same_owner(source, ContractId::this()),
+            Identity::Address(_) => false,
+        };
+        require(is_same_owner, "Unauthorized");
+
+        // the collateral is forwarded with the call
+        let collateral_amount = if msg_amount() != 0 {
+            require(msg_asset_id() == migration.collateral_asset, "Invalid asset");
+            msg_amount()
+        } else {
+            0
+        };
+        receive_migration(
+            migration,
+            collateral_amount,
+            borrow_amount,
+            MIRA_AMM_CONTRACT_ID,
+        );
+    }
+}
 
-                                    swaylend_market
-                                        .withdraw_collateral {
-                                            asset_id: AssetId::base().bits(),
-                                            coins: data.unwrap().update_fee,
-                                        }(asset, amount, data.unwrap());
-                                },
-                                LenderActionType::Repay => {
-                                    if amount == 0 {
-                                        let (_, user_borrow) = swaylend_market.get_user_supply_borrow(Identity::ContractId(ContractId::this()));
-                                        let borrow_64 = u64::try_from(user_borrow).unwrap();
-                                        if borrow_64 == 0u64 {
-                                            revert(INVALID_BALANCE);
-                                        } else {
-                                            amount = borrow_64;
-                                        }
-                                    }
-                                    swaylend_market
-                                        .supply_base {
-                                            asset_id: asset.into(),
-                                            coins: amount,
-                                        }();
-                                },
-                                _ => {
-                                    revert(EMPTY_ACTION_ENTRY);
-                                }
-                            }
-                        },
-                        _ => {
-                            revert(EMPTY_ACTION_ENTRY);
-                        }
-                    }
-                },
-                Some(Action::Transfer(TransferAction {
-                    asset, amount, receiver,
-                })) => {
-                    // zero for using this contract balance
-                    let amount_used = if amount != 0 {
-                        amount
-                    } else {
-                        this_balance(asset)
-                    };
-                    transfer(receiver, asset, amount_used);
-                    j += 1;
-                },
-                None => {
-                    revert(EMPTY_ACTION_ENTRY);
-                }
-            };
+/// Allows the account to act as maker for 1delta orders.
+/// The order hash has to be signed by the account owner.
+impl ISignatureValidator for Contract {
+    #[storage(read)]
+    fn is_valid_signature(order_hash: b256, signature: B512) -> bool {
+        let result_address: Result<Address, EcRecoverError> = ec_recover_address(signature, order_hash);
+        match result_address {
+            Ok(signer) => abi(ExecutionValidation, FACTORY_ID).can_call(ContractId::this(), Identity::Address(signer)),
+            Err(_) => false,
         }
     }
 }
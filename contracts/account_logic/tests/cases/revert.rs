@@ -0,0 +1,549 @@
+use crate::cases::success::{flash_loop, migrate, open_flash_loop, repay};
+use crate::utils::{setup, REPAY, SWAP, TRANSFER_TO_OWNER};
+use fuels::accounts::ViewOnlyAccount;
+use fuels::types::errors::{transaction::Reason, Error};
+use fuels::types::{Bits256, Identity};
+use test_harness::interface::account::{get_proposal_hash, sign_intent};
+use test_harness::interface::{
+    Action, BatchSwapStep, Proposal, SwapPath, SwapPathList, TransferAction,
+};
+use test_harness::types::{encode_mira_params, encode_mira_params_with_dex_address};
+
+// composer error codes
+const FLASH_INPUT_TOO_HIGH: u64 = 110;
+
+// executor dex id of 1delta orders
+const ONE_DELTA_ORDERS_DEX_ID: u64 = 100;
+
+fn assert_revert_reason<T: std::fmt::Debug>(result: Result<T, Error>, expected: &str) {
+    match result {
+        Err(Error::Transaction(Reason::Reverted { reason, .. })) => {
+            assert!(
+                reason.contains(expected),
+                "expected {expected}, got {reason}"
+            )
+        }
+        Err(e) => panic!("expected revert {expected}, got {e:?}"),
+        Ok(value) => panic!("expected revert {expected}, got {value:?}"),
+    }
+}
+
+fn assert_revert<T: std::fmt::Debug>(result: Result<T, Error>, expected: u64) {
+    match result {
+        Err(Error::Transaction(Reason::Reverted { revert_id, .. })) => {
+            assert_eq!(revert_id, expected)
+        }
+        Err(e) => panic!("expected revert {expected}, got {e:?}"),
+        Ok(value) => panic!("expected revert {expected}, got {value:?}"),
+    }
+}
+
+#[tokio::test]
+async fn cannot_flash_loop_above_maximum_in() {
+    let f = setup().await;
+
+    f.fund_account(f.collateral, 1_000).await;
+
+    // the flash swap costs more than 2_000 base
+    let result = f
+        .account
+        .methods()
+        .compose(vec![Action::FlashLoop(flash_loop(&f, true, 2_000, 2_000))])
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await;
+
+    assert_revert(result, FLASH_INPUT_TOO_HIGH);
+}
+
+#[tokio::test]
+async fn cannot_close_flash_loop_above_maximum_in() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+
+    // repaying the debt costs about as much collateral as the debt
+    let (_, debt) = f.position().await;
+    let result = f
+        .account
+        .methods()
+        .compose(vec![Action::FlashLoop(flash_loop(&f, false, 0, debt / 2))])
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await;
+
+    assert_revert(result, FLASH_INPUT_TOO_HIGH);
+}
+
+#[tokio::test]
+async fn unapproved_operator_cannot_compose() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Lending(repay(&f, 500))])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Unauthorized");
+}
+
+#[tokio::test]
+async fn operator_cannot_exceed_limit() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, REPAY, vec![(f.base, 100)])
+        .await;
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Lending(repay(&f, 500))])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Operator limit exceeded");
+}
+
+#[tokio::test]
+async fn operator_cannot_use_disallowed_action() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, REPAY, vec![(f.base, 500)])
+        .await;
+
+    let transfer = TransferAction {
+        asset: f.base,
+        amount: 500,
+        receiver: Identity::Address(f.wallet.address().into()),
+    };
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Transfer(transfer)])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Action not allowed");
+}
+
+#[tokio::test]
+async fn operator_can_only_transfer_to_owner() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, TRANSFER_TO_OWNER, vec![(f.base, 500)])
+        .await;
+
+    let transfer = TransferAction {
+        asset: f.base,
+        amount: 500,
+        receiver: Identity::Address(operator.address().into()),
+    };
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Transfer(transfer)])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Invalid receiver");
+}
+
+#[tokio::test]
+async fn operator_cannot_use_foreign_market() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, REPAY, vec![(f.base, 500)])
+        .await;
+
+    // the approval only covers the lender market
+    let mut foreign_repay = repay(&f, 500);
+    foreign_repay.market = f.factory.contract_id().into();
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Lending(foreign_repay)])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Invalid market");
+}
+
+#[tokio::test]
+async fn operator_cannot_swap_on_named_dex() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, SWAP, vec![(f.base, 500)])
+        .await;
+
+    // the first hop would pay to the contract named in the step data
+    let fork = Bits256(*f.factory.contract_id().hash());
+    let swap = SwapPathList {
+        paths: vec![SwapPath {
+            amount_in: 500,
+            min_amount_out: 1,
+            transfer_in: true,
+            steps: vec![BatchSwapStep {
+                dex_id: 0,
+                asset_in: f.base,
+                asset_out: f.collateral,
+                receiver: Identity::ContractId(f.account_id),
+                data: encode_mira_params_with_dex_address(f.swap_fee, false, fork),
+            }],
+        }],
+    };
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Swap(swap)])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Invalid DEX");
+}
+
+#[tokio::test]
+async fn operator_cannot_swap_through_orders() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, SWAP, vec![(f.base, 500)])
+        .await;
+
+    // the operator would choose the order and with it the maker
+    let swap = SwapPathList {
+        paths: vec![SwapPath {
+            amount_in: 500,
+            min_amount_out: 1,
+            transfer_in: true,
+            steps: vec![BatchSwapStep {
+                dex_id: ONE_DELTA_ORDERS_DEX_ID,
+                asset_in: f.base,
+                asset_out: f.collateral,
+                receiver: Identity::ContractId(f.account_id),
+                // never decoded, the step is rejected upfront
+                data: encode_mira_params(f.swap_fee, false),
+            }],
+        }],
+    };
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Swap(swap)])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Invalid DEX");
+}
+
+#[tokio::test]
+async fn operator_cannot_swap_without_minimum() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, SWAP, vec![(f.base, 500)])
+        .await;
+
+    let swap = SwapPathList {
+        paths: vec![SwapPath {
+            amount_in: 500,
+            min_amount_out: 0,
+            transfer_in: true,
+            steps: vec![BatchSwapStep {
+                dex_id: 0,
+                asset_in: f.base,
+                asset_out: f.collateral,
+                receiver: Identity::ContractId(f.account_id),
+                data: encode_mira_params(f.swap_fee, false),
+            }],
+        }],
+    };
+
+    let result = f
+        .account
+        .clone()
+        .with_account(operator)
+        .methods()
+        .compose(vec![Action::Swap(swap)])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Undefined minimum");
+}
+
+#[tokio::test]
+async fn cannot_replay_signed_intent() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+    f.fund_account(f.base, 1_000).await;
+
+    let actions = vec![Action::Lending(repay(&f, 500))];
+    let nonce = 1;
+    let deadline = f
+        .wallet
+        .provider()
+        .unwrap()
+        .latest_block_height()
+        .await
+        .unwrap()
+        + 10;
+    let signature = sign_intent(&f.wallet, &actions, nonce, deadline, f.account_id).await;
+
+    let relayer = f.account.clone().with_account(f.new_wallet().await);
+
+    relayer
+        .methods()
+        .execute_signed(actions.clone(), nonce, deadline, signature.clone())
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await
+        .unwrap();
+
+    let result = relayer
+        .methods()
+        .execute_signed(actions, nonce, deadline, signature)
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Nonce used");
+}
+
+#[tokio::test]
+async fn cannot_execute_intent_not_signed_by_owner() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let relayer = f.new_wallet().await;
+
+    // a transfer to the relayer signed by the relayer itself
+    let actions = vec![Action::Transfer(TransferAction {
+        asset: f.base,
+        amount: 500,
+        receiver: Identity::Address(relayer.address().into()),
+    })];
+    let nonce = 1;
+    let deadline = f
+        .wallet
+        .provider()
+        .unwrap()
+        .latest_block_height()
+        .await
+        .unwrap()
+        + 10;
+    let signature = sign_intent(&relayer, &actions, nonce, deadline, f.account_id).await;
+
+    let result = f
+        .account
+        .clone()
+        .with_account(relayer)
+        .methods()
+        .execute_signed(actions, nonce, deadline, signature)
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Invalid signature");
+}
+
+#[tokio::test]
+async fn shared_account_member_cannot_compose_alone() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let member = f.new_wallet().await;
+    f.share_account(vec![&f.wallet, &member], 2).await;
+
+    let transfer = TransferAction {
+        asset: f.base,
+        amount: 500,
+        receiver: Identity::Address(f.wallet.address().into()),
+    };
+
+    let result = f
+        .account
+        .methods()
+        .compose(vec![Action::Transfer(transfer)])
+        .with_contract_ids(&f.contract_ids)
+        .with_contracts(&[&f.factory])
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Unauthorized");
+}
+
+#[tokio::test]
+async fn cannot_execute_proposal_below_threshold() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let member = f.new_wallet().await;
+    f.share_account(vec![&f.wallet, &member], 2).await;
+
+    let proposal = Proposal::Compose(vec![Action::Transfer(TransferAction {
+        asset: f.base,
+        amount: 500,
+        receiver: Identity::Address(member.address().into()),
+    })]);
+    let nonce = 1;
+
+    f.factory
+        .clone()
+        .with_account(member.clone())
+        .methods()
+        .approve_proposal(
+            f.account_id,
+            get_proposal_hash(&proposal, nonce, f.account_id),
+        )
+        .call()
+        .await
+        .unwrap();
+
+    let result = f
+        .factory
+        .clone()
+        .with_account(member)
+        .methods()
+        .execute_proposal(f.account_id, proposal, nonce)
+        .with_contract_ids(&f.factory_contract_ids())
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Insufficient approvals");
+}
+
+#[tokio::test]
+async fn cannot_compose_with_duplicate_signatures() {
+    let f = setup().await;
+
+    f.fund_account(f.base, 500).await;
+
+    let member = f.new_wallet().await;
+    f.share_account(vec![&f.wallet, &member], 2).await;
+
+    let actions = vec![Action::Transfer(TransferAction {
+        asset: f.base,
+        amount: 500,
+        receiver: Identity::Address(member.address().into()),
+    })];
+    let nonce = 1;
+    let deadline = f
+        .wallet
+        .provider()
+        .unwrap()
+        .latest_block_height()
+        .await
+        .unwrap()
+        + 10;
+    let signature = sign_intent(&member, &actions, nonce, deadline, f.account_id).await;
+
+    let result = f
+        .factory
+        .clone()
+        .with_account(member)
+        .methods()
+        .compose_signed(
+            f.account_id,
+            actions,
+            nonce,
+            deadline,
+            vec![signature.clone(), signature],
+        )
+        .with_contract_ids(&f.factory_contract_ids())
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Invalid signature");
+}
+
+#[tokio::test]
+async fn cannot_migrate_to_account_of_other_owner() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+    let (collateral, _) = f.position().await;
+
+    let other_owner = f.new_wallet().await;
+    let target = f
+        .new_account(Identity::Address(other_owner.address().into()), [1u8; 32])
+        .await;
+
+    let mut contract_ids = f.contract_ids.clone();
+    contract_ids.push(target.into());
+
+    let result = f
+        .account
+        .methods()
+        .compose(vec![Action::Migrate(migrate(&f, target, collateral, 0))])
+        .with_contract_ids(&contract_ids)
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Unauthorized");
+}
@@ -0,0 +1,486 @@
+use crate::utils::{setup, Fixture, REPAY};
+use fuels::accounts::ViewOnlyAccount;
+use fuels::types::{AssetId, ContractId, Identity};
+use test_harness::interface::account::{get_proposal_hash, sign_intent};
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::{
+    Action, AddLiquidityAction, BatchSwapStep, FlashLoopAction, LenderAction, MigrateAction,
+    Proposal, RemoveLiquidityAction, SwapPath, SwapPathList,
+};
+use test_harness::types::encode_mira_params;
+use test_harness::utils::common::get_lp_asset_id;
+
+/// Returns the Mira reserve of `asset` in the fixture pool
+pub async fn pool_reserve(f: &Fixture, asset: AssetId) -> u64 {
+    let metadata = pool_metadata(&f.amm.instance, f.pool_id)
+        .await
+        .value
+        .unwrap();
+    if asset == f.pool_id.0 {
+        metadata.reserve_0
+    } else {
+        metadata.reserve_1
+    }
+}
+
+pub fn flash_loop(f: &Fixture, open: bool, amount: u64, maximum_in: u64) -> FlashLoopAction {
+    FlashLoopAction {
+        lender_id: 1,
+        market: f.lender_id,
+        collateral_asset: f.collateral,
+        base_asset: f.base,
+        is_stable: false,
+        fee: f.swap_fee,
+        open,
+        amount,
+        maximum_in,
+        data: None,
+    }
+}
+
+pub fn migrate(
+    f: &Fixture,
+    target: ContractId,
+    collateral_amount: u64,
+    debt_amount: u64,
+) -> MigrateAction {
+    MigrateAction {
+        lender_id: 1,
+        market: f.lender_id,
+        collateral_asset: f.collateral,
+        base_asset: f.base,
+        collateral_amount,
+        debt_amount,
+        target,
+        flash_pool: f.pool_id,
+        fee: f.swap_fee,
+        data: None,
+    }
+}
+
+pub fn repay(f: &Fixture, amount: u64) -> LenderAction {
+    LenderAction {
+        lender_id: 1,
+        action_id: 3,
+        asset: f.base,
+        amount_in: amount,
+        amount_type_id: 1,
+        data: None,
+        market: f.lender_id,
+        additional_params: None,
+    }
+}
+
+/// Deposits `equity` collateral and flash loops `amount` collateral on top
+pub async fn open_flash_loop(f: &Fixture, equity: u64, amount: u64, maximum_in: u64) {
+    f.fund_account(f.collateral, equity).await;
+
+    let deposit = LenderAction {
+        lender_id: 1,
+        action_id: 0,
+        asset: f.collateral,
+        amount_in: equity,
+        amount_type_id: 1,
+        data: None,
+        market: f.lender_id,
+        additional_params: None,
+    };
+
+    f.account
+        .methods()
+        .compose(vec![
+            Action::Lending(deposit),
+            Action::FlashLoop(flash_loop(f, true, amount, maximum_in)),
+        ])
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn account_flash_loop_open() {
+    let f = setup().await;
+
+    let equity = 1_000;
+    let flash_amount = 2_000;
+
+    let base_reserve_before = pool_reserve(&f, f.base).await;
+
+    open_flash_loop(&f, equity, flash_amount, 2_100).await;
+
+    let base_reserve_after = pool_reserve(&f, f.base).await;
+    let (collateral, debt) = f.position().await;
+
+    // the flashed collateral is deposited on top of the equity
+    assert_eq!(collateral, equity + flash_amount);
+    // the borrowed base paid for the flash swap
+    assert_eq!(debt, base_reserve_after - base_reserve_before);
+    assert!(debt > flash_amount && debt <= 2_100);
+}
+
+#[tokio::test]
+async fn account_flash_loop_close_full_debt() {
+    let f = setup().await;
+
+    let equity = 1_000;
+    let flash_amount = 2_000;
+
+    open_flash_loop(&f, equity, flash_amount, 2_100).await;
+
+    let collateral_reserve_before = pool_reserve(&f, f.collateral).await;
+
+    // zero repays the full debt
+    f.account
+        .methods()
+        .compose(vec![Action::FlashLoop(flash_loop(&f, false, 0, 2_100))])
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await
+        .unwrap();
+
+    let collateral_reserve_after = pool_reserve(&f, f.collateral).await;
+    let (collateral, debt) = f.position().await;
+
+    assert_eq!(debt, 0);
+    // the withdrawn collateral paid for the flash swap
+    assert_eq!(
+        collateral,
+        equity + flash_amount - (collateral_reserve_after - collateral_reserve_before)
+    );
+}
+
+#[tokio::test]
+async fn operator_can_repay_within_limit() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+
+    let repay_amount = 500;
+    f.fund_account(f.base, repay_amount).await;
+
+    let operator = f.new_wallet().await;
+    f.approve_operator(&operator, REPAY, vec![(f.base, repay_amount)])
+        .await;
+
+    let (_, debt_before) = f.position().await;
+
+    f.account
+        .clone()
+        .with_account(operator.clone())
+        .methods()
+        .compose(vec![Action::Lending(repay(&f, repay_amount))])
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await
+        .unwrap();
+
+    let (_, debt_after) = f.position().await;
+    assert_eq!(debt_after, debt_before - repay_amount);
+
+    // the limit is used up
+    let limit = f
+        .factory
+        .methods()
+        .get_operator_limit(
+            f.account_id,
+            Identity::Address(operator.address().into()),
+            f.base,
+        )
+        .call()
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(limit, 0);
+}
+
+#[tokio::test]
+async fn relayer_can_execute_signed_intent() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+
+    let repay_amount = 500;
+    f.fund_account(f.base, repay_amount).await;
+
+    // the owner signs, a relayer submits
+    let actions = vec![Action::Lending(repay(&f, repay_amount))];
+    let nonce = 1;
+    let deadline = f
+        .wallet
+        .provider()
+        .unwrap()
+        .latest_block_height()
+        .await
+        .unwrap()
+        + 10;
+    let signature = sign_intent(&f.wallet, &actions, nonce, deadline, f.account_id).await;
+
+    let relayer = f.new_wallet().await;
+
+    let (_, debt_before) = f.position().await;
+
+    f.account
+        .clone()
+        .with_account(relayer)
+        .methods()
+        .execute_signed(actions, nonce, deadline, signature)
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await
+        .unwrap();
+
+    let (_, debt_after) = f.position().await;
+    assert_eq!(debt_after, debt_before - repay_amount);
+
+    let nonce_used = f
+        .factory
+        .methods()
+        .is_nonce_used(f.account_id, nonce)
+        .call()
+        .await
+        .unwrap()
+        .value;
+    assert!(nonce_used);
+}
+
+#[tokio::test]
+async fn shared_account_executes_approved_proposal() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+
+    let repay_amount = 500;
+    f.fund_account(f.base, repay_amount).await;
+
+    let member_1 = f.new_wallet().await;
+    let member_2 = f.new_wallet().await;
+    f.share_account(vec![&f.wallet, &member_1, &member_2], 2)
+        .await;
+
+    // the account is listed for every member
+    let contracts = f
+        .factory
+        .methods()
+        .get_user_contracts(Identity::Address(member_2.address().into()), 0, 10)
+        .call()
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(contracts, vec![f.account_id]);
+
+    let proposal = Proposal::Compose(vec![Action::Lending(repay(&f, repay_amount))]);
+    let nonce = 1;
+    let proposal_hash = get_proposal_hash(&proposal, nonce, f.account_id);
+
+    for member in [&f.wallet, &member_1] {
+        f.factory
+            .clone()
+            .with_account(member.clone())
+            .methods()
+            .approve_proposal(f.account_id, proposal_hash)
+            .call()
+            .await
+            .unwrap();
+    }
+
+    let (_, debt_before) = f.position().await;
+
+    // anyone can execute an approved proposal
+    f.factory
+        .clone()
+        .with_account(member_2)
+        .methods()
+        .execute_proposal(f.account_id, proposal, nonce)
+        .with_contract_ids(&f.factory_contract_ids())
+        .call()
+        .await
+        .unwrap();
+
+    let (_, debt_after) = f.position().await;
+    assert_eq!(debt_after, debt_before - repay_amount);
+}
+
+#[tokio::test]
+async fn shared_account_composes_with_signatures() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+
+    let repay_amount = 500;
+    f.fund_account(f.base, repay_amount).await;
+
+    let member_1 = f.new_wallet().await;
+    let member_2 = f.new_wallet().await;
+    f.share_account(vec![&f.wallet, &member_1, &member_2], 2)
+        .await;
+
+    let actions = vec![Action::Lending(repay(&f, repay_amount))];
+    let nonce = 1;
+    let deadline = f
+        .wallet
+        .provider()
+        .unwrap()
+        .latest_block_height()
+        .await
+        .unwrap()
+        + 10;
+    let signatures = vec![
+        sign_intent(&member_1, &actions, nonce, deadline, f.account_id).await,
+        sign_intent(&member_2, &actions, nonce, deadline, f.account_id).await,
+    ];
+
+    let (_, debt_before) = f.position().await;
+
+    f.factory
+        .methods()
+        .compose_signed(f.account_id, actions, nonce, deadline, signatures)
+        .with_contract_ids(&f.factory_contract_ids())
+        .call()
+        .await
+        .unwrap();
+
+    let (_, debt_after) = f.position().await;
+    assert_eq!(debt_after, debt_before - repay_amount);
+}
+
+#[tokio::test]
+async fn account_migrates_position_to_own_account() {
+    let f = setup().await;
+
+    open_flash_loop(&f, 1_000, 2_000, 2_100).await;
+    let (collateral, debt) = f.position().await;
+
+    let target = f
+        .new_account(Identity::Address(f.wallet.address().into()), [1u8; 32])
+        .await;
+
+    let mut contract_ids = f.contract_ids.clone();
+    contract_ids.push(target.into());
+
+    // move the full position, zero migrates the full debt
+    f.account
+        .methods()
+        .compose(vec![Action::Migrate(migrate(&f, target, collateral, 0))])
+        .with_contract_ids(&contract_ids)
+        .call()
+        .await
+        .unwrap();
+
+    assert_eq!(f.position().await, (0, 0));
+
+    // the target pays the flash fee on the debt
+    let flash_fee = (debt * f.swap_fee).div_ceil(10_000 - f.swap_fee);
+    assert_eq!(f.position_of(target).await, (collateral, debt + flash_fee));
+}
+
+#[tokio::test]
+async fn account_borrows_swaps_half_and_adds_liquidity() {
+    let f = setup().await;
+
+    let collateral_amount = 2_000;
+    let borrow_amount = 1_000;
+    f.fund_account(f.collateral, collateral_amount).await;
+
+    let account = Identity::ContractId(f.account_id);
+
+    let deposit = LenderAction {
+        lender_id: 1,
+        action_id: 0,
+        asset: f.collateral,
+        amount_in: collateral_amount,
+        amount_type_id: 1,
+        data: None,
+        market: f.lender_id,
+        additional_params: None,
+    };
+    let borrow = LenderAction {
+        lender_id: 1,
+        action_id: 1,
+        asset: f.base,
+        amount_in: borrow_amount,
+        amount_type_id: 1,
+        data: None,
+        market: f.lender_id,
+        additional_params: None,
+    };
+    let swap_half = SwapPathList {
+        paths: vec![SwapPath {
+            amount_in: borrow_amount / 2,
+            min_amount_out: 0,
+            transfer_in: true,
+            steps: vec![BatchSwapStep {
+                dex_id: 0,
+                asset_in: f.base,
+                asset_out: f.collateral,
+                receiver: account,
+                data: encode_mira_params(f.swap_fee, false),
+            }],
+        }],
+    };
+    // the swap output is used via the cached amount
+    let (amount_0_desired, amount_1_desired) = if f.pool_id.0 == f.base {
+        (borrow_amount / 2, 0)
+    } else {
+        (0, borrow_amount / 2)
+    };
+    let add_liquidity = AddLiquidityAction {
+        pool_id: f.pool_id,
+        amount_0_desired,
+        amount_1_desired,
+        amount_0_min: 0,
+        amount_1_min: 0,
+        receiver: account,
+    };
+
+    f.account
+        .methods()
+        .compose(vec![
+            Action::Lending(deposit),
+            Action::Lending(borrow),
+            Action::Swap(swap_half),
+            Action::AddLiquidity(add_liquidity),
+        ])
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await
+        .unwrap();
+
+    let lp_balance = f
+        .wallet
+        .provider()
+        .unwrap()
+        .get_contract_asset_balance(&f.account_id.into(), get_lp_asset_id(f.amm.id, &f.pool_id))
+        .await
+        .unwrap();
+
+    assert!(lp_balance > 0);
+    assert_eq!(f.position().await, (collateral_amount, borrow_amount));
+
+    // remove the liquidity again and repay with the received base
+    let remove_liquidity = RemoveLiquidityAction {
+        pool_id: f.pool_id,
+        liquidity: lp_balance,
+        amount_0_min: 0,
+        amount_1_min: 0,
+        receiver: account,
+        cache_amount_1: f.pool_id.1 == f.base,
+    };
+    let mut repay_received = repay(&f, 0);
+    repay_received.amount_type_id = 0;
+
+    f.account
+        .methods()
+        .compose(vec![
+            Action::RemoveLiquidity(remove_liquidity),
+            Action::Lending(repay_received),
+        ])
+        .with_contract_ids(&f.contract_ids)
+        .call()
+        .await
+        .unwrap();
+
+    let (collateral, debt) = f.position().await;
+    assert_eq!(collateral, collateral_amount);
+    assert!(debt < borrow_amount);
+}
@@ -0,0 +1,337 @@
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::accounts::{Account, ViewOnlyAccount};
+use fuels::prelude::{Bech32ContractId, TxPolicies, VariableOutputPolicy};
+use fuels::types::{AssetId, ContractId, Identity};
+use std::str::FromStr;
+use test_harness::data_structures::{MiraAMMContract, WalletAssetConfiguration};
+use test_harness::interface::account::{
+    deploy_account_factory_contract, deploy_account_logic_contract, deploy_account_proxy_contract,
+    deploy_beacon_contract, register_account,
+};
+use test_harness::interface::amm::{create_pool, fees, initialize_ownership};
+use test_harness::interface::mock::{
+    add_token, deploy_mock_basic_lender_contract, deploy_mock_token_contract, get_sub_id,
+    mint_tokens,
+};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::{
+    AccountFactory, AccountLogic, AddLiquidityScript, AddLiquidityScriptConfigurables,
+    MockBasicLender, OwnerSet,
+};
+use test_harness::paths::ADD_LIQUIDITY_SCRIPT_BINARY_PATH;
+use test_harness::setup::common::{deploy_amm, setup_wallet_and_provider};
+use test_harness::types::PoolId;
+use test_harness::utils::common::order_sub_ids;
+
+pub const POOL_LIQUIDITY: u64 = 1_000_000;
+pub const LENDER_LIQUIDITY: u64 = 1_000_000;
+// collateral factor of 0.8 scaled by 1e18
+pub const COLLATERAL_FACTOR: u64 = 800_000_000_000_000_000;
+
+// operator action flags
+pub const SWAP: u64 = 1;
+pub const REPAY: u64 = 16;
+pub const TRANSFER_TO_OWNER: u64 = 128;
+
+pub struct Fixture {
+    pub wallet: WalletUnlocked,
+    pub amm: MiraAMMContract,
+    // the account logic called through the registered proxy
+    pub account: AccountLogic<WalletUnlocked>,
+    pub account_id: ContractId,
+    pub factory: AccountFactory<WalletUnlocked>,
+    pub beacon_id: ContractId,
+    pub lender: MockBasicLender<WalletUnlocked>,
+    pub lender_id: ContractId,
+    // contracts touched by a compose call besides the proxy
+    pub contract_ids: Vec<Bech32ContractId>,
+    pub pool_id: PoolId,
+    pub base: AssetId,
+    pub collateral: AssetId,
+    pub swap_fee: u64,
+}
+
+impl Fixture {
+    /// Returns the (collateral, debt) of the account at the lender
+    pub async fn position(&self) -> (u64, u64) {
+        self.position_of(self.account_id).await
+    }
+
+    /// Returns the (collateral, debt) of `account` at the lender
+    pub async fn position_of(&self, account: ContractId) -> (u64, u64) {
+        let owner = Identity::ContractId(account);
+        let collateral = self
+            .lender
+            .methods()
+            .get_collateral(owner, self.collateral)
+            .call()
+            .await
+            .unwrap()
+            .value;
+        let (_, debt) = self
+            .lender
+            .methods()
+            .get_supply_borrow(owner)
+            .call()
+            .await
+            .unwrap()
+            .value;
+        (collateral, debt)
+    }
+
+    /// Returns a new wallet with gas coins
+    pub async fn new_wallet(&self) -> WalletUnlocked {
+        let provider = self.wallet.provider().unwrap();
+        let operator = WalletUnlocked::new_random(Some(provider.clone()));
+        self.wallet
+            .transfer(
+                operator.address(),
+                1_000_000,
+                *provider.base_asset_id(),
+                TxPolicies::default(),
+            )
+            .await
+            .unwrap();
+        operator
+    }
+
+    /// Approves `operator` for the next 100 blocks on the lender market
+    pub async fn approve_operator(
+        &self,
+        operator: &WalletUnlocked,
+        allowed_actions: u64,
+        limits: Vec<(AssetId, u64)>,
+    ) {
+        let provider = self.wallet.provider().unwrap();
+        let expiry = provider.latest_block_height().await.unwrap() + 100;
+        self.factory
+            .methods()
+            .approve_operator(
+                self.account_id,
+                Identity::Address(operator.address().into()),
+                expiry,
+                allowed_actions,
+                limits,
+                vec![self.lender_id],
+            )
+            .call()
+            .await
+            .unwrap();
+    }
+
+    /// Deploys and registers another account for `owner`
+    pub async fn new_account(&self, owner: Identity, salt: [u8; 32]) -> ContractId {
+        let (account_id, _, _) =
+            deploy_account_proxy_contract(&self.wallet, self.beacon_id, salt).await;
+        register_account(&self.factory, account_id, owner).await;
+        account_id
+    }
+
+    /// Shares the account with `owners`
+    pub async fn share_account(&self, owners: Vec<&WalletUnlocked>, threshold: u64) {
+        let owners = owners
+            .iter()
+            .map(|owner| Identity::Address(owner.address().into()))
+            .collect();
+        self.factory
+            .methods()
+            .set_owners(self.account_id, OwnerSet { owners, threshold })
+            .call()
+            .await
+            .unwrap();
+    }
+
+    /// Contracts touched when the factory calls the account
+    pub fn factory_contract_ids(&self) -> Vec<Bech32ContractId> {
+        let mut contract_ids = vec![self.account_id.into()];
+        contract_ids.extend(self.contract_ids.clone());
+        contract_ids
+    }
+
+    pub async fn fund_account(&self, asset: AssetId, amount: u64) {
+        self.wallet
+            .force_transfer_to_contract(
+                &self.account_id.into(),
+                amount,
+                asset,
+                TxPolicies::default(),
+            )
+            .await
+            .unwrap();
+    }
+}
+
+////////////////////////////////////////////////////
+// Deploy the account system with a registered account,
+// a Mira pool base-collateral and a lender that lends
+// base against collateral
+////////////////////////////////////////////////////
+pub async fn setup() -> Fixture {
+    let (wallet, _asset_ids, provider) =
+        setup_wallet_and_provider(&WalletAssetConfiguration::default()).await;
+
+    ////////////////////////////////////////////////////
+    // deploy mira v1
+    ////////////////////////////////////////////////////
+
+    let amm: MiraAMMContract = deploy_amm(&wallet).await;
+    initialize_ownership(&amm.instance, Identity::Address(wallet.address().into())).await;
+
+    ////////////////////////////////////////////////////
+    // deploy tokens and mint
+    ////////////////////////////////////////////////////
+
+    let (token_contract_id, token_contract) = deploy_mock_token_contract(&wallet).await;
+
+    let token_a_id = add_token(&token_contract, "TOKEN_A".to_string(), "TKA".to_string(), 9)
+        .await
+        .value;
+    let token_b_id = add_token(&token_contract, "TOKEN_B".to_string(), "TKB".to_string(), 9)
+        .await
+        .value;
+
+    let token_a_sub_id = get_sub_id(&token_contract, token_a_id).await.value.unwrap();
+    let token_b_sub_id = get_sub_id(&token_contract, token_b_id).await.value.unwrap();
+
+    mint_tokens(&token_contract, token_a_id, 1_000_000_000).await;
+    mint_tokens(&token_contract, token_b_id, 1_000_000_000).await;
+
+    let (base, collateral) = (token_a_id, token_b_id);
+
+    ////////////////////////////////////////////////////
+    // create dex pool and add liquidity
+    ////////////////////////////////////////////////////
+
+    let (sub_id_0, sub_id_1) =
+        order_sub_ids((token_a_id, token_b_id), (token_a_sub_id, token_b_sub_id));
+
+    let pool_id = create_pool(
+        &amm.instance,
+        &token_contract,
+        token_contract_id,
+        sub_id_0,
+        token_contract_id,
+        sub_id_1,
+        false,
+    )
+    .await
+    .value;
+
+    let deadline = provider.latest_block_height().await.unwrap() + 30;
+
+    let add_liquidity_script_configurables = AddLiquidityScriptConfigurables::default()
+        .with_MIRA_AMM_CONTRACT_ID(ContractId::from_str(&amm.id.to_string()).unwrap())
+        .unwrap();
+    let add_liquidity_script_instance =
+        AddLiquidityScript::new(wallet.clone(), ADD_LIQUIDITY_SCRIPT_BINARY_PATH)
+            .with_configurables(add_liquidity_script_configurables);
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &wallet,
+        &vec![(pool_id.0, POOL_LIQUIDITY), (pool_id.1, POOL_LIQUIDITY)],
+    )
+    .await;
+
+    add_liquidity_script_instance
+        .main(
+            pool_id,
+            POOL_LIQUIDITY,
+            POOL_LIQUIDITY,
+            0,
+            0,
+            wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+
+    let swap_fees = fees(&amm.instance).await.value;
+
+    ////////////////////////////////////////////////////
+    // deploy the account system
+    ////////////////////////////////////////////////////
+
+    let (beacon_id, beacon) = deploy_beacon_contract(&wallet).await;
+    beacon
+        .methods()
+        .initialize(Identity::Address(wallet.address().into()))
+        .call()
+        .await
+        .unwrap();
+
+    let (account_id, _, account_root) =
+        deploy_account_proxy_contract(&wallet, beacon_id, [0u8; 32]).await;
+    let (factory_id, factory) = deploy_account_factory_contract(&wallet, account_root).await;
+    let (logic_id, _) = deploy_account_logic_contract(&wallet, factory_id, amm.id).await;
+
+    beacon
+        .methods()
+        .set_beacon_target(logic_id)
+        .call()
+        .await
+        .unwrap();
+
+    register_account(
+        &factory,
+        account_id,
+        Identity::Address(wallet.address().into()),
+    )
+    .await;
+
+    ////////////////////////////////////////////////////
+    // deploy lender and provide base liquidity
+    ////////////////////////////////////////////////////
+
+    let (lender_id, lender) = deploy_mock_basic_lender_contract(&wallet).await;
+    lender
+        .methods()
+        .mock_set_base_asset(base)
+        .call()
+        .await
+        .unwrap();
+    lender
+        .methods()
+        .mock_set_collateral_factor(collateral, COLLATERAL_FACTOR)
+        .call()
+        .await
+        .unwrap();
+    wallet
+        .force_transfer_to_contract(
+            &lender_id.into(),
+            LENDER_LIQUIDITY,
+            base,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    let account = AccountLogic::new(account_id, wallet.clone());
+    let amm_id = amm.id;
+
+    Fixture {
+        wallet,
+        amm,
+        account,
+        account_id,
+        factory,
+        beacon_id,
+        lender,
+        lender_id,
+        contract_ids: vec![
+            logic_id.into(),
+            beacon_id.into(),
+            factory_id.into(),
+            amm_id.into(),
+            lender_id.into(),
+        ],
+        pool_id,
+        base,
+        collateral,
+        swap_fee: swap_fees.0,
+    }
+}
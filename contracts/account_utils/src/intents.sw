@@ -0,0 +1,31 @@
+library;
+
+use ::structs::Action;
+use core::codec::encode;
+use std::{
+    bytes::Bytes,
+    bytes_conversions::b256::*,
+    bytes_conversions::u32::*,
+    bytes_conversions::u64::*,
+    hash::*,
+};
+
+// the intent hash is the sha256 hash of the packed
+// account address, nonce and deadline, followed by the encoded actions
+pub fn compute_intent_hash(
+    actions: Vec<Action>,
+    nonce: u64,
+    deadline: u32,
+    account: b256,
+) -> b256 {
+    sha256(pack_intent(actions, nonce, deadline, account))
+}
+
+pub fn pack_intent(actions: Vec<Action>, nonce: u64, deadline: u32, account: b256) -> Bytes {
+    let mut encoded_intent: Bytes = account.to_be_bytes();
+    encoded_intent.append(nonce.to_be_bytes());
+    encoded_intent.append(deadline.to_be_bytes());
+    // the actions are appended in their abi encoding
+    encoded_intent.append(Bytes::from(encode(actions)));
+    encoded_intent
+}
@@ -1,13 +1,23 @@
 library;
 
 pub mod structs;
+pub mod operators;
+pub mod intents;
+pub mod owners;
 use structs::Action;
+use std::b512::B512;
 
 /// the account is stateless
 /// ownership
 abi AccountLogic {
     #[payable, storage(write)]
     fn compose(actions: Vec<Action>);
+
+    /// executes actions signed by the owner
+    /// can be submitted by anyone before `deadline` (block height)
+    /// each `nonce` can only be used once
+    #[storage(write)]
+    fn execute_signed(actions: Vec<Action>, nonce: u64, deadline: u32, signature: B512);
 }
 
 abi ExecutionValidation {
@@ -16,3 +26,30 @@ abi ExecutionValidation {
     #[storage(read)]
     fn can_call(_contract: ContractId, _caller: Identity) -> bool;
 }
+
+abi OwnershipValidation {
+    /// whether two accounts are owned by the same single owner
+    #[storage(read)]
+    fn have_same_owner(_contract: ContractId, _other: ContractId) -> bool;
+}
+
+abi OperatorValidation {
+    /// called by the implementation for callers that are not the owner
+    /// reverts if `_operator` is not approved for the actions
+    /// and consumes the operator's per-asset limits
+    /// swap steps may only pay to the account or to `dex_contracts`
+    #[storage(read, write)]
+    fn use_operator(
+        _contract: ContractId,
+        _operator: Identity,
+        actions: Vec<Action>,
+        dex_contracts: Vec<ContractId>,
+    );
+}
+
+abi NonceValidation {
+    /// called by the implementation
+    /// reverts if `_nonce` was already used or cancelled for `_contract`
+    #[storage(read, write)]
+    fn use_nonce(_contract: ContractId, _nonce: u64);
+}
@@ -0,0 +1,68 @@
+library;
+
+use ::structs::{Action, LenderActionType};
+use executor::{BatchSwapStep, get_mira_like_pool};
+
+////////////////////////////////////////////////////
+// Operator action flags
+////////////////////////////////////////////////////
+pub const SWAP: u64 = 1;
+pub const DEPOSIT: u64 = 2;
+pub const BORROW: u64 = 4;
+pub const WITHDRAW: u64 = 8;
+pub const REPAY: u64 = 16;
+pub const DEPOSIT_BASE: u64 = 32;
+pub const WITHDRAW_BASE: u64 = 64;
+/// transfers are only allowed to the account owner
+pub const TRANSFER_TO_OWNER: u64 = 128;
+pub const FLASH_LOOP: u64 = 256;
+/// liquidity is only added and removed for the account itself
+pub const ADD_LIQUIDITY: u64 = 512;
+pub const REMOVE_LIQUIDITY: u64 = 1024;
+
+/// the dex id of Mira v1 in the executor
+const MIRA_V1_ID: u64 = 0;
+
+/// Approval of an operator by the account owner
+/// `allowed_actions` is a bitmap of the action flags
+/// `approval_id` scopes the per-asset limits and the markets to this approval
+pub struct OperatorPermissions {
+    /// block height from which the approval is invalid
+    pub expiry: u32,
+    pub allowed_actions: u64,
+    pub approval_id: u64,
+}
+
+/// Returns whether the action flags in `allowed_actions` permit `action`
+/// health checks only constrain the outcome and are always allowed
+pub fn is_action_allowed(allowed_actions: u64, action: Action) -> bool {
+    let flag = match action {
+        Action::Swap(_) => SWAP,
+        Action::Lending(lender_action) => {
+            match LenderActionType::from_u16(lender_action.action_id) {
+                Some(LenderActionType::Deposit) => DEPOSIT,
+                Some(LenderActionType::Borrow) => BORROW,
+                Some(LenderActionType::Withdraw) => WITHDRAW,
+                Some(LenderActionType::Repay) => REPAY,
+                Some(LenderActionType::DepositBase) => DEPOSIT_BASE,
+                Some(LenderActionType::WithdrawBase) => WITHDRAW_BASE,
+                None => return false,
+            }
+        },
+        Action::Transfer(_) => TRANSFER_TO_OWNER,
+        Action::AssertHealth(_) => return true,
+        Action::FlashLoop(_) => FLASH_LOOP,
+        // moving debt to other accounts is reserved to owners
+        Action::Migrate(_) => return false,
+        Action::AddLiquidity(_) => ADD_LIQUIDITY,
+        Action::RemoveLiquidity(_) => REMOVE_LIQUIDITY,
+    };
+    allowed_actions & flag != 0
+}
+
+/// Returns whether operators can use a swap step, which are Mira v1 steps
+/// without a DEX address. Forks named by address and order book steps
+/// pay to counterparties chosen in the step data, so these are reserved to owners
+pub fn is_operator_step(step: BatchSwapStep) -> bool {
+    step.dex_id == MIRA_V1_ID && get_mira_like_pool(step.data, ContractId::zero()) == ContractId::zero()
+}
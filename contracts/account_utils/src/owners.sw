@@ -0,0 +1,32 @@
+library;
+
+use ::structs::Action;
+use core::codec::encode;
+use std::{bytes::Bytes, bytes_conversions::b256::*, bytes_conversions::u64::*, hash::*};
+
+/// Owners of a shared account
+/// `threshold` of the owners have to approve a proposal or sign an intent
+pub struct OwnerSet {
+    pub owners: Vec<Identity>,
+    pub threshold: u64,
+}
+
+/// Operations that the owners of a shared account vote on
+pub enum Proposal {
+    Compose: Vec<Action>,
+    SetOwners: OwnerSet,
+}
+
+// the proposal hash is the sha256 hash of the packed
+// account address and nonce, followed by the encoded proposal
+pub fn compute_proposal_hash(proposal: Proposal, nonce: u64, account: b256) -> b256 {
+    sha256(pack_proposal(proposal, nonce, account))
+}
+
+pub fn pack_proposal(proposal: Proposal, nonce: u64, account: b256) -> Bytes {
+    let mut encoded_proposal: Bytes = account.to_be_bytes();
+    encoded_proposal.append(nonce.to_be_bytes());
+    // the proposal is appended in its abi encoding
+    encoded_proposal.append(Bytes::from(encode(proposal)));
+    encoded_proposal
+}
@@ -0,0 +1,177 @@
+contract;
+
+use basic_lender_abi::BasicLender;
+use std::{
+    asset::transfer,
+    auth::msg_sender,
+    call_frames::msg_asset_id,
+    context::msg_amount,
+    hash::Hash,
+};
+
+abi MockBasicLender {
+    #[storage(write)]
+    fn mock_set_base_asset(base: AssetId);
+
+    /// collateral is valued 1:1 in base, the factor is scaled by 1e18
+    #[storage(write)]
+    fn mock_set_collateral_factor(asset_id: AssetId, factor: u64);
+}
+
+const FACTOR_SCALE: u64 = 1_000_000_000_000_000_000;
+
+storage {
+    user_collateral: StorageMap<(Identity, AssetId), u64> = StorageMap {},
+    user_supply: StorageMap<Identity, u64> = StorageMap {},
+    user_borrow: StorageMap<Identity, u64> = StorageMap {},
+    // collateral value weighted by the factor at deposit
+    user_borrow_capacity: StorageMap<Identity, u64> = StorageMap {},
+    collateral_factors: StorageMap<AssetId, u64> = StorageMap {},
+    base_asset_id: Option<AssetId> = Option::None,
+}
+
+#[storage(read)]
+fn base_asset() -> AssetId {
+    let base_asset = storage.base_asset_id.try_read().unwrap();
+    require(base_asset.is_some(), "base_asset not initialized");
+    base_asset.unwrap()
+}
+
+#[storage(read)]
+fn weighted_collateral(asset_id: AssetId, amount: u64) -> u64 {
+    let factor = storage.collateral_factors.get(asset_id).try_read().unwrap_or(0);
+    u64::try_from(amount.as_u256() * factor.as_u256() / FACTOR_SCALE.as_u256()).unwrap()
+}
+
+impl MockBasicLender for Contract {
+    #[storage(write)]
+    fn mock_set_base_asset(base: AssetId) {
+        storage.base_asset_id.write(Some(base));
+    }
+
+    #[storage(write)]
+    fn mock_set_collateral_factor(asset_id: AssetId, factor: u64) {
+        storage.collateral_factors.insert(asset_id, factor);
+    }
+}
+
+impl BasicLender for Contract {
+    #[payable, storage(write)]
+    fn supply_collateral() {
+        let asset_id = msg_asset_id();
+        let sender = msg_sender().unwrap();
+        require(asset_id != base_asset(), "Invalid asset");
+
+        let amount = msg_amount();
+        let current_balance = storage.user_collateral.get((sender, asset_id)).try_read().unwrap_or(0);
+        storage.user_collateral.insert((sender, asset_id), current_balance + amount);
+
+        let capacity = storage.user_borrow_capacity.get(sender).try_read().unwrap_or(0);
+        storage.user_borrow_capacity.insert(sender, capacity + weighted_collateral(asset_id, amount));
+    }
+
+    #[storage(write)]
+    fn withdraw_collateral(asset_id: AssetId, amount: u64) {
+        let sender = msg_sender().unwrap();
+
+        let current_balance = storage.user_collateral.get((sender, asset_id)).try_read().unwrap_or(0);
+        require(current_balance >= amount, "Insufficient collateral balance");
+
+        storage.user_collateral.insert((sender, asset_id), current_balance - amount);
+
+        let capacity = storage.user_borrow_capacity.get(sender).try_read().unwrap_or(0);
+        let weighted = weighted_collateral(asset_id, amount);
+        let new_capacity = if capacity > weighted { capacity - weighted } else { 0 };
+        let current_borrow = storage.user_borrow.get(sender).try_read().unwrap_or(0);
+        require(new_capacity >= current_borrow, "Insufficient collateral");
+        storage.user_borrow_capacity.insert(sender, new_capacity);
+
+        transfer(sender, asset_id, amount);
+    }
+
+    #[payable, storage(write)]
+    fn supply() {
+        let sender = msg_sender().unwrap();
+        require(msg_asset_id() == base_asset(), "Invalid asset");
+
+        let current_borrow = storage.user_borrow.get(sender).try_read().unwrap_or(0);
+        require(current_borrow == 0, "Outstanding borrow");
+
+        let current_supply = storage.user_supply.get(sender).try_read().unwrap_or(0);
+        storage.user_supply.insert(sender, current_supply + msg_amount());
+    }
+
+    #[storage(write)]
+    fn withdraw(amount: u64) {
+        let sender = msg_sender().unwrap();
+
+        let current_supply = storage.user_supply.get(sender).try_read().unwrap_or(0);
+        require(current_supply >= amount, "Insufficient supply balance");
+
+        storage.user_supply.insert(sender, current_supply - amount);
+
+        transfer(sender, base_asset(), amount);
+    }
+
+    #[storage(write)]
+    fn borrow(amount: u64) {
+        let sender = msg_sender().unwrap();
+
+        let current_supply = storage.user_supply.get(sender).try_read().unwrap_or(0);
+        require(current_supply == 0, "Outstanding supply");
+
+        let current_borrow = storage.user_borrow.get(sender).try_read().unwrap_or(0);
+        let capacity = storage.user_borrow_capacity.get(sender).try_read().unwrap_or(0);
+        require(capacity >= current_borrow + amount, "Insufficient collateral");
+        storage.user_borrow.insert(sender, current_borrow + amount);
+
+        transfer(sender, base_asset(), amount);
+    }
+
+    #[payable, storage(write)]
+    fn repay() {
+        let sender = msg_sender().unwrap();
+        require(msg_asset_id() == base_asset(), "Invalid asset");
+
+        let amount = msg_amount();
+        let current_borrow = storage.user_borrow.get(sender).try_read().unwrap_or(0);
+        require(current_borrow >= amount, "Repay exceeds borrow");
+
+        storage.user_borrow.insert(sender, current_borrow - amount);
+    }
+
+    #[storage(read)]
+    fn get_base_asset() -> AssetId {
+        base_asset()
+    }
+
+    #[storage(read)]
+    fn get_collateral(account: Identity, asset_id: AssetId) -> u64 {
+        storage.user_collateral.get((account, asset_id)).try_read().unwrap_or(0)
+    }
+
+    #[storage(read)]
+    fn get_supply_borrow(account: Identity) -> (u64, u64) {
+        (
+            storage.user_supply.get(account).try_read().unwrap_or(0),
+            storage.user_borrow.get(account).try_read().unwrap_or(0),
+        )
+    }
+
+    #[storage(read)]
+    fn available_to_borrow(account: Identity) -> u64 {
+        let capacity = storage.user_borrow_capacity.get(account).try_read().unwrap_or(0);
+        let current_borrow = storage.user_borrow.get(account).try_read().unwrap_or(0);
+        if capacity > current_borrow {
+            capacity - current_borrow
+        } else {
+            0
+        }
+    }
+
+    #[storage(read)]
+    fn is_liquidatable(account: Identity) -> bool {
+        let capacity = storage.user_borrow_capacity.get(account).try_read().unwrap_or(0);
+        storage.user_borrow.get(account).try_read().unwrap_or(0) > capacity
+    }
+}
@@ -0,0 +1,21 @@
+contract;
+
+use std::{b512::B512, ecr::{ec_recover_address, EcRecoverError,},};
+use order_utils::ISignatureValidator;
+
+/// the key that is allowed to sign on behalf of this contract
+configurable {
+    SIGNER: b256 = b256::zero(),
+}
+
+/// A contract maker that accepts orders signed by `SIGNER`
+impl ISignatureValidator for Contract {
+    #[storage(read)]
+    fn is_valid_signature(order_hash: b256, signature: B512) -> bool {
+        let result_address: Result<Address, EcRecoverError> = ec_recover_address(signature, order_hash);
+        match result_address {
+            Ok(signer) => signer.bits() == SIGNER,
+            Err(_) => false,
+        }
+    }
+}
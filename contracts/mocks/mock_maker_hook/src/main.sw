@@ -0,0 +1,77 @@
+contract;
+
+use std::{asset::transfer, b512::B512, ecr::{ec_recover_address, EcRecoverError,},};
+use order_utils::{IMakerHook, ISignatureValidator};
+
+configurable {
+    ONE_DELTA_ORDERS_CONTRACT_ID: ContractId = ContractId::from(0xf6caa75386fe9ba4da15b82723ecffb0d56b28ae7ece396b15c5650b605359ac),
+    /// the key that is allowed to sign on behalf of this contract
+    SIGNER: b256 = b256::zero(),
+}
+
+storage {
+    /// the number of pre-fill calls
+    before_fill_count: u64 = 0,
+    /// the number of post-fill calls
+    after_fill_count: u64 = 0,
+}
+
+abi MockMakerHookInfo {
+    #[storage(read)]
+    fn get_call_counts() -> (u64, u64);
+}
+
+/// A contract maker that accepts orders signed by `SIGNER`
+impl ISignatureValidator for Contract {
+    #[storage(read)]
+    fn is_valid_signature(order_hash: b256, signature: B512) -> bool {
+        let result_address: Result<Address, EcRecoverError> = ec_recover_address(signature, order_hash);
+        match result_address {
+            Ok(signer) => signer.bits() == SIGNER,
+            Err(_) => false,
+        }
+    }
+}
+
+/// A maker hook that funds fills from its own balance
+impl IMakerHook for Contract {
+    #[storage(read, write)]
+    fn before_fill(
+        order_hash: b256,
+        maker: b256,
+        maker_asset: b256,
+        taker_asset: b256,
+        maker_amount: u64,
+        taker_amount: u64,
+    ) {
+        transfer(
+            Identity::ContractId(ONE_DELTA_ORDERS_CONTRACT_ID),
+            AssetId::from(maker_asset),
+            maker_amount,
+        );
+        storage
+            .before_fill_count
+            .write(storage.before_fill_count.read() + 1);
+    }
+
+    #[storage(read, write)]
+    fn after_fill(
+        order_hash: b256,
+        maker: b256,
+        maker_asset: b256,
+        taker_asset: b256,
+        maker_amount: u64,
+        taker_amount: u64,
+    ) {
+        storage
+            .after_fill_count
+            .write(storage.after_fill_count.read() + 1);
+    }
+}
+
+impl MockMakerHookInfo for Contract {
+    #[storage(read)]
+    fn get_call_counts() -> (u64, u64) {
+        (storage.before_fill_count.read(), storage.after_fill_count.read())
+    }
+}
@@ -21,6 +21,13 @@ storage {
     user_base: StorageMap<Identity, u64> = StorageMap {},
     user_base_borrow: StorageMap<Identity, u64> = StorageMap {},
     base_asset_id: Option<AssetId> = Option::None,
+    prices: StorageMap<PriceFeedId, Price> = StorageMap {},
+}
+
+abi MockPriceOracle {
+    // set the price returned by `get_price`
+    #[storage(write)]
+    fn mock_set_price(price_feed_id: PriceFeedId, price: Price);
 }
 
 impl Market for Contract {
@@ -282,12 +289,12 @@ impl Market for Contract {
 
     #[storage(read)]
     fn get_price(price_feed_id: PriceFeedId) -> Price {
-        Price {
+        storage.prices.get(price_feed_id).try_read().unwrap_or(Price {
             confidence: 0,
             exponent: 0,
             price: 0,
             publish_time: 0,
-        }
+        })
     }
 
     #[storage(read)]
@@ -311,3 +318,10 @@ impl Market for Contract {
     fn renounce_ownership() {
     }
 }
+
+impl MockPriceOracle for Contract {
+    #[storage(write)]
+    fn mock_set_price(price_feed_id: PriceFeedId, price: Price) {
+        storage.prices.insert(price_feed_id, price);
+    }
+}
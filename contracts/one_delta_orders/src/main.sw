@@ -3,8 +3,12 @@ contract;
 use std::{
     asset::transfer,
     b512::B512,
-    block::height,
+    block::{
+        height,
+        timestamp,
+    },
     bytes::Bytes,
+    bytes_conversions::b256::*,
     call_frames::msg_asset_id,
     context::this_balance,
     ecr::{
@@ -13,26 +17,58 @@ use std::{
     },
     hash::Hash,
     revert::require,
+    storage::storage_vec::*,
 };
 use sway_libs::reentrancy::reentrancy_guard;
+use market_abi::Market;
 use order_utils::{
+    CANCEL_ORDER_OPERATION,
+    compute_conditional_order_hash,
+    compute_operation_hash,
     compute_order_hash,
     get_expiry,
+    has_maker_hook,
     IFlashCallback,
+    IMakerHook,
+    INVALIDATE_NONCE_OPERATION,
     is_contract_receiver,
+    is_condition_met,
+    is_conditional,
+    is_contract_signer,
+    is_in_delegation_scope,
+    ISignatureValidator,
+    MAX_PRICE_EXPONENT,
     min64,
     no_partial_fill,
     OneDeltaOrders,
+    pack_delegation,
+    pack_invalidate_nonce,
+    pack_withdraw,
+    REGISTER_DELEGATE_OPERATION,
     structs::{
         CancelEvent,
         CancelPairEvent,
         DepositEvent,
         Order,
         OrderFillEvent,
+        OwnershipTransferEvent,
+        PairPauseEvent,
+        PauseEvent,
+        PriceCondition,
+        SignerDelegateEvent,
+        SignerDelegation,
         WithdrawEvent,
     },
+    WITHDRAW_OPERATION,
 };
 
+configurable {
+    // maximum age of an oracle price in seconds for conditional orders
+    MAX_PRICE_AGE: u64 = 60,
+    // the only identity that can call `initialize`, set at deployment
+    INITIAL_OWNER: Identity = Identity::Address(Address::zero()),
+}
+
 storage {
     // maker -> maker_asset -> taker_asset -> nonce_value
     nonces: StorageMap<b256, StorageMap<b256, StorageMap<b256, u64>>> = StorageMap {},
@@ -40,16 +76,31 @@ storage {
     order_hash_to_filled_amount: StorageMap<b256, (bool, u64)> = StorageMap {},
     // owner -> assetId -> balance
     maker_balances: StorageMap<b256, StorageMap<b256, u64>> = StorageMap {},
+    // maker -> assets that the maker ever held a balance of
+    maker_assets: StorageMap<b256, StorageVec<b256>> = StorageMap {},
+    // maker -> assetId -> whether the asset is in the maker's asset list
+    maker_asset_registered: StorageMap<b256, StorageMap<b256, bool>> = StorageMap {},
     // assetId -> contract balance
     balances: StorageMap<b256, u64> = StorageMap {},
-    // signer -> signer on behalf
-    order_signer_registry: StorageMap<b256, StorageMap<b256, bool>> = StorageMap {},
+    // signer -> signer on behalf -> scope of the delegation
+    order_signer_registry: StorageMap<b256, StorageMap<b256, SignerDelegation>> = StorageMap {},
     // taker_asset_lock
     taker_asset_locked: StorageMap<b256, bool> = StorageMap {},
+    // maker -> nonce for the next signed operation
+    operation_nonces: StorageMap<b256, u64> = StorageMap {},
+    // the guardian that can pause fills
+    owner: Identity = Identity::Address(Address::zero()),
+    // the initialization flag
+    initialized: bool = false,
+    // global pause flag for fills
+    paused: bool = false,
+    // maker_asset -> taker_asset -> pause flag for fills
+    paused_pairs: StorageMap<b256, StorageMap<b256, bool>> = StorageMap {},
 }
 
 // constants
 const ZERO_B256 = b256::zero();
+const MAX_EXPIRY: u32 = 4294967295u32;
 
 // error codes
 const NO_ERROR = 0u64;
@@ -68,6 +119,16 @@ const BALANCE_VIOLATION = 12u64;
 const MAKER_RECEIVER_CANNOT_BE_THIS = 13u64;
 const REENTER_TAKER_ASSET = 14u64;
 const SIGNER_NOT_RECOVERED = 15u64;
+const INVALID_OPERATION_NONCE = 16u64;
+const INVALID_OPERATION_SIGNATURE = 17u64;
+const INVALID_MAKER_HOOK = 18u64;
+const MISSING_PRICE_CONDITION = 19u64;
+const PRICE_CONDITION_NOT_MET = 20u64;
+const NOT_OWNER = 21u64;
+const ALREADY_INITIALIZED = 22u64;
+const PAUSED = 23u64;
+const INVALID_PRICE = 24u64;
+const STALE_PRICE = 25u64;
 
 impl OneDeltaOrders for Contract {
     /*
@@ -88,142 +149,35 @@ impl OneDeltaOrders for Contract {
         taker_receiver: Identity,
         data: Option<Bytes>,
     ) -> (u64, u64) {
-        // reentrancy check for taker_asset
-        if storage.taker_asset_locked.get(order.taker_asset).try_read().unwrap_or(false)
-        {
-            revert(REENTER_TAKER_ASSET);
-        }
-        // lock taker_asset
-        storage.taker_asset_locked.insert(order.taker_asset, true);
-
-        // validate order
-        let (order_hash, error, taker_asset_already_filled_amount) = validate_order_internal(order, order_signature);
-
-        // revert if error in validation
-        if error != 0u64 {
-            revert(error);
-        }
-
-        // get stored maker_asset balance
-        let maker_maker_asset_balance = storage.maker_balances.get(order.maker).get(order.maker_asset).try_read().unwrap_or(0u64);
-
-        // compute fill amounts
-        let (maker_filled_amount, taker_filled_amount) = compute_fill_amounts(
+        fill_internal(
+            order,
+            None,
+            order_signature,
             taker_fill_amount,
-            taker_asset_already_filled_amount,
-            order.maker_amount,
-            order.taker_amount,
-        );
-
-        // if any of the amounts is zero, we revert
-        // this is to ensure that no msg_amount is lost 
-        // attempting to fill an empty order
-        require(
-            maker_filled_amount != 0u64 && taker_filled_amount != 0u64,
-            ZERO_FILL_AMOUNT,
-        );
-
-        // make sure that the maker balance is high enough
-        if (maker_filled_amount > maker_maker_asset_balance) {
-            revert(MAKER_BALANCE_TOO_LOW)
-        }
-
-        // prevent partial fills if specified as such
-        if no_partial_fill(order.maker_traits) {
-            if taker_filled_amount < order.taker_amount {
-                revert(NO_PARTIAL_FILL);
-            }
-        }
-
-        // reduce maker's recods for maker_asset
-        // this must be done before the flash call
-        update_maker_maker_asset_balances(
-            order.maker_asset,
-            maker_maker_asset_balance,
-            maker_filled_amount,
-            order.maker,
-        );
-
-        // register the order as filed
-        update_remaining_fill_amount(
-            order_hash,
-            taker_asset_already_filled_amount,
-            taker_filled_amount,
-        );
-
-        // reduce the total maker balance
-        // we account for the fact that we might transfer back
-        // to this contract
-        transfer_maker_asset_out(order.maker_asset, maker_filled_amount, taker_receiver);
-
-        // flash callback if the data is specified
-        // note that we already validated the maker_asset
-        // all validations for the taker_asset happen after the callback
-        // That is why we check against reentrancy for the taker_asset
-        if let Some(d) = data {
-            abi(IFlashCallback, msg_sender()
-                .unwrap()
-                .bits())
-                .flash(
-                    order.maker_asset,
-                    order.taker_asset,
-                    maker_filled_amount,
-                    taker_filled_amount,
-                    d,
-                );
-        }
-
-        // fetch the real taker asset balance
-        let real_taker_asset_balance = this_balance(AssetId::from(order.taker_asset));
-        // this internal balance is unadjusted for the amount received 
-        let taker_asset_accounting_balance = get_total_asset_balance(order.taker_asset);
-        // the funds received are real balance minus accounting balance
-        let taker_fill_amount_received = real_taker_asset_balance - taker_asset_accounting_balance;
-
-        // validate that we received enough
-        // note that a too high amount will be consumed by the maker
-        if taker_fill_amount_received < taker_filled_amount {
-            revert(INSUFFICIENT_TAKER_AMOUNT_RECEIVED);
-        }
-
-        // if the maker receiver is not provided we default to maker 
-        let maker_receiver = if order.maker_receiver != ZERO_B256 {
-            order.maker_receiver
-        } else {
-            order.maker
-        };
-
-        // if the maker_receiver is defined, we send the funds to
-        // the provided address
-        transfer(
-            if is_contract_receiver(order.maker_traits) {
-                // we enforce that the maker_receiver cannot be this contract
-                // this is the default behaviour which assumes maker_receiver==0
-                require(
-                    maker_receiver != ContractId::this()
-                        .bits(),
-                    MAKER_RECEIVER_CANNOT_BE_THIS,
-                );
-                Identity::ContractId(ContractId::from(maker_receiver))
-            } else {
-                Identity::Address(Address::from(maker_receiver))
-            },
-            AssetId::from(order.taker_asset),
-            taker_filled_amount,
-        );
-
-        // log the fill info and hash
-        log(OrderFillEvent {
-            order_hash,
-            taker_filled_amount,
-            maker_filled_amount,
-        });
-
-        // unlock taker_asset
-        storage.taker_asset_locked.insert(order.taker_asset, false);
+            taker_receiver,
+            data,
+        )
+    }
 
-        // return filled amounts
-        (taker_filled_amount, maker_filled_amount)
+    // Fills a conditional order in the same way as `fill`
+    // the order is only fillable once the oracle price meets the condition
+    #[storage(write, read), payable]
+    fn fill_conditional(
+        order: Order,
+        condition: PriceCondition,
+        order_signature: B512,
+        taker_fill_amount: u64,
+        taker_receiver: Identity,
+        data: Option<Bytes>,
+    ) -> (u64, u64) {
+        fill_internal(
+            order,
+            Some(condition),
+            order_signature,
+            taker_fill_amount,
+            taker_receiver,
+            data,
+        )
     }
 
     // deposit assets to the contract
@@ -250,6 +204,7 @@ impl OneDeltaOrders for Contract {
                 .maker_balances
                 .get(fund_recipient)
                 .insert(asset, fund_recipient_asset_balance + deposit_amount);
+            register_maker_asset(fund_recipient, asset);
 
             // update total balance
             storage.balances.insert(asset, total_asset_balance_real);
@@ -269,43 +224,32 @@ impl OneDeltaOrders for Contract {
     fn withdraw(asset: b256, amount: u64, receiver: Identity) {
         reentrancy_guard();
 
-        // convert to bits for maps
-        let owner_bits = msg_sender().unwrap().bits();
-        let owner_asset_balance = storage.maker_balances.get(owner_bits).get(asset).try_read().unwrap_or(0u64);
-
-        // require that the owner has enough balance
-        if owner_asset_balance < amount {
-            revert(WITHDRAW_TOO_MUCH);
-        }
-
-        // update balance as reduced
-        storage
-            .maker_balances
-            .get(owner_bits)
-            .insert(asset, owner_asset_balance - amount);
+        withdraw_internal(msg_sender().unwrap().bits(), asset, amount, receiver);
+    }
 
-        // we sync the overall balance by 
-        let total_balance_before = storage.balances.get(asset).try_read().unwrap_or(0u64);
+    // Withdraw multiple assets at once
+    #[storage(write, read)]
+    fn withdraw_many(withdrawals: Vec<(b256, u64)>, receiver: Identity) {
+        reentrancy_guard();
 
-        // ensure that in no way there are withdrawals
-        // that violate the total balance post transfer
-        let new_balance = total_balance_before - amount;
-        if new_balance > this_balance(AssetId::from(asset)) {
-            revert(BALANCE_VIOLATION);
+        let owner_bits = msg_sender().unwrap().bits();
+        for withdrawal in withdrawals.iter() {
+            withdraw_internal(owner_bits, withdrawal.0, withdrawal.1, receiver);
         }
+    }
 
-        // update total balance
-        storage.balances.insert(asset, new_balance);
-
-        // finalize transfer from this to owner
-        transfer(receiver, AssetId::from(asset), amount);
+    // Withdraw the full balance of every asset the maker holds,
+    // returns the withdrawn amounts
+    #[storage(write, read)]
+    fn withdraw_all(receiver: Identity) -> Vec<(b256, u64)> {
+        reentrancy_guard();
 
-        // log the withdrawal
-        log(WithdrawEvent {
-            maker: owner_bits,
-            asset,
-            amount,
-        });
+        let owner_bits = msg_sender().unwrap().bits();
+        let withdrawals = get_maker_balances_internal(owner_bits, 0, storage.maker_assets.get(owner_bits).len());
+        for withdrawal in withdrawals.iter() {
+            withdraw_internal(owner_bits, withdrawal.0, withdrawal.1, receiver);
+        }
+        withdrawals
     }
 
     // Makers can emergency-cancel orders by setting the nonce to a higher value than
@@ -314,26 +258,14 @@ impl OneDeltaOrders for Contract {
     fn invalidate_nonce(maker_asset: b256, taker_asset: b256, new_nonce: u64) {
         reentrancy_guard();
 
-        let maker = msg_sender().unwrap().bits();
-        // get current maker nonce
-        let mut old_nonce: u64 = storage.nonces.get(maker).get(maker_asset).get(taker_asset).try_read().unwrap_or(0u64);
-
-        // valdiate nonce
-        require(new_nonce > old_nonce, INVALID_NONCE);
-
-        // set new nonce
-        storage
-            .nonces
-            .get(maker) // maker
-            .get(maker_asset) // maker_asset
-            .insert(taker_asset, new_nonce);
-
-        log(CancelPairEvent {
-            maker,
+        invalidate_nonce_internal(
+            msg_sender()
+                .unwrap()
+                .bits(),
             maker_asset,
             taker_asset,
-            nonce: new_nonce,
-        });
+            new_nonce,
+        );
     }
 
     // cancel an order with signature
@@ -341,36 +273,165 @@ impl OneDeltaOrders for Contract {
     fn cancel_order(order: Order) {
         reentrancy_guard();
 
-        let order_hash = compute_order_hash(order, ContractId::this().bits());
-        let caller = msg_sender().unwrap().bits();
-        require(
-            caller == order.maker || is_order_signer_delegate_internal(order.maker, caller),
-            INVALID_CANCEL,
-        );
+        require_cancel_allowed(order);
 
-        // we ignore thje cancel flag here and always override
-        let (_, amount_filled) = storage.order_hash_to_filled_amount.get(order_hash).try_read().unwrap_or((false, 0u64));
+        cancel_order_internal(compute_order_hash(order, ContractId::this().bits()));
+    }
 
-        // we deduct add the fill amount to the already filled amount for the hash 
-        storage
-            .order_hash_to_filled_amount
-            // we already know that the order is not cancelled
-            .insert(order_hash, (true, amount_filled));
+    // cancel a conditional order, the hash includes the condition
+    #[storage(write, read)]
+    fn cancel_conditional_order(order: Order, condition: PriceCondition) {
+        reentrancy_guard();
+
+        require_cancel_allowed(order);
 
-        // log the cancellation
-        log(CancelEvent { order_hash });
+        cancel_order_internal(compute_conditional_order_hash(order, condition, ContractId::this().bits()));
     }
 
     // allows the signer_delegate to sign on behalf of the caller if allowed=true
+    // the delegation is unrestricted and does not expire
     #[storage(write)]
     fn register_order_signer_delegate(signer_delegate: b256, allowed: bool) {
         reentrancy_guard();
 
         let caller = msg_sender().unwrap().bits();
-        storage
-            .order_signer_registry
-            .get(caller)
-            .insert(signer_delegate, allowed);
+        if allowed {
+            set_order_signer_delegation(
+                caller,
+                signer_delegate,
+                SignerDelegation {
+                    expiry: MAX_EXPIRY,
+                    maker_asset: ZERO_B256,
+                    taker_asset: ZERO_B256,
+                    max_maker_amount: 0,
+                },
+            );
+        } else {
+            remove_order_signer_delegation(caller, signer_delegate);
+        }
+    }
+
+    // allows the signer_delegate to sign on behalf of the caller
+    // until the expiry, for a pair and up to a maker_amount per order
+    #[storage(write)]
+    fn register_order_signer_delegate_scoped(signer_delegate: b256, delegation: SignerDelegation) {
+        reentrancy_guard();
+
+        let caller = msg_sender().unwrap().bits();
+        set_order_signer_delegation(caller, signer_delegate, delegation);
+    }
+
+    // withdraw on behalf of a maker that signed the operation
+    #[storage(write, read)]
+    fn withdraw_signed(
+        maker: b256,
+        asset: b256,
+        amount: u64,
+        receiver: Identity,
+        operation_nonce: u64,
+        signature: B512,
+    ) {
+        reentrancy_guard();
+
+        use_operation_signature(
+            WITHDRAW_OPERATION,
+            maker,
+            operation_nonce,
+            pack_withdraw(asset, amount, receiver),
+            signature,
+        );
+
+        withdraw_internal(maker, asset, amount, receiver);
+    }
+
+    // invalidate a pair nonce on behalf of a maker that signed the operation
+    #[storage(write, read)]
+    fn invalidate_nonce_signed(
+        maker: b256,
+        maker_asset: b256,
+        taker_asset: b256,
+        new_nonce: u64,
+        operation_nonce: u64,
+        signature: B512,
+    ) {
+        reentrancy_guard();
+
+        use_operation_signature(
+            INVALIDATE_NONCE_OPERATION,
+            maker,
+            operation_nonce,
+            pack_invalidate_nonce(maker_asset, taker_asset, new_nonce),
+            signature,
+        );
+
+        invalidate_nonce_internal(maker, maker_asset, taker_asset, new_nonce);
+    }
+
+    // cancel an order on behalf of its maker that signed the operation
+    #[storage(write, read)]
+    fn cancel_order_signed(order: Order, operation_nonce: u64, signature: B512) {
+        reentrancy_guard();
+
+        let order_hash = compute_order_hash(order, ContractId::this().bits());
+
+        use_operation_signature(
+            CANCEL_ORDER_OPERATION,
+            order.maker,
+            operation_nonce,
+            order_hash.to_be_bytes(),
+            signature,
+        );
+
+        cancel_order_internal(order_hash);
+    }
+
+    // cancel a conditional order on behalf of its maker that signed the operation
+    // the signed payload is the conditional order hash
+    #[storage(write, read)]
+    fn cancel_conditional_order_signed(
+        order: Order,
+        condition: PriceCondition,
+        operation_nonce: u64,
+        signature: B512,
+    ) {
+        reentrancy_guard();
+
+        let order_hash = compute_conditional_order_hash(order, condition, ContractId::this().bits());
+
+        use_operation_signature(
+            CANCEL_ORDER_OPERATION,
+            order.maker,
+            operation_nonce,
+            order_hash.to_be_bytes(),
+            signature,
+        );
+
+        cancel_order_internal(order_hash);
+    }
+
+    // set or remove (if None) a delegation on behalf of a maker that signed the operation
+    #[storage(write, read)]
+    fn register_order_signer_delegate_signed(
+        maker: b256,
+        signer_delegate: b256,
+        delegation: Option<SignerDelegation>,
+        operation_nonce: u64,
+        signature: B512,
+    ) {
+        reentrancy_guard();
+
+        use_operation_signature(
+            REGISTER_DELEGATE_OPERATION,
+            maker,
+            operation_nonce,
+            pack_delegation(signer_delegate, delegation),
+            signature,
+        );
+
+        match delegation {
+            Some(d) => set_order_signer_delegation(maker, signer_delegate, d),
+            None => remove_order_signer_delegation(maker, signer_delegate),
+        }
     }
 
     // Get a maker's nonce for a trading pair
@@ -385,6 +446,19 @@ impl OneDeltaOrders for Contract {
         storage.maker_balances.get(maker).get(asset).try_read().unwrap_or(0u64)
     }
 
+    // Return the number of assets a maker ever held a balance of
+    #[storage(read)]
+    fn get_maker_asset_count(maker: b256) -> u64 {
+        storage.maker_assets.get(maker).len()
+    }
+
+    // Return the non-zero balances of a maker, paginated over the maker's
+    // asset list via `start` and `count`
+    #[storage(read)]
+    fn get_maker_balances(maker: b256, start: u64, count: u64) -> Vec<(b256, u64)> {
+        get_maker_balances_internal(maker, start, count)
+    }
+
     // Return the total (accounting) balance
     #[storage(read)]
     fn get_balance(asset: b256) -> u64 {
@@ -394,18 +468,283 @@ impl OneDeltaOrders for Contract {
     // Soft-validate an order as read function
     #[storage(read)]
     fn validate_order(order: Order, order_signature: B512) -> (b256, u64, u64) {
-        validate_order_internal(order, order_signature)
+        validate_order_internal(order, None, order_signature)
+    }
+
+    // Soft-validate a conditional order including its price condition
+    #[storage(read)]
+    fn validate_conditional_order(
+        order: Order,
+        condition: PriceCondition,
+        order_signature: B512,
+    ) -> (b256, u64, u64) {
+        validate_order_internal(order, Some(condition), order_signature)
     }
 
+    // a delegate is active if registered and not expired
     #[storage(read)]
     fn is_order_signer_delegate(signer: b256, signer_delegate: b256) -> bool {
-        is_order_signer_delegate_internal(signer, signer_delegate)
+        match get_order_signer_delegation_internal(signer, signer_delegate) {
+            Some(delegation) => delegation.expiry >= height(),
+            None => false,
+        }
+    }
+
+    // Return the scope of a delegation, None if not registered
+    #[storage(read)]
+    fn get_order_signer_delegation(signer: b256, signer_delegate: b256) -> Option<SignerDelegation> {
+        get_order_signer_delegation_internal(signer, signer_delegate)
     }
 
     #[storage(read)]
     fn get_order_fill_status(order_hash: b256) -> (bool, u64) {
         storage.order_hash_to_filled_amount.get(order_hash).try_read().unwrap_or((false, 0u64))
     }
+
+    // Get the nonce a maker has to use for the next signed operation
+    #[storage(read)]
+    fn get_operation_nonce(maker: b256) -> u64 {
+        storage.operation_nonces.get(maker).try_read().unwrap_or(0u64)
+    }
+
+    // set the initial owner, can only be called once by INITIAL_OWNER
+    #[storage(read, write)]
+    fn initialize(initial_owner: Identity) {
+        require(!storage.initialized.read(), ALREADY_INITIALIZED);
+        require(msg_sender().unwrap() == INITIAL_OWNER, NOT_OWNER);
+        storage.owner.write(initial_owner);
+        storage.initialized.write(true);
+
+        log(OwnershipTransferEvent {
+            previous_owner: Identity::Address(Address::zero()),
+            new_owner: initial_owner,
+        });
+    }
+
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity) {
+        only_owner();
+        let previous_owner = storage.owner.read();
+        storage.owner.write(new_owner);
+
+        log(OwnershipTransferEvent {
+            previous_owner,
+            new_owner,
+        });
+    }
+
+    // pause or unpause all fills, withdrawals remain available
+    #[storage(read, write)]
+    fn set_paused(paused: bool) {
+        only_owner();
+        storage.paused.write(paused);
+
+        log(PauseEvent { paused });
+    }
+
+    // pause or unpause fills for a pair in both directions
+    #[storage(read, write)]
+    fn set_pair_paused(maker_asset: b256, taker_asset: b256, paused: bool) {
+        only_owner();
+        storage.paused_pairs.get(maker_asset).insert(taker_asset, paused);
+
+        log(PairPauseEvent {
+            maker_asset,
+            taker_asset,
+            paused,
+        });
+    }
+
+    #[storage(read)]
+    fn owner() -> Identity {
+        storage.owner.read()
+    }
+
+    // Check whether fills are paused for a pair
+    #[storage(read)]
+    fn is_paused(maker_asset: b256, taker_asset: b256) -> bool {
+        is_paused_internal(maker_asset, taker_asset)
+    }
+}
+
+// Executes the fill, see `fill`
+// conditional orders are validated against their price condition
+#[storage(write, read)]
+fn fill_internal(
+    order: Order,
+    condition: Option<PriceCondition>,
+    order_signature: B512,
+    taker_fill_amount: u64,
+    taker_receiver: Identity,
+    data: Option<Bytes>,
+) -> (u64, u64) {
+    // reentrancy check for taker_asset
+    if storage.taker_asset_locked.get(order.taker_asset).try_read().unwrap_or(false)
+    {
+        revert(REENTER_TAKER_ASSET);
+    }
+    // lock taker_asset
+    storage.taker_asset_locked.insert(order.taker_asset, true);
+
+    // validate order
+    let (order_hash, error, taker_asset_already_filled_amount) = validate_order_internal(order, condition, order_signature);
+
+    // revert if error in validation
+    if error != 0u64 {
+        revert(error);
+    }
+
+    // compute fill amounts
+    let (maker_filled_amount, taker_filled_amount) = compute_fill_amounts(
+        taker_fill_amount,
+        taker_asset_already_filled_amount,
+        order.maker_amount,
+        order.taker_amount,
+    );
+
+    // if any of the amounts is zero, we revert
+    // this is to ensure that no msg_amount is lost 
+    // attempting to fill an empty order
+    require(
+        maker_filled_amount != 0u64 && taker_filled_amount != 0u64,
+        ZERO_FILL_AMOUNT,
+    );
+
+    // the maker hook can provide the maker_asset just in time
+    // this has to happen before reading the maker balance
+    let maker_hook = has_maker_hook(order.maker_traits);
+    if maker_hook {
+        call_before_fill_hook(
+            order,
+            order_hash,
+            maker_filled_amount,
+            taker_filled_amount,
+        );
+    }
+
+    // get stored maker_asset balance
+    let maker_maker_asset_balance = storage.maker_balances.get(order.maker).get(order.maker_asset).try_read().unwrap_or(0u64);
+
+    // make sure that the maker balance is high enough
+    if (maker_filled_amount > maker_maker_asset_balance) {
+        revert(MAKER_BALANCE_TOO_LOW)
+    }
+
+    // prevent partial fills if specified as such
+    if no_partial_fill(order.maker_traits) {
+        if taker_filled_amount < order.taker_amount {
+            revert(NO_PARTIAL_FILL);
+        }
+    }
+
+    // reduce maker's recods for maker_asset
+    // this must be done before the flash call
+    update_maker_maker_asset_balances(
+        order.maker_asset,
+        maker_maker_asset_balance,
+        maker_filled_amount,
+        order.maker,
+    );
+
+    // register the order as filed
+    update_remaining_fill_amount(
+        order_hash,
+        taker_asset_already_filled_amount,
+        taker_filled_amount,
+    );
+
+    // reduce the total maker balance
+    // we account for the fact that we might transfer back
+    // to this contract
+    transfer_maker_asset_out(order.maker_asset, maker_filled_amount, taker_receiver);
+
+    // flash callback if the data is specified
+    // note that we already validated the maker_asset
+    // all validations for the taker_asset happen after the callback
+    // That is why we check against reentrancy for the taker_asset
+    if let Some(d) = data {
+        abi(IFlashCallback, msg_sender()
+            .unwrap()
+            .bits())
+            .flash(
+                order.maker_asset,
+                order.taker_asset,
+                maker_filled_amount,
+                taker_filled_amount,
+                d,
+            );
+    }
+
+    // fetch the real taker asset balance
+    let real_taker_asset_balance = this_balance(AssetId::from(order.taker_asset));
+    // this internal balance is unadjusted for the amount received 
+    let taker_asset_accounting_balance = get_total_asset_balance(order.taker_asset);
+    // the funds received are real balance minus accounting balance
+    let taker_fill_amount_received = real_taker_asset_balance - taker_asset_accounting_balance;
+
+    // validate that we received enough
+    // note that a too high amount will be consumed by the maker
+    if taker_fill_amount_received < taker_filled_amount {
+        revert(INSUFFICIENT_TAKER_AMOUNT_RECEIVED);
+    }
+
+    // if the maker receiver is not provided we default to maker 
+    let maker_receiver = if order.maker_receiver != ZERO_B256 {
+        order.maker_receiver
+    } else {
+        order.maker
+    };
+
+    // if the maker_receiver is defined, we send the funds to
+    // the provided address
+    // the hook is always a contract (validated in the pre-fill call)
+    // and so is a contract signer that receives as the default maker_receiver
+    let is_contract = maker_hook
+        || is_contract_receiver(order.maker_traits)
+        || (order.maker_receiver == ZERO_B256 && is_contract_signer(order.maker_traits));
+    transfer(
+        if is_contract {
+            // we enforce that the maker_receiver cannot be this contract
+            // this is the default behaviour which assumes maker_receiver==0
+            require(
+                maker_receiver != ContractId::this()
+                    .bits(),
+                MAKER_RECEIVER_CANNOT_BE_THIS,
+            );
+            Identity::ContractId(ContractId::from(maker_receiver))
+        } else {
+            Identity::Address(Address::from(maker_receiver))
+        },
+        AssetId::from(order.taker_asset),
+        taker_filled_amount,
+    );
+
+    // log the fill info and hash
+    log(OrderFillEvent {
+        order_hash,
+        taker_filled_amount,
+        maker_filled_amount,
+    });
+
+    // the maker hook can redeploy the received taker_asset
+    // the taker_asset remains locked while doing so
+    if maker_hook {
+        abi(IMakerHook, order.maker_receiver)
+            .after_fill(
+                order_hash,
+                order.maker,
+                order.maker_asset,
+                order.taker_asset,
+                maker_filled_amount,
+                taker_filled_amount,
+            );
+    }
+
+    // unlock taker_asset
+    storage.taker_asset_locked.insert(order.taker_asset, false);
+
+    // return filled amounts
+    (taker_filled_amount, maker_filled_amount)
 }
 
 // Getter for the internal total balance
@@ -416,15 +755,28 @@ fn get_total_asset_balance(asset: b256) -> u64 {
 
 // Soft-validate an order as read function
 #[storage(read)]
-fn validate_order_internal(order: Order, order_signature: B512) -> (b256, u64, u64) {
+fn validate_order_internal(
+    order: Order,
+    condition: Option<PriceCondition>,
+    order_signature: B512,
+) -> (b256, u64, u64) {
     // get current maker nonce
     let old_nonce: u64 = storage.nonces.get(order.maker).get(order.maker_asset).get(order.taker_asset).try_read().unwrap_or(0u64);
 
-    let order_hash = compute_order_hash(order, ContractId::this().bits());
+    // the condition is part of the signed hash
+    let order_hash = match condition {
+        Some(c) => compute_conditional_order_hash(order, c, ContractId::this().bits()),
+        None => compute_order_hash(order, ContractId::this().bits()),
+    };
 
     // the the amount that is already filled
     let (cancelled, taker_asset_filled_amount) = storage.order_hash_to_filled_amount.get(order_hash).try_read().unwrap_or((false, 0u64));
 
+    // check whether fills are paused
+    if is_paused_internal(order.maker_asset, order.taker_asset) {
+        return (order_hash, PAUSED, taker_asset_filled_amount);
+    }
+
     // check cancellation status
     if cancelled {
         return (order_hash, CANCELLED, taker_asset_filled_amount);
@@ -435,12 +787,20 @@ fn validate_order_internal(order: Order, order_signature: B512) -> (b256, u64, u
         return (order_hash, EXPIRED, taker_asset_filled_amount);
     }
 
-    // check that signer is maker or delegate
-    let signer = recover_signer(order_signature, order_hash).bits();
-    if signer != order.maker
-        && !is_order_signer_delegate_internal(order.maker, signer)
-    {
-        return (order_hash, INVALID_ORDER_SIGNATURE, taker_asset_filled_amount);
+    // contract makers validate the hash themselves
+    if is_contract_signer(order.maker_traits) {
+        if !abi(ISignatureValidator, order.maker).is_valid_signature(order_hash, order_signature)
+        {
+            return (order_hash, INVALID_ORDER_SIGNATURE, taker_asset_filled_amount);
+        }
+    } else {
+        // check that signer is maker or delegate
+        let signer = recover_signer(order_signature, order_hash).bits();
+        if signer != order.maker
+            && !is_order_signer_delegate_for_order(order, signer)
+        {
+            return (order_hash, INVALID_ORDER_SIGNATURE, taker_asset_filled_amount);
+        }
     }
 
     // valdiate nonce
@@ -448,9 +808,278 @@ fn validate_order_internal(order: Order, order_signature: B512) -> (b256, u64, u
         return (order_hash, INVALID_NONCE, taker_asset_filled_amount);
     }
 
+    // conditional orders cannot be filled without their condition
+    if is_conditional(order.maker_traits) && condition.is_none() {
+        return (order_hash, MISSING_PRICE_CONDITION, taker_asset_filled_amount);
+    }
+
+    // check the oracle price against the trigger
+    if let Some(c) = condition {
+        let price = abi(Market, c.market).get_price(c.price_feed_id);
+        // an unset price would trigger any stop-loss
+        // and larger exponents cannot be compared
+        if price.price == 0
+            || price.exponent > MAX_PRICE_EXPONENT
+            || c.trigger_exponent > MAX_PRICE_EXPONENT
+        {
+            return (order_hash, INVALID_PRICE, taker_asset_filled_amount);
+        }
+        // publish_time is in TAI64 like the block timestamp
+        // prices from the future are invalid
+        let now = timestamp();
+        if price.publish_time > now {
+            return (order_hash, INVALID_PRICE, taker_asset_filled_amount);
+        }
+        if now - price.publish_time > MAX_PRICE_AGE {
+            return (order_hash, STALE_PRICE, taker_asset_filled_amount);
+        }
+        if !is_condition_met(c, price.price, price.exponent) {
+            return (order_hash, PRICE_CONDITION_NOT_MET, taker_asset_filled_amount);
+        }
+    }
+
     return (order_hash, NO_ERROR, taker_asset_filled_amount);
 }
 
+#[storage(read)]
+fn only_owner() {
+    require(storage.owner.read() == msg_sender().unwrap(), NOT_OWNER);
+}
+
+// a pair is paused if all fills are paused or the pair is paused in any direction
+#[storage(read)]
+fn is_paused_internal(maker_asset: b256, taker_asset: b256) -> bool {
+    storage.paused.read()
+        || storage.paused_pairs.get(maker_asset).get(taker_asset).try_read().unwrap_or(false)
+        || storage.paused_pairs.get(taker_asset).get(maker_asset).try_read().unwrap_or(false)
+}
+
+// Only the maker or a delegate for the pair can cancel an order
+#[storage(read)]
+fn require_cancel_allowed(order: Order) {
+    let caller = msg_sender().unwrap().bits();
+    require(
+        caller == order.maker || is_order_signer_delegate_for_pair(
+            order.maker,
+            caller,
+            order.maker_asset,
+            order.taker_asset,
+        ),
+        INVALID_CANCEL,
+    );
+}
+
+// Withdraw a maker's balance, the maker has to be authorized beforehand
+#[storage(read, write)]
+fn withdraw_internal(owner_bits: b256, asset: b256, amount: u64, receiver: Identity) {
+    let owner_asset_balance = storage.maker_balances.get(owner_bits).get(asset).try_read().unwrap_or(0u64);
+
+    // require that the owner has enough balance
+    if owner_asset_balance < amount {
+        revert(WITHDRAW_TOO_MUCH);
+    }
+
+    // update balance as reduced
+    storage
+        .maker_balances
+        .get(owner_bits)
+        .insert(asset, owner_asset_balance - amount);
+
+    // we sync the overall balance by 
+    let total_balance_before = storage.balances.get(asset).try_read().unwrap_or(0u64);
+
+    // ensure that in no way there are withdrawals
+    // that violate the total balance post transfer
+    let new_balance = total_balance_before - amount;
+    if new_balance > this_balance(AssetId::from(asset)) {
+        revert(BALANCE_VIOLATION);
+    }
+
+    // update total balance
+    storage.balances.insert(asset, new_balance);
+
+    // finalize transfer from this to owner
+    transfer(receiver, AssetId::from(asset), amount);
+
+    // log the withdrawal
+    log(WithdrawEvent {
+        maker: owner_bits,
+        asset,
+        amount,
+    });
+}
+
+// Add an asset to the maker's asset list once it is credited
+#[storage(read, write)]
+fn register_maker_asset(maker: b256, asset: b256) {
+    if !storage.maker_asset_registered.get(maker).get(asset).try_read().unwrap_or(false) {
+        storage.maker_asset_registered.get(maker).insert(asset, true);
+        storage.maker_assets.get(maker).push(asset);
+    }
+}
+
+// Get the non-zero balances for the asset list entries [start, start + count)
+#[storage(read)]
+fn get_maker_balances_internal(maker: b256, start: u64, count: u64) -> Vec<(b256, u64)> {
+    let mut balances: Vec<(b256, u64)> = Vec::new();
+    let len = storage.maker_assets.get(maker).len();
+    let end = start + min64(len - min64(start, len), count);
+
+    let mut i = start;
+    while i < end {
+        let asset = storage.maker_assets.get(maker).get(i).unwrap().read();
+        let balance = storage.maker_balances.get(maker).get(asset).try_read().unwrap_or(0u64);
+        if balance != 0 {
+            balances.push((asset, balance));
+        }
+        i += 1;
+    }
+    balances
+}
+
+// Increase a maker's pair nonce, the maker has to be authorized beforehand
+#[storage(read, write)]
+fn invalidate_nonce_internal(
+    maker: b256,
+    maker_asset: b256,
+    taker_asset: b256,
+    new_nonce: u64,
+) {
+    // get current maker nonce
+    let old_nonce: u64 = storage.nonces.get(maker).get(maker_asset).get(taker_asset).try_read().unwrap_or(0u64);
+
+    // valdiate nonce
+    require(new_nonce > old_nonce, INVALID_NONCE);
+
+    // set new nonce
+    storage
+        .nonces
+        .get(maker) // maker
+        .get(maker_asset) // maker_asset
+        .insert(taker_asset, new_nonce);
+
+    log(CancelPairEvent {
+        maker,
+        maker_asset,
+        taker_asset,
+        nonce: new_nonce,
+    });
+}
+
+// Flag an order hash as cancelled, the caller has to be authorized beforehand
+#[storage(read, write)]
+fn cancel_order_internal(order_hash: b256) {
+    // we ignore thje cancel flag here and always override
+    let (_, amount_filled) = storage.order_hash_to_filled_amount.get(order_hash).try_read().unwrap_or((false, 0u64));
+
+    // we deduct add the fill amount to the already filled amount for the hash 
+    storage
+        .order_hash_to_filled_amount
+        // we already know that the order is not cancelled
+        .insert(order_hash, (true, amount_filled));
+
+    // log the cancellation
+    log(CancelEvent { order_hash });
+}
+
+// Validates that the maker signed the operation with the current operation nonce
+// and consumes the nonce so that the signature cannot be replayed
+#[storage(read, write)]
+fn use_operation_signature(
+    operation: u8,
+    maker: b256,
+    operation_nonce: u64,
+    payload: Bytes,
+    signature: B512,
+) {
+    let current_nonce = storage.operation_nonces.get(maker).try_read().unwrap_or(0u64);
+    require(operation_nonce == current_nonce, INVALID_OPERATION_NONCE);
+
+    let operation_hash = compute_operation_hash(
+        operation,
+        maker,
+        operation_nonce,
+        payload,
+        ContractId::this()
+            .bits(),
+    );
+    require(
+        recover_signer(signature, operation_hash)
+            .bits() == maker,
+        INVALID_OPERATION_SIGNATURE,
+    );
+
+    storage.operation_nonces.insert(maker, current_nonce + 1);
+}
+
+// Calls the maker hook to provide the maker_asset just in time
+// Whatever the hook sends to this contract is credited to the maker
+#[storage(read, write)]
+fn call_before_fill_hook(
+    order: Order,
+    order_hash: b256,
+    maker_filled_amount: u64,
+    taker_filled_amount: u64,
+) {
+    let hook = order.maker_receiver;
+    // the hook has to be the maker itself, otherwise anyone could
+    // sign an order that drains a third-party hook into their balance
+    // the hook also receives the taker_asset and cannot be this contract
+    require(
+        hook == order.maker && hook != ContractId::this()
+            .bits() && order.maker_asset != order.taker_asset,
+        INVALID_MAKER_HOOK,
+    );
+
+    // lock the maker_asset as well so that no fill can
+    // consume what the hook sends
+    if storage.taker_asset_locked.get(order.maker_asset).try_read().unwrap_or(false)
+    {
+        revert(REENTER_TAKER_ASSET);
+    }
+    storage.taker_asset_locked.insert(order.maker_asset, true);
+
+    abi(IMakerHook, hook)
+        .before_fill(
+            order_hash,
+            order.maker,
+            order.maker_asset,
+            order.taker_asset,
+            maker_filled_amount,
+            taker_filled_amount,
+        );
+
+    storage.taker_asset_locked.insert(order.maker_asset, false);
+
+    // the amount provided is the real balance minus accounting balance
+    let total_asset_balance_accounting = get_total_asset_balance(order.maker_asset);
+    let total_asset_balance_real = this_balance(AssetId::from(order.maker_asset));
+    let deposit_amount = total_asset_balance_real - total_asset_balance_accounting;
+
+    if deposit_amount != 0 {
+        let maker_asset_balance = storage.maker_balances.get(order.maker).get(order.maker_asset).try_read().unwrap_or(0u64);
+
+        // credit the maker
+        storage
+            .maker_balances
+            .get(order.maker)
+            .insert(order.maker_asset, maker_asset_balance + deposit_amount);
+        register_maker_asset(order.maker, order.maker_asset);
+
+        // update total balance
+        storage
+            .balances
+            .insert(order.maker_asset, total_asset_balance_real);
+
+        // log the deposit
+        log(DepositEvent {
+            maker: order.maker,
+            asset: order.maker_asset,
+            amount: deposit_amount,
+        });
+    }
+}
+
 // Update the internal balances based on order fill info
 // for the case where the real_taker_asset_balance is provided
 #[storage(read, write)]
@@ -515,10 +1144,71 @@ fn update_remaining_fill_amount(
         );
 }
 
-// check the registry if signer delegated to signer_delegate
+// read the delegation of signer to signer_delegate from the registry
 #[storage(read)]
-fn is_order_signer_delegate_internal(signer: b256, signer_delegate: b256) -> bool {
-    storage.order_signer_registry.get(signer).get(signer_delegate).try_read().unwrap_or(false)
+fn get_order_signer_delegation_internal(signer: b256, signer_delegate: b256) -> Option<SignerDelegation> {
+    storage.order_signer_registry.get(signer).get(signer_delegate).try_read()
+}
+
+// check the registry if the maker delegated the order to signer_delegate
+#[storage(read)]
+fn is_order_signer_delegate_for_order(order: Order, signer_delegate: b256) -> bool {
+    match get_order_signer_delegation_internal(order.maker, signer_delegate) {
+        Some(delegation) => is_in_delegation_scope(
+            delegation,
+            order.maker_asset,
+            order.taker_asset,
+            order.maker_amount,
+            height(),
+        ),
+        None => false,
+    }
+}
+
+// check the registry if the maker delegated a pair to signer_delegate
+// the amount is irrelevant here, this is used for cancellations
+#[storage(read)]
+fn is_order_signer_delegate_for_pair(
+    maker: b256,
+    signer_delegate: b256,
+    maker_asset: b256,
+    taker_asset: b256,
+) -> bool {
+    match get_order_signer_delegation_internal(maker, signer_delegate) {
+        Some(delegation) => is_in_delegation_scope(delegation, maker_asset, taker_asset, 0, height()),
+        None => false,
+    }
+}
+
+// store a delegation and log it
+#[storage(write)]
+fn set_order_signer_delegation(
+    maker: b256,
+    signer_delegate: b256,
+    delegation: SignerDelegation,
+) {
+    storage
+        .order_signer_registry
+        .get(maker)
+        .insert(signer_delegate, delegation);
+
+    log(SignerDelegateEvent {
+        maker,
+        signer_delegate,
+        delegation: Some(delegation),
+    });
+}
+
+// remove a delegation and log it
+#[storage(write)]
+fn remove_order_signer_delegation(maker: b256, signer_delegate: b256) {
+    let _ = storage.order_signer_registry.get(maker).remove(signer_delegate);
+
+    log(SignerDelegateEvent {
+        maker,
+        signer_delegate,
+        delegation: None,
+    });
 }
 
 fn compute_fill_amounts(
@@ -0,0 +1,160 @@
+use crate::utils::{
+    create_order, deposit_for_order, fill, get_maker_balances, setup, ALREADY_INITIALIZED,
+    NOT_OWNER, PAUSED, WITHDRAW_TOO_MUCH,
+};
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::errors::{transaction::Reason, Error};
+use fuels::types::{Bits256, Identity};
+use test_harness::interface::orders::{deploy_one_delta_orders_contract, sign_order};
+
+fn assert_revert<T: std::fmt::Debug>(result: Result<T, Error>, expected: u64) {
+    match result {
+        Err(Error::Transaction(Reason::Reverted { revert_id, .. })) => {
+            assert_eq!(revert_id, expected)
+        }
+        Err(e) => panic!("expected revert {expected}, got {e:?}"),
+        Ok(value) => panic!("expected revert {expected}, got {value:?}"),
+    }
+}
+
+#[tokio::test]
+async fn cannot_fill_while_paused() {
+    let (orders, (guardian, maker, taker), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker, &order, orders_id).await;
+    deposit_for_order(&orders, &maker, &order).await;
+
+    orders
+        .clone()
+        .with_account(guardian.clone())
+        .methods()
+        .set_paused(true)
+        .call()
+        .await
+        .unwrap();
+
+    let result = fill(&orders, &taker, &order, signature, 20_000).await;
+    assert_revert(result, PAUSED);
+}
+
+#[tokio::test]
+async fn cannot_fill_while_pair_paused() {
+    let (orders, (guardian, maker, taker), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker, &order, orders_id).await;
+    deposit_for_order(&orders, &maker, &order).await;
+
+    // pausing the reverse direction pauses the pair
+    orders
+        .clone()
+        .with_account(guardian.clone())
+        .methods()
+        .set_pair_paused(Bits256(*token_1_id), Bits256(*token_0_id), true)
+        .call()
+        .await
+        .unwrap();
+
+    let result = fill(&orders, &taker, &order, signature, 20_000).await;
+    assert_revert(result, PAUSED);
+}
+
+#[tokio::test]
+async fn cannot_pause_if_not_owner() {
+    let (orders, (_, maker, _), (token_0_id, token_1_id)) = setup().await;
+    let maker_orders = orders.clone().with_account(maker.clone());
+
+    let result = maker_orders.methods().set_paused(true).call().await;
+    assert_revert(result, NOT_OWNER);
+
+    let result = maker_orders
+        .methods()
+        .set_pair_paused(Bits256(*token_0_id), Bits256(*token_1_id), true)
+        .call()
+        .await;
+    assert_revert(result, NOT_OWNER);
+}
+
+#[tokio::test]
+async fn previous_owner_cannot_pause_after_transfer() {
+    let (orders, (guardian, _, taker), _) = setup().await;
+    let guardian_orders = orders.clone().with_account(guardian.clone());
+
+    guardian_orders
+        .methods()
+        .transfer_ownership(Identity::Address(taker.address().into()))
+        .call()
+        .await
+        .unwrap();
+
+    let result = guardian_orders.methods().set_paused(true).call().await;
+    assert_revert(result, NOT_OWNER);
+}
+
+#[tokio::test]
+async fn cannot_initialize_twice() {
+    let (orders, (_, maker, _), _) = setup().await;
+
+    let result = orders
+        .clone()
+        .with_account(maker.clone())
+        .methods()
+        .initialize(Identity::Address(maker.address().into()))
+        .call()
+        .await;
+    assert_revert(result, ALREADY_INITIALIZED);
+}
+
+#[tokio::test]
+async fn only_deployer_can_initialize() {
+    let (_, (guardian, maker, _), _) = setup().await;
+
+    // a fresh deployment, INITIAL_OWNER is the guardian
+    let (_, orders) = deploy_one_delta_orders_contract(&guardian).await;
+
+    let result = orders
+        .clone()
+        .with_account(maker.clone())
+        .methods()
+        .initialize(Identity::Address(maker.address().into()))
+        .call()
+        .await;
+    assert_revert(result, NOT_OWNER);
+
+    orders
+        .methods()
+        .initialize(Identity::Address(maker.address().into()))
+        .call()
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn cannot_withdraw_many_more_than_balance() {
+    let (orders, (_, maker, _), (token_0_id, token_1_id)) = setup().await;
+
+    let order = create_order(&maker, token_0_id, token_1_id, 10_000, 20_000, 1);
+    deposit_for_order(&orders, &maker, &order).await;
+
+    // the second withdrawal exceeds the remaining balance
+    let result = orders
+        .clone()
+        .with_account(maker.clone())
+        .methods()
+        .withdraw_many(
+            vec![(Bits256(*token_0_id), 6_000), (Bits256(*token_0_id), 6_000)],
+            Identity::Address(maker.address().into()),
+        )
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await;
+    assert_revert(result, WITHDRAW_TOO_MUCH);
+
+    assert_eq!(
+        get_maker_balances(&orders, &maker, 0, 10).await,
+        vec![(Bits256(*token_0_id), 10_000)]
+    );
+}
@@ -0,0 +1,297 @@
+use crate::utils::{create_order, deposit_for_order, fill, get_maker_balances, setup, withdraw};
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{Execution, VariableOutputPolicy};
+use fuels::types::{AssetId, Bits256, Identity};
+use test_harness::interface::orders::{deposit, get_order_fill_status, sign_order};
+use test_harness::interface::OneDeltaOrders;
+
+// deposit token_0 from the maker and token_1 from the taker on behalf of the maker
+async fn deposit_both_assets(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    (maker, taker): (&WalletUnlocked, &WalletUnlocked),
+    (token_0_id, token_1_id): (AssetId, AssetId),
+) {
+    let maker_identity = Identity::Address(maker.address().into());
+    deposit(
+        &orders.clone().with_account(maker.clone()),
+        token_0_id,
+        10_000,
+        maker_identity,
+    )
+    .await;
+    deposit(
+        &orders.clone().with_account(taker.clone()),
+        token_1_id,
+        20_000,
+        maker_identity,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn withdraw_succeeds_while_paused() {
+    let (orders, (guardian, maker, _), (token_0_id, token_1_id)) = setup().await;
+
+    let order = create_order(&maker, token_0_id, token_1_id, 10_000, 20_000, 1);
+    deposit_for_order(&orders, &maker, &order).await;
+
+    orders
+        .clone()
+        .with_account(guardian.clone())
+        .methods()
+        .set_paused(true)
+        .call()
+        .await
+        .unwrap();
+
+    let balance_before = maker.get_asset_balance(&token_0_id).await.unwrap();
+    withdraw(&orders, &maker, token_0_id, 10_000).await.unwrap();
+    let balance_after = maker.get_asset_balance(&token_0_id).await.unwrap();
+
+    assert_eq!(balance_after - balance_before, 10_000);
+    let maker_balance = orders
+        .methods()
+        .get_maker_balance(Bits256(*maker.address().hash()), Bits256(*token_0_id))
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(maker_balance, 0);
+}
+
+#[tokio::test]
+async fn withdraw_succeeds_while_pair_paused() {
+    let (orders, (guardian, maker, _), (token_0_id, token_1_id)) = setup().await;
+
+    let order = create_order(&maker, token_0_id, token_1_id, 10_000, 20_000, 1);
+    deposit_for_order(&orders, &maker, &order).await;
+
+    orders
+        .clone()
+        .with_account(guardian.clone())
+        .methods()
+        .set_pair_paused(Bits256(*token_0_id), Bits256(*token_1_id), true)
+        .call()
+        .await
+        .unwrap();
+
+    let balance_before = maker.get_asset_balance(&token_0_id).await.unwrap();
+    withdraw(&orders, &maker, token_0_id, 4_000).await.unwrap();
+    let balance_after = maker.get_asset_balance(&token_0_id).await.unwrap();
+
+    assert_eq!(balance_after - balance_before, 4_000);
+}
+
+#[tokio::test]
+async fn pair_pause_applies_in_both_directions() {
+    let (orders, (guardian, _, _), (token_0_id, token_1_id)) = setup().await;
+
+    orders
+        .clone()
+        .with_account(guardian.clone())
+        .methods()
+        .set_pair_paused(Bits256(*token_0_id), Bits256(*token_1_id), true)
+        .call()
+        .await
+        .unwrap();
+
+    let is_paused_0_1 = orders
+        .methods()
+        .is_paused(Bits256(*token_0_id), Bits256(*token_1_id))
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+    let is_paused_1_0 = orders
+        .methods()
+        .is_paused(Bits256(*token_1_id), Bits256(*token_0_id))
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+    let is_paused_0_0 = orders
+        .methods()
+        .is_paused(Bits256(*token_0_id), Bits256(*token_0_id))
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+
+    assert!(is_paused_0_1);
+    assert!(is_paused_1_0);
+    assert!(!is_paused_0_0);
+}
+
+#[tokio::test]
+async fn fill_resumes_after_unpause() {
+    let (orders, (guardian, maker, taker), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+    let guardian_orders = orders.clone().with_account(guardian.clone());
+
+    let order = create_order(&maker, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker, &order, orders_id).await;
+    deposit_for_order(&orders, &maker, &order).await;
+
+    guardian_orders
+        .methods()
+        .set_paused(true)
+        .call()
+        .await
+        .unwrap();
+    guardian_orders
+        .methods()
+        .set_paused(false)
+        .call()
+        .await
+        .unwrap();
+
+    let (taker_fill, maker_fill) = fill(&orders, &taker, &order, signature, 20_000)
+        .await
+        .unwrap();
+
+    assert_eq!(taker_fill, 20_000);
+    assert_eq!(maker_fill, 10_000);
+    assert_eq!(get_order_fill_status(&orders, &order).await, (false, 20_000));
+}
+
+#[tokio::test]
+async fn new_owner_can_pause() {
+    let (orders, (guardian, _, taker), _) = setup().await;
+    let new_owner = Identity::Address(taker.address().into());
+
+    orders
+        .clone()
+        .with_account(guardian.clone())
+        .methods()
+        .transfer_ownership(new_owner)
+        .call()
+        .await
+        .unwrap();
+
+    orders
+        .clone()
+        .with_account(taker.clone())
+        .methods()
+        .set_paused(true)
+        .call()
+        .await
+        .unwrap();
+
+    let owner = orders
+        .methods()
+        .owner()
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(owner, new_owner);
+}
+
+#[tokio::test]
+async fn can_enumerate_maker_balances() {
+    let (orders, (_, maker, taker), (token_0_id, token_1_id)) = setup().await;
+
+    assert!(get_maker_balances(&orders, &maker, 0, 10).await.is_empty());
+
+    deposit_both_assets(&orders, (&maker, &taker), (token_0_id, token_1_id)).await;
+
+    let asset_count = orders
+        .methods()
+        .get_maker_asset_count(Bits256(*maker.address().hash()))
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+    assert_eq!(asset_count, 2);
+
+    assert_eq!(
+        get_maker_balances(&orders, &maker, 0, 10).await,
+        vec![
+            (Bits256(*token_0_id), 10_000),
+            (Bits256(*token_1_id), 20_000)
+        ]
+    );
+    assert_eq!(
+        get_maker_balances(&orders, &maker, 1, 1).await,
+        vec![(Bits256(*token_1_id), 20_000)]
+    );
+    assert!(get_maker_balances(&orders, &maker, 2, 10).await.is_empty());
+
+    // the asset stays listed, but drops out of the balances once empty
+    withdraw(&orders, &maker, token_0_id, 10_000).await.unwrap();
+    assert_eq!(
+        get_maker_balances(&orders, &maker, 0, 10).await,
+        vec![(Bits256(*token_1_id), 20_000)]
+    );
+}
+
+#[tokio::test]
+async fn can_withdraw_many() {
+    let (orders, (_, maker, taker), (token_0_id, token_1_id)) = setup().await;
+
+    deposit_both_assets(&orders, (&maker, &taker), (token_0_id, token_1_id)).await;
+
+    let token_0_before = maker.get_asset_balance(&token_0_id).await.unwrap();
+    let token_1_before = maker.get_asset_balance(&token_1_id).await.unwrap();
+
+    orders
+        .clone()
+        .with_account(maker.clone())
+        .methods()
+        .withdraw_many(
+            vec![
+                (Bits256(*token_0_id), 4_000),
+                (Bits256(*token_1_id), 20_000),
+            ],
+            Identity::Address(maker.address().into()),
+        )
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+
+    let token_0_after = maker.get_asset_balance(&token_0_id).await.unwrap();
+    let token_1_after = maker.get_asset_balance(&token_1_id).await.unwrap();
+
+    assert_eq!(token_0_after - token_0_before, 4_000);
+    assert_eq!(token_1_after - token_1_before, 20_000);
+    assert_eq!(
+        get_maker_balances(&orders, &maker, 0, 10).await,
+        vec![(Bits256(*token_0_id), 6_000)]
+    );
+}
+
+#[tokio::test]
+async fn can_withdraw_all() {
+    let (orders, (_, maker, taker), (token_0_id, token_1_id)) = setup().await;
+
+    deposit_both_assets(&orders, (&maker, &taker), (token_0_id, token_1_id)).await;
+
+    let token_0_before = maker.get_asset_balance(&token_0_id).await.unwrap();
+    let token_1_before = maker.get_asset_balance(&token_1_id).await.unwrap();
+
+    let withdrawn = orders
+        .clone()
+        .with_account(maker.clone())
+        .methods()
+        .withdraw_all(Identity::Address(maker.address().into()))
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let token_0_after = maker.get_asset_balance(&token_0_id).await.unwrap();
+    let token_1_after = maker.get_asset_balance(&token_1_id).await.unwrap();
+
+    assert_eq!(
+        withdrawn,
+        vec![
+            (Bits256(*token_0_id), 10_000),
+            (Bits256(*token_1_id), 20_000)
+        ]
+    );
+    assert_eq!(token_0_after - token_0_before, 10_000);
+    assert_eq!(token_1_after - token_1_before, 20_000);
+    assert!(get_maker_balances(&orders, &maker, 0, 10).await.is_empty());
+}
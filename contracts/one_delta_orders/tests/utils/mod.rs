@@ -0,0 +1,182 @@
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{
+    launch_custom_provider_and_get_wallets, CallParameters, Execution, VariableOutputPolicy,
+    WalletsConfig,
+};
+use fuels::types::errors::Error;
+use fuels::types::{AssetId, Bits256, Identity, B512};
+use test_harness::interface::mock::{add_token, deploy_mock_token_contract, mint_tokens};
+use test_harness::interface::orders::{deploy_one_delta_orders_contract, deposit};
+use test_harness::interface::{MockToken, OneDeltaOrders, Order};
+
+pub const MAX_EXPIRY: u64 = 4_294_967_295;
+pub const MINT_AMOUNT: u64 = 1_000_000_000;
+
+// orders error codes
+pub const WITHDRAW_TOO_MUCH: u64 = 6;
+pub const NOT_OWNER: u64 = 21;
+pub const ALREADY_INITIALIZED: u64 = 22;
+pub const PAUSED: u64 = 23;
+
+////////////////////////////////////////////////////
+// Create 2 tokens and 3 wallets
+// the guardian owns the orders contract
+// the maker holds token_0, the taker holds token_1
+////////////////////////////////////////////////////
+pub async fn setup() -> (
+    OneDeltaOrders<WalletUnlocked>,
+    (WalletUnlocked, WalletUnlocked, WalletUnlocked),
+    (AssetId, AssetId),
+) {
+    let wallets = launch_custom_provider_and_get_wallets(
+        WalletsConfig::new(Some(3), Some(1), Some(1_000_000_000)),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let (guardian, maker, taker) = (wallets[0].clone(), wallets[1].clone(), wallets[2].clone());
+
+    ////////////////////////////////////////////////////
+    // deploy tokens and mint
+    ////////////////////////////////////////////////////
+
+    let (token_contract_id, token_contract) = deploy_mock_token_contract(&guardian).await;
+
+    let token_0_id = add_token(&token_contract, "TOKEN_A".to_string(), "TKA".to_string(), 9)
+        .await
+        .value;
+    let token_1_id = add_token(&token_contract, "TOKEN_B".to_string(), "TKB".to_string(), 9)
+        .await
+        .value;
+
+    mint_tokens(
+        &MockToken::new(token_contract_id, maker.clone()),
+        token_0_id,
+        MINT_AMOUNT,
+    )
+    .await;
+    mint_tokens(
+        &MockToken::new(token_contract_id, taker.clone()),
+        token_1_id,
+        MINT_AMOUNT,
+    )
+    .await;
+
+    ////////////////////////////////////////////////////
+    // deploy orders and set the guardian
+    ////////////////////////////////////////////////////
+
+    let (_, orders) = deploy_one_delta_orders_contract(&guardian).await;
+    orders
+        .methods()
+        .initialize(Identity::Address(guardian.address().into()))
+        .call()
+        .await
+        .unwrap();
+
+    (orders, (guardian, maker, taker), (token_0_id, token_1_id))
+}
+
+// deposit the maker_amount of an order on behalf of its maker
+pub async fn deposit_for_order(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    maker: &WalletUnlocked,
+    order: &Order,
+) {
+    deposit(
+        &orders.clone().with_account(maker.clone()),
+        AssetId::from(order.maker_asset.0),
+        order.maker_amount,
+        Identity::Address(maker.address().into()),
+    )
+    .await;
+}
+
+pub fn create_order(
+    maker: &WalletUnlocked,
+    maker_asset: AssetId,
+    taker_asset: AssetId,
+    maker_amount: u64,
+    taker_amount: u64,
+    nonce: u64,
+) -> Order {
+    Order {
+        maker_asset: Bits256(*maker_asset),
+        taker_asset: Bits256(*taker_asset),
+        maker_amount,
+        taker_amount,
+        maker: Bits256(*maker.address().hash()),
+        nonce,
+        maker_traits: MAX_EXPIRY,
+        maker_receiver: Bits256::zeroed(),
+    }
+}
+
+// fill an order by attaching the taker amount
+pub async fn fill(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    taker: &WalletUnlocked,
+    order: &Order,
+    signature: B512,
+    taker_fill_amount: u64,
+) -> Result<(u64, u64), Error> {
+    orders
+        .clone()
+        .with_account(taker.clone())
+        .methods()
+        .fill(
+            order.clone(),
+            signature,
+            taker_fill_amount,
+            Identity::Address(taker.address().into()),
+            None,
+        )
+        .call_params(
+            CallParameters::default()
+                .with_amount(taker_fill_amount)
+                .with_asset_id(AssetId::from(order.taker_asset.0)),
+        )
+        .unwrap()
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .map(|response| response.value)
+}
+
+// withdraw a maker balance to the maker
+pub async fn withdraw(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    maker: &WalletUnlocked,
+    asset_id: AssetId,
+    amount: u64,
+) -> Result<(), Error> {
+    orders
+        .clone()
+        .with_account(maker.clone())
+        .methods()
+        .withdraw(
+            Bits256(*asset_id),
+            amount,
+            Identity::Address(maker.address().into()),
+        )
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .map(|response| response.value)
+}
+
+pub async fn get_maker_balances(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    maker: &WalletUnlocked,
+    start: u64,
+    count: u64,
+) -> Vec<(Bits256, u64)> {
+    orders
+        .methods()
+        .get_maker_balances(Bits256(*maker.address().hash()), start, count)
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value
+}
@@ -8,10 +8,14 @@ use std::{
     hash::*,
     revert::require,
 };
+use core::codec::{abi_decode, encode};
+use executor::{BatchSwapStep, execute_exact_in, get_dex_input_receiver};
 use order_utils::{
+    compute_maker_fill_amount,
     compute_taker_fill_amount,
     IFlashCallback,
     OneDeltaOrders,
+    pack_order_and_sig,
     structs::{
         CancelEvent,
         CancelPairEvent,
@@ -34,6 +38,25 @@ abi OrderRouter {
         data: Option<Bytes>,
     );
 
+    #[storage(read, write)]
+    fn match_orders(
+        left: Order,
+        left_signature: B512,
+        right: Order,
+        right_signature: B512,
+        spread_receiver: Identity,
+    ) -> (u64, u64, u64);
+
+    #[storage(read, write)]
+    fn fill_order_and_swap(
+        order: Order,
+        order_signature: B512,
+        taker_fill_amount: u64,
+        path: Vec<BatchSwapStep>,
+        minimum_profit: u64,
+        profit_receiver: Identity,
+    ) -> u64;
+
     #[storage(read, write)]
     fn flash(
         maker_asset: b256,
@@ -45,14 +68,22 @@ abi OrderRouter {
 }
 
 configurable {
+    MIRA_AMM_CONTRACT_ID: ContractId = ContractId::from(0x2e40f2b244b98ed6b8204b3de0156c6961f98525c8162f80162fcf53eebd90e7),
     ONE_DELTA_ORDERS_CONTRACT_ID: ContractId = ContractId::from(0xf6caa75386fe9ba4da15b82723ecffb0d56b28ae7ece396b15c5650b605359ac),
 }
 
-storage {}
+storage {
+    // set while a fill is settled through a swap path
+    swap_in_progress: bool = false,
+}
 
 // error codes
 const INVALID_SENDER = 101u64;
 const INVALID_MATCH = 102u64;
+const ORDERS_DO_NOT_CROSS = 103u64;
+const NOTHING_TO_MATCH = 104u64;
+const INVALID_PATH = 105u64;
+const INSUFFICIENT_PROFIT = 106u64;
 
 impl OrderRouter for Contract {
     #[storage(write, read), payable]
@@ -78,6 +109,154 @@ impl OrderRouter for Contract {
         }
     }
 
+    /*
+    * Matches two orders on the same pair against each other.
+    * The left maker_asset is sold to the right maker, the
+    * right maker_asset is used to pay the left maker.
+    * The matched amount is the maximum that both orders can still fill.
+    * The spread (left taker_asset) is sent to `spread_receiver`.
+    * Returns (left taker_asset filled, left maker_asset filled, spread)
+    */
+    #[storage(read, write)]
+    fn match_orders(
+        left: Order,
+        left_signature: B512,
+        right: Order,
+        right_signature: B512,
+        spread_receiver: Identity,
+    ) -> (u64, u64, u64) {
+        // the orders have to be on opposite sides of the same pair
+        require(
+            left.maker_asset == right.taker_asset && left.taker_asset == right.maker_asset,
+            INVALID_MATCH,
+        );
+
+        // the prices have to cross, i.e. the left maker accepts
+        // less than what the right maker offers
+        // left.taker_amount / left.maker_amount <= right.maker_amount / right.taker_amount
+        let left_maker_amount: u256 = left.maker_amount.into();
+        let left_taker_amount: u256 = left.taker_amount.into();
+        let right_maker_amount: u256 = right.maker_amount.into();
+        let right_taker_amount: u256 = right.taker_amount.into();
+        require(
+            left_maker_amount * right_maker_amount >= left_taker_amount * right_taker_amount,
+            ORDERS_DO_NOT_CROSS,
+        );
+
+        let orders = abi(OneDeltaOrders, ONE_DELTA_ORDERS_CONTRACT_ID.into());
+
+        // fetch the amounts that are already filled
+        // the validity of the orders is checked when filling
+        let (_, _, left_filled) = orders.validate_order(left, left_signature);
+        let (_, _, right_filled) = orders.validate_order(right, right_signature);
+        require(
+            left_filled < left.taker_amount && right_filled < right.taker_amount,
+            NOTHING_TO_MATCH,
+        );
+
+        // remaining taker_asset amounts of both orders
+        let left_remaining = left.taker_amount - left_filled;
+        let right_remaining = right.taker_amount - right_filled;
+
+        // the left maker_asset that would be paid out for the left remainder
+        let left_maker_remaining = compute_maker_fill_amount(left_remaining, left.maker_amount, left.taker_amount);
+
+        // if the right order cannot absorb the left remainder, we
+        // fill the left order only up to the right remainder
+        // rounding down ensures that the left maker_asset paid out does not exceed it
+        let left_fill_amount = if left_maker_remaining <= right_remaining {
+            left_remaining
+        } else {
+            compute_maker_fill_amount(right_remaining, left.taker_amount, left.maker_amount)
+        };
+
+        // the amounts have to be settleable after rounding
+        let left_maker_fill_amount = compute_maker_fill_amount(left_fill_amount, left.maker_amount, left.taker_amount);
+        let right_maker_fill_amount = compute_maker_fill_amount(left_maker_fill_amount, right.maker_amount, right.taker_amount);
+        require(
+            left_maker_fill_amount != 0 && right_maker_fill_amount >= left_fill_amount,
+            ORDERS_DO_NOT_CROSS,
+        );
+
+        // the left maker_asset is sent to the orders contract directly
+        // to pre-fund the right fill that is executed in the flash callback
+        let (left_taker_filled, left_maker_filled) = orders.fill(
+            left,
+            left_signature,
+            left_fill_amount,
+            Identity::ContractId(ONE_DELTA_ORDERS_CONTRACT_ID),
+            Some(pack_order_and_sig(right, right_signature)),
+        );
+
+        // the spread is what remains after paying the left maker
+        let asset_id = AssetId::from(left.taker_asset);
+        let spread = this_balance(asset_id);
+        if spread != 0 {
+            transfer(spread_receiver, asset_id, spread);
+        }
+
+        (left_taker_filled, left_maker_filled, spread)
+    }
+
+    /*
+    * Fills an order and sells the received maker_asset along `path`.
+    * The order's taker_amount is paid from the swap proceeds, as such
+    * no upfront capital is required.
+    * The path has to start with the maker_asset and end with the
+    * taker_asset, the last step has to send the proceeds to this contract.
+    * The remaining taker_asset has to be at least `minimum_profit`
+    * and is sent to `profit_receiver`.
+    * Returns the profit
+    */
+    #[storage(read, write)]
+    fn fill_order_and_swap(
+        order: Order,
+        order_signature: B512,
+        taker_fill_amount: u64,
+        path: Vec<BatchSwapStep>,
+        minimum_profit: u64,
+        profit_receiver: Identity,
+    ) -> u64 {
+        require(path.len() != 0, INVALID_PATH);
+        let first_step = path.get(0).unwrap();
+        let last_step = path.get(path.len() - 1).unwrap();
+        require(
+            first_step.asset_in.bits() == order.maker_asset && last_step.asset_out.bits() == order.taker_asset && last_step.receiver == Identity::ContractId(ContractId::this()),
+            INVALID_PATH,
+        );
+
+        // the maker_asset is sent to the first DEX directly
+        let first_receiver = get_dex_input_receiver(
+            first_step
+                .dex_id,
+            first_step
+                .data,
+            MIRA_AMM_CONTRACT_ID,
+            ONE_DELTA_ORDERS_CONTRACT_ID,
+        );
+
+        // the flag tells the flash callback to execute the path
+        storage.swap_in_progress.write(true);
+        abi(OneDeltaOrders, ONE_DELTA_ORDERS_CONTRACT_ID.into()).fill(
+            order,
+            order_signature,
+            taker_fill_amount,
+            first_receiver,
+            Some(Bytes::from(encode(path))),
+        );
+        storage.swap_in_progress.write(false);
+
+        // the profit is what remains after paying the maker
+        let asset_id = AssetId::from(order.taker_asset);
+        let profit = this_balance(asset_id);
+        require(profit >= minimum_profit, INSUFFICIENT_PROFIT);
+        if profit != 0 {
+            transfer(profit_receiver, asset_id, profit);
+        }
+
+        profit
+    }
+
     #[storage(read, write)]
     fn flash(
         maker_asset: b256,
@@ -94,23 +273,44 @@ impl OrderRouter for Contract {
             INVALID_SENDER,
         );
 
-        let (order, signature) = to_order_and_sig(data);
-        require(
-            msg_sender()
-                .unwrap()
-                .bits() == ONE_DELTA_ORDERS_CONTRACT_ID
-                .bits(),
-            INVALID_MATCH,
-        );
+        if storage.swap_in_progress.read() {
+            // sell the maker_asset along the path
+            let path = abi_decode::<Vec<BatchSwapStep>>(data.as_raw_slice());
+            let mut amount = maker_amount;
+            let mut i = 0;
+            while i < path.len() {
+                amount = execute_exact_in(
+                    amount,
+                    path.get(i).unwrap(),
+                    MIRA_AMM_CONTRACT_ID,
+                    ONE_DELTA_ORDERS_CONTRACT_ID,
+                );
+                i += 1;
+            }
 
-        let orders = abi(OneDeltaOrders, ONE_DELTA_ORDERS_CONTRACT_ID.into());
-        let (taker_filled, maker_filled) = orders.fill(
-            order,
-            signature,
-            maker_amount,
-            Identity::ContractId(ContractId::this()),
-            None,
-        );
+            // the proceeds have to pay the taker_amount
+            require(amount >= taker_amount, INSUFFICIENT_PROFIT);
+        } else {
+            let (order, signature) = to_order_and_sig(data);
+
+            // the matched order has to sell the taker_asset for the maker_asset
+            require(
+                order.maker_asset == taker_asset && order.taker_asset == maker_asset,
+                INVALID_MATCH,
+            );
+
+            let orders = abi(OneDeltaOrders, ONE_DELTA_ORDERS_CONTRACT_ID.into());
+            let (_, maker_filled) = orders.fill(
+                order,
+                signature,
+                maker_amount,
+                Identity::ContractId(ContractId::this()),
+                None,
+            );
+
+            // the matched order has to provide enough to pay the taker_amount
+            require(maker_filled >= taker_amount, ORDERS_DO_NOT_CROSS);
+        }
 
         transfer(
             Identity::ContractId(ContractId::from(ONE_DELTA_ORDERS_CONTRACT_ID)),
@@ -0,0 +1,227 @@
+use crate::utils::{
+    create_order, deposit_for_order, setup, INSUFFICIENT_PROFIT, INVALID_MATCH, INVALID_PATH,
+    NOTHING_TO_MATCH, ORDERS_DO_NOT_CROSS,
+};
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::errors::{transaction::Reason, Error};
+use fuels::types::{Identity, B512};
+use test_harness::interface::orders::sign_order;
+use test_harness::interface::{BatchSwapStep, OneDeltaOrders, Order, OrderRouter};
+use test_harness::types::encode_mira_params;
+
+async fn match_orders(
+    router: &OrderRouter<WalletUnlocked>,
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    (left, left_signature): (&Order, B512),
+    (right, right_signature): (&Order, B512),
+    spread_receiver: &WalletUnlocked,
+) -> Result<(u64, u64, u64), Error> {
+    router
+        .methods()
+        .match_orders(
+            left.clone(),
+            left_signature,
+            right.clone(),
+            right_signature,
+            Identity::Address(spread_receiver.address().into()),
+        )
+        .with_contracts(&[orders])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .map(|response| response.value)
+}
+
+fn assert_revert<T: std::fmt::Debug>(result: Result<T, Error>, expected: u64) {
+    match result {
+        Err(Error::Transaction(Reason::Reverted { revert_id, .. })) => {
+            assert_eq!(revert_id, expected)
+        }
+        Err(e) => panic!("expected revert {expected}, got {e:?}"),
+        Ok(value) => panic!("expected revert {expected}, got {value:?}"),
+    }
+}
+
+#[tokio::test]
+async fn cannot_match_orders_that_do_not_cross() {
+    let (orders, router, _, _, (maker_0, maker_1, matcher), (token_0_id, token_1_id), _) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // left sells 10k for 20k, right only pays 19.999k for 10k
+    let left = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let right = create_order(&maker_1, token_1_id, token_0_id, 19_999, 10_000, 1);
+
+    let left_signature = sign_order(&maker_0, &left, orders_id).await;
+    let right_signature = sign_order(&maker_1, &right, orders_id).await;
+
+    let result = match_orders(
+        &router,
+        &orders,
+        (&left, left_signature),
+        (&right, right_signature),
+        &matcher,
+    )
+    .await;
+    assert_revert(result, ORDERS_DO_NOT_CROSS);
+}
+
+#[tokio::test]
+async fn cannot_match_orders_on_same_side() {
+    let (orders, router, _, _, (maker_0, maker_1, matcher), (token_0_id, token_1_id), _) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let left = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let right = create_order(&maker_1, token_0_id, token_1_id, 10_000, 20_000, 1);
+
+    let left_signature = sign_order(&maker_0, &left, orders_id).await;
+    let right_signature = sign_order(&maker_1, &right, orders_id).await;
+
+    let result = match_orders(
+        &router,
+        &orders,
+        (&left, left_signature),
+        (&right, right_signature),
+        &matcher,
+    )
+    .await;
+    assert_revert(result, INVALID_MATCH);
+}
+
+#[tokio::test]
+async fn cannot_match_dust_that_rounds_to_zero() {
+    let (orders, router, _, _, (maker_0, maker_1, matcher), (token_0_id, token_1_id), _) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // the prices cross, but 1 unit of the right order
+    // buys less than 1 unit of the left taker_asset
+    let left = create_order(&maker_0, token_0_id, token_1_id, 3, 2, 1);
+    let right = create_order(&maker_1, token_1_id, token_0_id, 1, 1, 1);
+    deposit_for_order(&orders, &maker_0, &left).await;
+    deposit_for_order(&orders, &maker_1, &right).await;
+
+    let left_signature = sign_order(&maker_0, &left, orders_id).await;
+    let right_signature = sign_order(&maker_1, &right, orders_id).await;
+
+    let result = match_orders(
+        &router,
+        &orders,
+        (&left, left_signature),
+        (&right, right_signature),
+        &matcher,
+    )
+    .await;
+    assert_revert(result, ORDERS_DO_NOT_CROSS);
+}
+
+#[tokio::test]
+async fn cannot_match_filled_order() {
+    let (orders, router, _, _, (maker_0, maker_1, matcher), (token_0_id, token_1_id), _) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let left = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let right = create_order(&maker_1, token_1_id, token_0_id, 22_000, 10_000, 1);
+    deposit_for_order(&orders, &maker_0, &left).await;
+    deposit_for_order(&orders, &maker_1, &right).await;
+
+    let left_signature = sign_order(&maker_0, &left, orders_id).await;
+    let right_signature = sign_order(&maker_1, &right, orders_id).await;
+
+    match_orders(
+        &router,
+        &orders,
+        (&left, left_signature.clone()),
+        (&right, right_signature.clone()),
+        &matcher,
+    )
+    .await
+    .unwrap();
+
+    let result = match_orders(
+        &router,
+        &orders,
+        (&left, left_signature),
+        (&right, right_signature),
+        &matcher,
+    )
+    .await;
+    assert_revert(result, NOTHING_TO_MATCH);
+}
+
+#[tokio::test]
+async fn fill_order_and_swap_respects_minimum_profit() {
+    let (orders, router, amm, _, (maker_0, _, matcher), (token_0_id, token_1_id), swap_fees) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // the pool pays less than 10k for 10k, so the profit is below 1k
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 9_000, 1);
+    deposit_for_order(&orders, &maker_0, &order).await;
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+
+    let path = vec![BatchSwapStep {
+        dex_id: 0,
+        asset_in: token_0_id,
+        asset_out: token_1_id,
+        receiver: Identity::ContractId(router.contract_id().into()),
+        data: encode_mira_params(swap_fees.0, false),
+    }];
+
+    let result = router
+        .methods()
+        .fill_order_and_swap(
+            order,
+            signature,
+            9_000,
+            path,
+            1_000,
+            Identity::Address(matcher.address().into()),
+        )
+        .with_contracts(&[&orders, &amm.instance])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .map(|response| response.value);
+    assert_revert(result, INSUFFICIENT_PROFIT);
+}
+
+#[tokio::test]
+async fn fill_order_and_swap_rejects_path_to_other_asset() {
+    let (orders, router, amm, _, (maker_0, _, matcher), (token_0_id, token_1_id), swap_fees) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 9_000, 1);
+    deposit_for_order(&orders, &maker_0, &order).await;
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+
+    // the path does not end with the taker_asset
+    let path = vec![BatchSwapStep {
+        dex_id: 0,
+        asset_in: token_1_id,
+        asset_out: token_0_id,
+        receiver: Identity::ContractId(router.contract_id().into()),
+        data: encode_mira_params(swap_fees.0, false),
+    }];
+
+    let result = router
+        .methods()
+        .fill_order_and_swap(
+            order,
+            signature,
+            9_000,
+            path,
+            0,
+            Identity::Address(matcher.address().into()),
+        )
+        .with_contracts(&[&orders, &amm.instance])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .map(|response| response.value);
+    assert_revert(result, INVALID_PATH);
+}
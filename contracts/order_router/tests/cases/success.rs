@@ -0,0 +1,249 @@
+use crate::utils::{create_order, deposit_for_order, setup};
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::Identity;
+use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::orders::{get_order_fill_status, sign_order};
+use test_harness::interface::BatchSwapStep;
+use test_harness::types::encode_mira_params;
+use test_harness::utils::common::asset_balance;
+
+#[tokio::test]
+async fn match_crossing_orders_pays_spread() {
+    let (orders, router, _, _, (maker_0, maker_1, matcher), (token_0_id, token_1_id), _) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // left sells 10k for 20k, right buys 10k for 22k
+    let left = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let right = create_order(&maker_1, token_1_id, token_0_id, 22_000, 10_000, 1);
+    deposit_for_order(&orders, &maker_0, &left).await;
+    deposit_for_order(&orders, &maker_1, &right).await;
+
+    let left_signature = sign_order(&maker_0, &left, orders_id).await;
+    let right_signature = sign_order(&maker_1, &right, orders_id).await;
+
+    let maker_0_balance_before = asset_balance(&maker_0, &token_1_id).await;
+    let maker_1_balance_before = asset_balance(&maker_1, &token_0_id).await;
+    let matcher_balance_before = asset_balance(&matcher, &token_1_id).await;
+
+    let (left_taker_filled, left_maker_filled, spread) = router
+        .methods()
+        .match_orders(
+            left.clone(),
+            left_signature,
+            right.clone(),
+            right_signature,
+            Identity::Address(matcher.address().into()),
+        )
+        .with_contracts(&[&orders])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(left_taker_filled, 20_000);
+    assert_eq!(left_maker_filled, 10_000);
+    assert_eq!(spread, 2_000);
+
+    // makers receive their taker amounts, the matcher the spread
+    assert_eq!(
+        asset_balance(&maker_0, &token_1_id).await,
+        maker_0_balance_before + 20_000
+    );
+    assert_eq!(
+        asset_balance(&maker_1, &token_0_id).await,
+        maker_1_balance_before + 10_000
+    );
+    assert_eq!(
+        asset_balance(&matcher, &token_1_id).await,
+        matcher_balance_before + 2_000
+    );
+
+    assert_eq!(get_order_fill_status(&orders, &left).await, (false, 20_000));
+    assert_eq!(
+        get_order_fill_status(&orders, &right).await,
+        (false, 10_000)
+    );
+}
+
+#[tokio::test]
+async fn match_fills_left_up_to_right_remainder() {
+    let (orders, router, _, _, (maker_0, maker_1, matcher), (token_0_id, token_1_id), _) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // left sells 10k for 20k, right only buys 5k for 11k
+    let left = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let right = create_order(&maker_1, token_1_id, token_0_id, 11_000, 5_000, 1);
+    deposit_for_order(&orders, &maker_0, &left).await;
+    deposit_for_order(&orders, &maker_1, &right).await;
+
+    let left_signature = sign_order(&maker_0, &left, orders_id).await;
+    let right_signature = sign_order(&maker_1, &right, orders_id).await;
+
+    let (left_taker_filled, left_maker_filled, spread) = router
+        .methods()
+        .match_orders(
+            left.clone(),
+            left_signature,
+            right.clone(),
+            right_signature,
+            Identity::Address(matcher.address().into()),
+        )
+        .with_contracts(&[&orders])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(left_taker_filled, 10_000);
+    assert_eq!(left_maker_filled, 5_000);
+    assert_eq!(spread, 1_000);
+
+    // left is half filled, right is filled completely
+    assert_eq!(get_order_fill_status(&orders, &left).await, (false, 10_000));
+    assert_eq!(get_order_fill_status(&orders, &right).await, (false, 5_000));
+}
+
+#[tokio::test]
+async fn match_partially_filled_order_twice() {
+    let (orders, router, _, _, (maker_0, maker_1, matcher), (token_0_id, token_1_id), _) =
+        setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // right buys 10k for 22k, it is matched against two left orders
+    let right = create_order(&maker_1, token_1_id, token_0_id, 22_000, 10_000, 1);
+    let left_0 = create_order(&maker_0, token_0_id, token_1_id, 4_000, 8_000, 1);
+    let left_1 = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 2);
+    deposit_for_order(&orders, &maker_1, &right).await;
+    deposit_for_order(&orders, &maker_0, &left_0).await;
+    deposit_for_order(&orders, &maker_0, &left_1).await;
+
+    let right_signature = sign_order(&maker_1, &right, orders_id).await;
+    let left_0_signature = sign_order(&maker_0, &left_0, orders_id).await;
+    let left_1_signature = sign_order(&maker_0, &left_1, orders_id).await;
+
+    let (_, _, spread_0) = router
+        .methods()
+        .match_orders(
+            left_0.clone(),
+            left_0_signature,
+            right.clone(),
+            right_signature.clone(),
+            Identity::Address(matcher.address().into()),
+        )
+        .with_contracts(&[&orders])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(spread_0, 800);
+    assert_eq!(get_order_fill_status(&orders, &right).await, (false, 4_000));
+
+    // only the remaining 6k of the right order can be matched
+    let (left_taker_filled, left_maker_filled, spread_1) = router
+        .methods()
+        .match_orders(
+            left_1.clone(),
+            left_1_signature,
+            right.clone(),
+            right_signature,
+            Identity::Address(matcher.address().into()),
+        )
+        .with_contracts(&[&orders])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(left_taker_filled, 12_000);
+    assert_eq!(left_maker_filled, 6_000);
+    assert_eq!(spread_1, 1_200);
+
+    assert_eq!(
+        get_order_fill_status(&orders, &right).await,
+        (false, 10_000)
+    );
+    assert_eq!(
+        get_order_fill_status(&orders, &left_1).await,
+        (false, 12_000)
+    );
+}
+
+#[tokio::test]
+async fn fill_order_and_swap_pays_profit() {
+    let (
+        orders,
+        router,
+        amm,
+        pool_id_0_1,
+        (maker_0, _, matcher),
+        (token_0_id, token_1_id),
+        swap_fees,
+    ) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // the order sells 10k for 9k, below the pool price
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 9_000, 1);
+    deposit_for_order(&orders, &maker_0, &order).await;
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+
+    let path = vec![BatchSwapStep {
+        dex_id: 0,
+        asset_in: token_0_id,
+        asset_out: token_1_id,
+        receiver: Identity::ContractId(router.contract_id().into()),
+        data: encode_mira_params(swap_fees.0, false),
+    }];
+
+    let maker_balance_before = asset_balance(&maker_0, &token_1_id).await;
+    let matcher_balance_before = asset_balance(&matcher, &token_1_id).await;
+    let pool_metadata_before = pool_metadata(&amm.instance, pool_id_0_1)
+        .await
+        .value
+        .unwrap();
+
+    let profit = router
+        .methods()
+        .fill_order_and_swap(
+            order.clone(),
+            signature,
+            9_000,
+            path,
+            1,
+            Identity::Address(matcher.address().into()),
+        )
+        .with_contracts(&[&orders, &amm.instance])
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let pool_metadata_after = pool_metadata(&amm.instance, pool_id_0_1)
+        .await
+        .value
+        .unwrap();
+    let swap_output = pool_metadata_before.reserve_1 - pool_metadata_after.reserve_1;
+
+    // the maker_asset is sold to the pool, the proceeds pay the maker and the profit
+    assert_eq!(
+        pool_metadata_after.reserve_0,
+        pool_metadata_before.reserve_0 + 10_000
+    );
+    assert_eq!(profit, swap_output - 9_000);
+    assert_eq!(
+        asset_balance(&maker_0, &token_1_id).await,
+        maker_balance_before + 9_000
+    );
+    assert_eq!(
+        asset_balance(&matcher, &token_1_id).await,
+        matcher_balance_before + profit
+    );
+    assert_eq!(get_order_fill_status(&orders, &order).await, (false, 9_000));
+}
@@ -0,0 +1,211 @@
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{launch_custom_provider_and_get_wallets, VariableOutputPolicy, WalletsConfig};
+use fuels::types::{AssetId, Bits256, Identity};
+use test_harness::data_structures::MiraAMMContract;
+use test_harness::interface::amm::{create_pool, fees, initialize_ownership};
+use test_harness::interface::mock::{
+    add_token, deploy_mock_token_contract, get_sub_id, mint_tokens,
+};
+use test_harness::interface::orders::{
+    deploy_one_delta_orders_contract, deploy_order_router_contract, deposit,
+};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::{
+    AddLiquidityScript, AddLiquidityScriptConfigurables, MockToken, OneDeltaOrders, Order,
+    OrderRouter,
+};
+use test_harness::paths::ADD_LIQUIDITY_SCRIPT_BINARY_PATH;
+use test_harness::setup::common::deploy_amm;
+use test_harness::types::PoolId;
+use test_harness::utils::common::order_sub_ids;
+
+pub const MAX_EXPIRY: u64 = 4_294_967_295;
+pub const MINT_AMOUNT: u64 = 1_000_000_000;
+pub const POOL_LIQUIDITY: u64 = 1_000_000;
+
+// router error codes
+pub const INVALID_MATCH: u64 = 102;
+pub const ORDERS_DO_NOT_CROSS: u64 = 103;
+pub const NOTHING_TO_MATCH: u64 = 104;
+pub const INVALID_PATH: u64 = 105;
+pub const INSUFFICIENT_PROFIT: u64 = 106;
+
+////////////////////////////////////////////////////
+// Create 2 tokens, a 0-1 Mira pool and 3 wallets
+// maker_0 holds token_0, maker_1 holds token_1
+// the matcher provides the pool liquidity
+////////////////////////////////////////////////////
+pub async fn setup() -> (
+    OneDeltaOrders<WalletUnlocked>,
+    OrderRouter<WalletUnlocked>,
+    MiraAMMContract,
+    PoolId,
+    (WalletUnlocked, WalletUnlocked, WalletUnlocked),
+    (AssetId, AssetId),
+    (u64, u64, u64, u64),
+) {
+    let wallets = launch_custom_provider_and_get_wallets(
+        WalletsConfig::new(Some(3), Some(1), Some(1_000_000_000)),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let (maker_0, maker_1, matcher) = (wallets[0].clone(), wallets[1].clone(), wallets[2].clone());
+
+    ////////////////////////////////////////////////////
+    // deploy mira v1
+    ////////////////////////////////////////////////////
+
+    let amm: MiraAMMContract = deploy_amm(&matcher).await;
+    initialize_ownership(&amm.instance, Identity::Address(matcher.address().into())).await;
+
+    ////////////////////////////////////////////////////
+    // deploy tokens and mint
+    ////////////////////////////////////////////////////
+
+    let (token_contract_id, token_contract) = deploy_mock_token_contract(&matcher).await;
+
+    let token_0_id = add_token(&token_contract, "TOKEN_A".to_string(), "TKA".to_string(), 9)
+        .await
+        .value;
+    let token_1_id = add_token(&token_contract, "TOKEN_B".to_string(), "TKB".to_string(), 9)
+        .await
+        .value;
+
+    let mut all_assets = vec![token_0_id, token_1_id];
+    all_assets.sort();
+    let [token_0_id, token_1_id] = all_assets[..] else {
+        unreachable!("two assets were added")
+    };
+
+    let token_0_sub_id = get_sub_id(&token_contract, token_0_id).await.value.unwrap();
+    let token_1_sub_id = get_sub_id(&token_contract, token_1_id).await.value.unwrap();
+
+    mint_tokens(&token_contract, token_0_id, MINT_AMOUNT).await;
+    mint_tokens(&token_contract, token_1_id, MINT_AMOUNT).await;
+    mint_tokens(
+        &MockToken::new(token_contract_id, maker_0.clone()),
+        token_0_id,
+        MINT_AMOUNT,
+    )
+    .await;
+    mint_tokens(
+        &MockToken::new(token_contract_id, maker_1.clone()),
+        token_1_id,
+        MINT_AMOUNT,
+    )
+    .await;
+
+    ////////////////////////////////////////////////////
+    // create dex pool and add liquidity
+    ////////////////////////////////////////////////////
+
+    let (token_a_sub_id, token_b_sub_id) =
+        order_sub_ids((token_0_id, token_1_id), (token_0_sub_id, token_1_sub_id));
+
+    let pool_id_0_1 = create_pool(
+        &amm.instance,
+        &token_contract,
+        token_contract_id,
+        token_a_sub_id,
+        token_contract_id,
+        token_b_sub_id,
+        false,
+    )
+    .await
+    .value;
+
+    let deadline = matcher
+        .provider()
+        .unwrap()
+        .latest_block_height()
+        .await
+        .unwrap()
+        + 30;
+
+    let add_liquidity_script_configurables = AddLiquidityScriptConfigurables::default()
+        .with_MIRA_AMM_CONTRACT_ID(amm.id)
+        .unwrap();
+    let add_liquidity_script_instance =
+        AddLiquidityScript::new(matcher.clone(), ADD_LIQUIDITY_SCRIPT_BINARY_PATH)
+            .with_configurables(add_liquidity_script_configurables);
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &matcher,
+        &vec![(token_0_id, POOL_LIQUIDITY), (token_1_id, POOL_LIQUIDITY)],
+    )
+    .await;
+
+    add_liquidity_script_instance
+        .main(
+            pool_id_0_1,
+            POOL_LIQUIDITY,
+            POOL_LIQUIDITY,
+            0,
+            0,
+            matcher.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+
+    let swap_fees = fees(&amm.instance).await.value;
+
+    ////////////////////////////////////////////////////
+    // deploy orders and router
+    ////////////////////////////////////////////////////
+
+    let (orders_id, orders) = deploy_one_delta_orders_contract(&matcher).await;
+    let (_, router) = deploy_order_router_contract(&matcher, orders_id, amm.id).await;
+
+    (
+        orders,
+        router,
+        amm,
+        pool_id_0_1,
+        (maker_0, maker_1, matcher),
+        (token_0_id, token_1_id),
+        swap_fees,
+    )
+}
+
+// deposit the maker_amount of an order on behalf of its maker
+pub async fn deposit_for_order(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    maker: &WalletUnlocked,
+    order: &Order,
+) {
+    deposit(
+        &orders.clone().with_account(maker.clone()),
+        AssetId::from(order.maker_asset.0),
+        order.maker_amount,
+        Identity::Address(maker.address().into()),
+    )
+    .await;
+}
+
+pub fn create_order(
+    maker: &WalletUnlocked,
+    maker_asset: AssetId,
+    taker_asset: AssetId,
+    maker_amount: u64,
+    taker_amount: u64,
+    nonce: u64,
+) -> Order {
+    Order {
+        maker_asset: Bits256(*maker_asset),
+        taker_asset: Bits256(*taker_asset),
+        maker_amount,
+        taker_amount,
+        maker: Bits256(*maker.address().hash()),
+        nonce,
+        maker_traits: MAX_EXPIRY,
+        maker_receiver: Bits256::zeroed(),
+    }
+}
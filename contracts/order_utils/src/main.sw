@@ -1,7 +1,7 @@
 library;
 pub mod structs;
 
-use structs::Order;
+use structs::{Order, PriceCondition, SignerDelegation};
 use std::{
     b512::B512,
     bytes::Bytes,
@@ -28,6 +28,40 @@ abi IFlashCallback {
     );
 }
 
+// makers can provide liquidity just in time and redeploy proceeds
+// the hook is the maker contract itself, i.e. `maker_receiver == maker`
+// `before_fill` has to transfer at least `maker_amount` of `maker_asset`
+// to the orders contract that is then credited to the maker
+// `after_fill` is called once the hook received the taker amount
+abi IMakerHook {
+    #[storage(read, write)]
+    fn before_fill(
+        order_hash: b256,
+        maker: b256,
+        maker_asset: b256,
+        taker_asset: b256,
+        maker_amount: u64,
+        taker_amount: u64,
+    );
+
+    #[storage(read, write)]
+    fn after_fill(
+        order_hash: b256,
+        maker: b256,
+        maker_asset: b256,
+        taker_asset: b256,
+        maker_amount: u64,
+        taker_amount: u64,
+    );
+}
+
+// contract makers cannot sign, as such they validate
+// the order hash themselves when this is called
+abi ISignatureValidator {
+    #[storage(read)]
+    fn is_valid_signature(order_hash: b256, signature: B512) -> bool;
+}
+
 // the order hash is the sha256 hash of the packed
 // verifying contract address, followed by the order values
 pub fn compute_order_hash(order: Order, verifying_contract: b256) -> b256 {
@@ -50,6 +84,98 @@ pub fn pack_order(order: Order, verifying_contract: b256) -> Bytes {
     encoded_order
 }
 
+// conditional orders append the packed condition to the packed order
+pub fn compute_conditional_order_hash(
+    order: Order,
+    condition: PriceCondition,
+    verifying_contract: b256,
+) -> b256 {
+    let mut encoded_order = pack_order(order, verifying_contract);
+    encoded_order.append(pack_condition(condition));
+    sha256(encoded_order)
+}
+
+pub fn pack_condition(condition: PriceCondition) -> Bytes {
+    let mut encoded: Bytes = condition.market.to_be_bytes();
+    encoded.append(condition.price_feed_id.to_be_bytes());
+    encoded.append(condition.trigger_price.to_be_bytes());
+    encoded.append(condition.trigger_exponent.to_be_bytes());
+    encoded.push(if condition.is_above { 1u8 } else { 0u8 });
+    encoded
+}
+
+// operation types for signed maker operations
+pub const WITHDRAW_OPERATION: u8 = 0u8;
+pub const INVALIDATE_NONCE_OPERATION: u8 = 1u8;
+pub const CANCEL_ORDER_OPERATION: u8 = 2u8;
+pub const REGISTER_DELEGATE_OPERATION: u8 = 3u8;
+
+// the operation hash is the sha256 hash of the packed
+// verifying contract address, operation type, maker, operation nonce
+// and the operation payload
+pub fn compute_operation_hash(
+    operation: u8,
+    maker: b256,
+    operation_nonce: u64,
+    payload: Bytes,
+    verifying_contract: b256,
+) -> b256 {
+    let mut encoded: Bytes = verifying_contract.to_be_bytes();
+    encoded.push(operation);
+    encoded.append(maker.to_be_bytes());
+    encoded.append(operation_nonce.to_be_bytes());
+    encoded.append(payload);
+    sha256(encoded)
+}
+
+// identities are packed as 1 byte flag (0 address, 1 contract) followed by the bits
+pub fn pack_identity(identity: Identity) -> Bytes {
+    let mut encoded = Bytes::new();
+    match identity {
+        Identity::Address(address) => {
+            encoded.push(0u8);
+            encoded.append(address.bits().to_be_bytes());
+        },
+        Identity::ContractId(contract_id) => {
+            encoded.push(1u8);
+            encoded.append(contract_id.bits().to_be_bytes());
+        },
+    };
+    encoded
+}
+
+pub fn pack_withdraw(asset: b256, amount: u64, receiver: Identity) -> Bytes {
+    let mut encoded: Bytes = asset.to_be_bytes();
+    encoded.append(amount.to_be_bytes());
+    encoded.append(pack_identity(receiver));
+    encoded
+}
+
+pub fn pack_invalidate_nonce(maker_asset: b256, taker_asset: b256, new_nonce: u64) -> Bytes {
+    let mut encoded: Bytes = maker_asset.to_be_bytes();
+    encoded.append(taker_asset.to_be_bytes());
+    encoded.append(new_nonce.to_be_bytes());
+    encoded
+}
+
+// a removal is packed as a single zero byte
+pub fn pack_delegation(signer_delegate: b256, delegation: Option<SignerDelegation>) -> Bytes {
+    let mut encoded: Bytes = signer_delegate.to_be_bytes();
+    match delegation {
+        Some(d) => {
+            encoded.push(1u8);
+            encoded.append(d.expiry.to_be_bytes());
+            encoded.append(d.maker_asset.to_be_bytes());
+            encoded.append(d.taker_asset.to_be_bytes());
+            encoded.append(d.max_maker_amount.to_be_bytes());
+        },
+        None => {
+            encoded.push(0u8);
+        },
+    };
+    encoded
+}
+
 // computes the maker amount relative to the rates given in the order and taker amount
 pub fn compute_maker_fill_amount(
     taker_fill_amount: u64,
@@ -82,6 +208,9 @@ pub fn min64(a: u64, b: u64) -> u64 {
 
 const HIGH_BIT_0: u64 = 1u64 << 63u64;
 const HIGH_BIT_1: u64 = 1u64 << 62u64;
+const HIGH_BIT_2: u64 = 1u64 << 61u64;
+const HIGH_BIT_3: u64 = 1u64 << 60u64;
+const HIGH_BIT_4: u64 = 1u64 << 59u64;
 const EXPIRY_MASK: u64 = 0x00000000ffffffff;
 
 // extract the expiry from the maker_traits field
@@ -102,6 +231,55 @@ pub fn no_partial_fill(maker_traits: u64) -> bool {
     maker_traits & HIGH_BIT_1 != 0u64
 }
 
+// check if the maker is a contract that validates the signature itself
+pub fn is_contract_signer(maker_traits: u64) -> bool {
+    maker_traits & HIGH_BIT_2 != 0u64
+}
+
+// check if the maker_receiver is a hook that is called before and after the fill
+pub fn has_maker_hook(maker_traits: u64) -> bool {
+    maker_traits & HIGH_BIT_3 != 0u64
+}
+
+// check if the order is only fillable once a price condition is met
+pub fn is_conditional(maker_traits: u64) -> bool {
+    maker_traits & HIGH_BIT_4 != 0u64
+}
+
+// larger exponents overflow the scaled prices in `is_condition_met`
+pub const MAX_PRICE_EXPONENT: u32 = 38u32;
+
+// check whether an oracle price (`price * 10^-exponent`) meets the condition
+// both sides are scaled to a common exponent to compare them
+// out of range exponents never meet a condition
+pub fn is_condition_met(condition: PriceCondition, price: u64, exponent: u32) -> bool {
+    if exponent > MAX_PRICE_EXPONENT || condition.trigger_exponent > MAX_PRICE_EXPONENT {
+        return false;
+    }
+    let ten: u256 = 10u256;
+    let price_scaled: u256 = u256::from(price) * ten.pow(condition.trigger_exponent);
+    let trigger_scaled: u256 = u256::from(condition.trigger_price) * ten.pow(exponent);
+    if condition.is_above {
+        price_scaled >= trigger_scaled
+    } else {
+        price_scaled <= trigger_scaled
+    }
+}
+
+// check if a delegation covers the given pair and maker amount at height `at`
+pub fn is_in_delegation_scope(
+    delegation: SignerDelegation,
+    maker_asset: b256,
+    taker_asset: b256,
+    maker_amount: u64,
+    at: u32,
+) -> bool {
+    delegation.expiry >= at
+        && (delegation.maker_asset == b256::zero() || delegation.maker_asset == maker_asset)
+        && (delegation.taker_asset == b256::zero() || delegation.taker_asset == taker_asset)
+        && (delegation.max_maker_amount == 0 || maker_amount <= delegation.max_maker_amount)
+}
+
 // The interface for interacting with Rfq orders 
 abi OneDeltaOrders {
     #[storage(write, read), payable]
@@ -119,18 +297,108 @@ abi OneDeltaOrders {
     #[storage(write, read)]
     fn withdraw(asset: b256, amount: u64, receiver: Identity);
 
+    #[storage(write, read)]
+    fn withdraw_many(withdrawals: Vec<(b256, u64)>, receiver: Identity);
+
+    #[storage(write, read)]
+    fn withdraw_all(receiver: Identity) -> Vec<(b256, u64)>;
+
     #[storage(write, read)]
     fn invalidate_nonce(maker_asset: b256, taker_asset: b256, new_nonce: u64);
 
+    #[storage(write, read), payable]
+    fn fill_conditional(
+        order: Order,
+        condition: PriceCondition,
+        order_signature: B512,
+        taker_fill_amount: u64,
+        taker_receiver: Identity,
+        data: Option<Bytes>,
+    ) -> (u64, u64);
+
     #[storage(write, read)]
     fn cancel_order(order: Order);
 
+    #[storage(write, read)]
+    fn cancel_conditional_order(order: Order, condition: PriceCondition);
+
     #[storage(write)]
     fn register_order_signer_delegate(signer_delegate: b256, allowed: bool);
 
+    #[storage(write)]
+    fn register_order_signer_delegate_scoped(signer_delegate: b256, delegation: SignerDelegation);
+
+    #[storage(write, read)]
+    fn withdraw_signed(
+        maker: b256,
+        asset: b256,
+        amount: u64,
+        receiver: Identity,
+        operation_nonce: u64,
+        signature: B512,
+    );
+
+    #[storage(write, read)]
+    fn invalidate_nonce_signed(
+        maker: b256,
+        maker_asset: b256,
+        taker_asset: b256,
+        new_nonce: u64,
+        operation_nonce: u64,
+        signature: B512,
+    );
+
+    #[storage(write, read)]
+    fn cancel_order_signed(order: Order, operation_nonce: u64, signature: B512);
+
+    #[storage(write, read)]
+    fn cancel_conditional_order_signed(
+        order: Order,
+        condition: PriceCondition,
+        operation_nonce: u64,
+        signature: B512,
+    );
+
+    #[storage(write, read)]
+    fn register_order_signer_delegate_signed(
+        maker: b256,
+        signer_delegate: b256,
+        delegation: Option<SignerDelegation>,
+        operation_nonce: u64,
+        signature: B512,
+    );
+
+    #[storage(read)]
+    fn get_operation_nonce(maker: b256) -> u64;
+
+    #[storage(read, write)]
+    fn initialize(initial_owner: Identity);
+
+    #[storage(read, write)]
+    fn transfer_ownership(new_owner: Identity);
+
+    #[storage(read, write)]
+    fn set_paused(paused: bool);
+
+    #[storage(read, write)]
+    fn set_pair_paused(maker_asset: b256, taker_asset: b256, paused: bool);
+
+    #[storage(read)]
+    fn owner() -> Identity;
+
+    #[storage(read)]
+    fn is_paused(maker_asset: b256, taker_asset: b256) -> bool;
+
     #[storage(read)]
     fn validate_order(order: Order, order_signature: B512) -> (b256, u64, u64);
 
+    #[storage(read)]
+    fn validate_conditional_order(
+        order: Order,
+        condition: PriceCondition,
+        order_signature: B512,
+    ) -> (b256, u64, u64);
+
     #[storage(read)]
     fn get_order_fill_status(order_hash: b256) -> (bool, u64);
 
@@ -143,8 +411,33 @@ abi OneDeltaOrders {
     #[storage(read)]
     fn get_maker_balance(maker: b256, asset: b256) -> u64;
 
+    #[storage(read)]
+    fn get_maker_asset_count(maker: b256) -> u64;
+
+    #[storage(read)]
+    fn get_maker_balances(maker: b256, start: u64, count: u64) -> Vec<(b256, u64)>;
+
     #[storage(read)]
     fn is_order_signer_delegate(signer: b256, signer_delegate: b256) -> bool;
+
+    #[storage(read)]
+    fn get_order_signer_delegation(signer: b256, signer_delegate: b256) -> Option<SignerDelegation>;
+}
+
+// convert an order and signature to bytes, inverse of `to_order_and_sig`
+pub fn pack_order_and_sig(order: Order, signature: B512) -> Bytes {
+    let mut encoded: Bytes = order.maker_asset.to_be_bytes();
+    encoded.append(order.taker_asset.to_be_bytes());
+    encoded.append(order.maker_amount.to_be_bytes());
+    encoded.append(order.taker_amount.to_be_bytes());
+    encoded.append(order.maker.to_be_bytes());
+    encoded.append(order.nonce.to_be_bytes());
+    encoded.append(order.maker_traits.to_be_bytes());
+    encoded.append(order.maker_receiver.to_be_bytes());
+    let signature_bits = signature.bits();
+    encoded.append(signature_bits[0].to_be_bytes());
+    encoded.append(signature_bits[1].to_be_bytes());
+    encoded
 }
 
 // convert bytes to an order
@@ -182,15 +475,142 @@ fn test_maker_traits() {
     let expiry: u32 = 12345u32;
     let mut maker_traits: u64 = HIGH_BIT_0;
     maker_traits = maker_traits | HIGH_BIT_1;
+    maker_traits = maker_traits | HIGH_BIT_2;
+    maker_traits = maker_traits | HIGH_BIT_3;
+    maker_traits = maker_traits | HIGH_BIT_4;
     maker_traits = maker_traits | u64::from(expiry);
     assert_eq(is_contract_receiver(maker_traits), true);
     assert_eq(get_expiry(maker_traits), expiry);
     assert_eq(no_partial_fill(maker_traits), true);
+    assert_eq(is_contract_signer(maker_traits), true);
+    assert_eq(has_maker_hook(maker_traits), true);
+    assert_eq(is_conditional(maker_traits), true);
 
     // populating only the expiry
     let expiry0: u32 = 9999999u32;
     let maker_traits0: u64 = u64::from(expiry0);
     assert_eq(is_contract_receiver(maker_traits0), false);
     assert_eq(no_partial_fill(maker_traits0), false);
+    assert_eq(is_contract_signer(maker_traits0), false);
+    assert_eq(has_maker_hook(maker_traits0), false);
+    assert_eq(is_conditional(maker_traits0), false);
     assert_eq(get_expiry(maker_traits0), expiry0);
 }
+
+#[test]
+fn test_order_and_sig_bytes() {
+    let order = Order {
+        maker_asset: 0x4d3a44b2e2e53a5a452f3acac85bdd4f0e38a170a5cfbe4dfce2c79bf21a0f07,
+        taker_asset: 0xa1e88e8fba0e93b94bee471d7447dcc86967389e0a8bf875a0f638c631627127,
+        maker_amount: 1000u64,
+        taker_amount: 2000u64,
+        maker: 0x0000000000000000000000000000000000000000000000000000000000000001,
+        nonce: 3u64,
+        maker_traits: HIGH_BIT_0 | 12345u64,
+        maker_receiver: 0x0000000000000000000000000000000000000000000000000000000000000002,
+    };
+    let signature = B512::from((
+        0x0000000000000000000000000000000000000000000000000000000000000003,
+        0x0000000000000000000000000000000000000000000000000000000000000004,
+    ));
+    let (decoded, decoded_signature) = to_order_and_sig(pack_order_and_sig(order, signature));
+    assert_eq(
+        compute_order_hash(decoded, b256::zero()),
+        compute_order_hash(order, b256::zero()),
+    );
+    assert(decoded_signature == signature);
+}
+
+#[test]
+fn test_delegation_scope() {
+    let maker_asset: b256 = 0x4d3a44b2e2e53a5a452f3acac85bdd4f0e38a170a5cfbe4dfce2c79bf21a0f07;
+    let taker_asset: b256 = 0xa1e88e8fba0e93b94bee471d7447dcc86967389e0a8bf875a0f638c631627127;
+
+    // unrestricted delegation
+    let unrestricted = SignerDelegation {
+        expiry: 4294967295u32,
+        maker_asset: b256::zero(),
+        taker_asset: b256::zero(),
+        max_maker_amount: 0,
+    };
+    assert_eq(
+        is_in_delegation_scope(unrestricted, maker_asset, taker_asset, 999999999u64, 100u32),
+        true,
+    );
+
+    // scoped delegation
+    let scoped = SignerDelegation {
+        expiry: 100u32,
+        maker_asset,
+        taker_asset,
+        max_maker_amount: 1000u64,
+    };
+    assert_eq(
+        is_in_delegation_scope(scoped, maker_asset, taker_asset, 1000u64, 100u32),
+        true,
+    );
+    // expired
+    assert_eq(
+        is_in_delegation_scope(scoped, maker_asset, taker_asset, 1000u64, 101u32),
+        false,
+    );
+    // amount too large
+    assert_eq(
+        is_in_delegation_scope(scoped, maker_asset, taker_asset, 1001u64, 100u32),
+        false,
+    );
+    // wrong pair
+    assert_eq(
+        is_in_delegation_scope(scoped, taker_asset, maker_asset, 1000u64, 100u32),
+        false,
+    );
+}
+
+#[test]
+fn test_price_condition() {
+    // take-profit at 2.5 with 1 decimal
+    let take_profit = PriceCondition {
+        market: b256::zero(),
+        price_feed_id: b256::zero(),
+        trigger_price: 25u64,
+        trigger_exponent: 1u32,
+        is_above: true,
+    };
+    // 2.49 with 2 decimals
+    assert_eq(is_condition_met(take_profit, 249u64, 2u32), false);
+    // 2.5 with 8 decimals
+    assert_eq(is_condition_met(take_profit, 250000000u64, 8u32), true);
+    // 3 with no decimals
+    assert_eq(is_condition_met(take_profit, 3u64, 0u32), true);
+
+    // stop-loss at 2.5 with 1 decimal
+    let stop_loss = PriceCondition {
+        market: b256::zero(),
+        price_feed_id: b256::zero(),
+        trigger_price: 25u64,
+        trigger_exponent: 1u32,
+        is_above: false,
+    };
+    assert_eq(is_condition_met(stop_loss, 249u64, 2u32), true);
+    assert_eq(is_condition_met(stop_loss, 250000000u64, 8u32), true);
+    assert_eq(is_condition_met(stop_loss, 3u64, 0u32), false);
+
+    // exponents beyond the bound do not overflow
+    assert_eq(is_condition_met(stop_loss, 1u64, 39u32), false);
+    assert_eq(
+        is_condition_met(
+            PriceCondition {
+                market: b256::zero(),
+                price_feed_id: b256::zero(),
+                trigger_price: u64::max(),
+                trigger_exponent: 39u32,
+                is_above: true,
+            },
+            u64::max(),
+            MAX_PRICE_EXPONENT,
+        ),
+        false,
+    );
+    // the bound itself is fine
+    assert_eq(is_condition_met(stop_loss, u64::max(), MAX_PRICE_EXPONENT), true);
+}
@@ -12,6 +12,28 @@ pub struct Order {
     pub maker_receiver: b256,
 }
 
+// the scope within which a delegate can sign on behalf of a maker
+// zero assets allow any asset, a zero max_maker_amount allows any amount
+pub struct SignerDelegation {
+    pub expiry: u32,
+    pub maker_asset: b256,
+    pub taker_asset: b256,
+    pub max_maker_amount: u64,
+}
+
+// a price trigger for conditional (stop-loss / take-profit) orders
+// the price is read via `get_price` of a Swaylend `market`
+// and compared against `trigger_price * 10^-trigger_exponent`
+// `is_above` triggers once the price is at or above the trigger (take-profit),
+// otherwise once the price is at or below the trigger (stop-loss)
+pub struct PriceCondition {
+    pub market: b256,
+    pub price_feed_id: b256,
+    pub trigger_price: u64,
+    pub trigger_exponent: u32,
+    pub is_above: bool,
+}
+
 // we log the hash and the funds exchanged
 pub struct OrderFillEvent {
     pub order_hash: b256,
@@ -45,3 +67,28 @@ pub struct CancelPairEvent {
     pub taker_asset: b256,
     pub nonce: u64,
 }
+
+// we log maker, delegate and the scope (None if removed)
+pub struct SignerDelegateEvent {
+    pub maker: b256,
+    pub signer_delegate: b256,
+    pub delegation: Option<SignerDelegation>,
+}
+
+// we log the global pause state
+pub struct PauseEvent {
+    pub paused: bool,
+}
+
+// we log the pair and its pause state
+pub struct PairPauseEvent {
+    pub maker_asset: b256,
+    pub taker_asset: b256,
+    pub paused: bool,
+}
+
+// we log the previous and the new owner
+pub struct OwnershipTransferEvent {
+    pub previous_owner: Identity,
+    pub new_owner: Identity,
+}
@@ -0,0 +1,42 @@
+library;
+
+/// Minimal lending market interface
+/// collateral is supplied per asset, base is a single asset
+/// that can be supplied or borrowed, but not both at once
+abi BasicLender {
+    #[payable, storage(write)]
+    fn supply_collateral(); // Payment is required: any collateral asset
+
+    #[storage(write)]
+    fn withdraw_collateral(asset_id: AssetId, amount: u64);
+
+    #[payable, storage(write)]
+    fn supply(); // Payment is required: base asset
+
+    /// withdraws supplied base, never borrows
+    #[storage(write)]
+    fn withdraw(amount: u64);
+
+    #[storage(write)]
+    fn borrow(amount: u64);
+
+    #[payable, storage(write)]
+    fn repay(); // Payment is required: base asset
+
+    #[storage(read)]
+    fn get_base_asset() -> AssetId;
+
+    #[storage(read)]
+    fn get_collateral(account: Identity, asset_id: AssetId) -> u64;
+
+    /// returns the supplied and borrowed base of `account`
+    #[storage(read)]
+    fn get_supply_borrow(account: Identity) -> (u64, u64);
+
+    /// returns the base that `account` can borrow in addition to its debt
+    #[storage(read)]
+    fn available_to_borrow(account: Identity) -> u64;
+
+    #[storage(read)]
+    fn is_liquidatable(account: Identity) -> bool;
+}
@@ -0,0 +1,214 @@
+library;
+
+use std::{asset::transfer, auth::msg_sender, bytes::Bytes, revert::revert};
+use core::codec::{abi_decode, encode};
+use interfaces::{data_structures::PoolId, mira_amm::MiraAMM};
+use math::pool_math::add_fee;
+use mira_v1_swap::swap::get_mira_amount_in;
+use ::lenders::{Lender, LenderAdapter};
+use ::structs::{FlashLoopAction, LenderId, MigrateAction};
+
+////////////////////////////////////////////////////
+// Error codes
+////////////////////////////////////////////////////
+const INVALID_FLASH_AMOUNT: u64 = 109;
+const FLASH_INPUT_TOO_HIGH: u64 = 110;
+const INVALID_FLASH_CALLER: u64 = 111;
+const INVALID_MIGRATION: u64 = 112;
+
+/// Receiver of the migrated position
+abi PositionMigration {
+    /// deposits the forwarded collateral and borrows `borrow_amount`
+    /// to repay the flash swap of the migration
+    #[payable, storage(read)]
+    fn migrate_in(migration: MigrateAction, borrow_amount: u64);
+}
+
+/// Data passed through the Mira flash swap
+/// with the lender and the input amount owed to the pool
+pub enum FlashCallback {
+    Loop: (Lender, FlashLoopAction, u64),
+    Migrate: (Lender, MigrateAction, u64),
+}
+
+/// Flash swaps the asset to receive from the Mira pool to `self_identity`
+/// the swap input is paid in `settle_flash_loop` via the `IBaseCallee` hook
+pub fn start_flash_loop(
+    lender: Lender,
+    flash_loop: FlashLoopAction,
+    self_identity: Identity,
+    mira_amm_contract_id: ContractId,
+) {
+    let mut flash_loop = flash_loop;
+    let (asset_in, asset_out) = if flash_loop.open {
+        (flash_loop.base_asset, flash_loop.collateral_asset)
+    } else {
+        // 0 indicates full debt repay
+        if flash_loop.amount == 0 {
+            let (_, user_borrow) = lender.get_supply_borrow(self_identity);
+            flash_loop.amount = user_borrow;
+        }
+        (flash_loop.collateral_asset, flash_loop.base_asset)
+    };
+    if flash_loop.amount == 0 {
+        revert(INVALID_FLASH_AMOUNT);
+    }
+
+    // the input is paid from the lender position
+    let amount_in = get_mira_amount_in(
+        mira_amm_contract_id,
+        asset_in,
+        asset_out,
+        flash_loop
+            .is_stable,
+        flash_loop
+            .fee,
+        flash_loop
+            .amount,
+    );
+    if amount_in > flash_loop.maximum_in {
+        revert(FLASH_INPUT_TOO_HIGH);
+    }
+
+    let (pool_id, amount_0_out, amount_1_out): (PoolId, u64, u64) = if asset_in.bits() < asset_out.bits() {
+        ((asset_in, asset_out, flash_loop.is_stable), 0, flash_loop.amount)
+    } else {
+        ((asset_out, asset_in, flash_loop.is_stable), flash_loop.amount, 0)
+    };
+
+    abi(MiraAMM, mira_amm_contract_id.into()).swap(
+        pool_id,
+        amount_0_out,
+        amount_1_out,
+        self_identity,
+        Option::Some(Bytes::from(encode(FlashCallback::Loop((lender, flash_loop, amount_in))))),
+    );
+}
+
+/// Flash borrows the debt to migrate from the Mira pool to `self_identity`
+/// the debt is repaid and the flash swap settled in `settle_flash_swap`
+pub fn start_migration(
+    lender: Lender,
+    migration: MigrateAction,
+    self_identity: Identity,
+    mira_amm_contract_id: ContractId,
+) {
+    let mut migration = migration;
+    if Identity::ContractId(migration.target) == self_identity {
+        revert(INVALID_MIGRATION);
+    }
+
+    // 0 indicates full debt migration
+    if migration.debt_amount == 0 {
+        let (_, user_borrow) = lender.get_supply_borrow(self_identity);
+        migration.debt_amount = user_borrow;
+    }
+    if migration.debt_amount == 0 {
+        revert(INVALID_FLASH_AMOUNT);
+    }
+
+    // the base is flash borrowed and paid back in base
+    let (amount_0_out, amount_1_out) = if migration.flash_pool.0 == migration.base_asset {
+        (migration.debt_amount, 0)
+    } else if migration.flash_pool.1 == migration.base_asset {
+        (0, migration.debt_amount)
+    } else {
+        revert(INVALID_MIGRATION)
+    };
+    let amount_in = add_fee(migration.debt_amount, migration.fee);
+
+    abi(MiraAMM, mira_amm_contract_id.into()).swap(
+        migration
+            .flash_pool,
+        amount_0_out,
+        amount_1_out,
+        self_identity,
+        Option::Some(Bytes::from(encode(FlashCallback::Migrate((lender, migration, amount_in))))),
+    );
+}
+
+/// Receives a migrated position from another account
+/// the forwarded collateral is deposited and the base owed to the Mira pool borrowed
+pub fn receive_migration(
+    migration: MigrateAction,
+    collateral_amount: u64,
+    borrow_amount: u64,
+    mira_amm_contract_id: ContractId,
+) {
+    let lender = match LenderId::from_u64(migration.lender_id) {
+        Some(lender) => Lender::new(lender, migration.market),
+        None => revert(INVALID_MIGRATION),
+    };
+    if collateral_amount != 0 {
+        lender.deposit_collateral(migration.collateral_asset, collateral_amount);
+    }
+    lender.borrow(migration.base_asset, borrow_amount, migration.data);
+    transfer(
+        Identity::ContractId(mira_amm_contract_id),
+        migration
+            .base_asset,
+        borrow_amount,
+    );
+}
+
+/// Settles a flash loop or migration from the `IBaseCallee` hook
+/// the flash swap has to be initiated by `self_identity` through the Mira AMM
+pub fn settle_flash_swap(
+    sender: Identity,
+    flash_data: Bytes,
+    self_identity: Identity,
+    mira_amm_contract_id: ContractId,
+) {
+    if msg_sender().unwrap() != Identity::ContractId(mira_amm_contract_id)
+        || sender != self_identity
+    {
+        revert(INVALID_FLASH_CALLER);
+    }
+
+    match abi_decode::<FlashCallback>(flash_data.as_raw_slice()) {
+        FlashCallback::Loop((lender, flash_loop, amount_in)) => settle_flash_loop(lender, flash_loop, amount_in, mira_amm_contract_id),
+        FlashCallback::Migrate((lender, migration, amount_in)) => settle_migration(lender, migration, amount_in),
+    }
+}
+
+fn settle_flash_loop(
+    lender: Lender,
+    flash_loop: FlashLoopAction,
+    amount_in: u64,
+    mira_amm_contract_id: ContractId,
+) {
+    let mira_amm = Identity::ContractId(mira_amm_contract_id);
+
+    if flash_loop.open {
+        // deposit the flashed collateral and borrow the base to pay for it
+        lender.deposit_collateral(flash_loop.collateral_asset, flash_loop.amount);
+        lender.borrow(flash_loop.base_asset, amount_in, flash_loop.data);
+        transfer(mira_amm, flash_loop.base_asset, amount_in);
+    } else {
+        // repay with the flashed base and withdraw the collateral to pay for it
+        lender.repay(flash_loop.base_asset, flash_loop.amount);
+        lender.withdraw_collateral(flash_loop.collateral_asset, amount_in, flash_loop.data);
+        transfer(mira_amm, flash_loop.collateral_asset, amount_in);
+    }
+}
+
+fn settle_migration(lender: Lender, migration: MigrateAction, amount_in: u64) {
+    // repay with the flashed base and move the collateral to the target
+    lender.repay(migration.base_asset, migration.debt_amount);
+    if migration.collateral_amount != 0 {
+        lender.withdraw_collateral(
+            migration
+                .collateral_asset,
+            migration
+                .collateral_amount,
+            migration
+                .data,
+        );
+    }
+
+    // the target borrows the base owed to the pool
+    abi(PositionMigration, migration.target.bits()).migrate_in {
+        asset_id: migration.collateral_asset.bits(),
+        coins: migration.collateral_amount,
+    }(migration, amount_in);
+}
@@ -0,0 +1,115 @@
+library;
+
+pub mod swaylend;
+pub mod basic;
+
+use market_abi::structs::PriceDataUpdate;
+use ::structs::LenderId;
+use swaylend::SwaylendAdapter;
+use basic::BasicLenderAdapter;
+
+/// Lending protocol operations used by the action interpreter
+/// `market` is the protocol contract the adapter is created for,
+/// positions are held by the caller of the interpreter
+pub trait LenderAdapter {
+    fn deposit_collateral(self, asset: AssetId, amount: u64);
+    fn withdraw_collateral(self, asset: AssetId, amount: u64, data: Option<PriceDataUpdate>);
+    fn borrow(self, asset: AssetId, amount: u64, data: Option<PriceDataUpdate>);
+    fn repay(self, asset: AssetId, amount: u64);
+    fn deposit_base(self, asset: AssetId, amount: u64);
+    fn withdraw_base(self, asset: AssetId, amount: u64, data: Option<PriceDataUpdate>);
+    /// returns the supplied and borrowed base of `account`
+    fn get_supply_borrow(self, account: Identity) -> (u64, u64);
+    /// returns the base that `account` can borrow in addition to its debt
+    fn available_to_borrow(self, account: Identity) -> u64;
+    fn is_liquidatable(self, account: Identity) -> bool;
+    /// returns the fee to forward for a price update, zero if none is needed
+    fn price_update_fee(self, data: Option<PriceDataUpdate>) -> u64;
+}
+
+/// Adapter registry, a new protocol is added as a `LenderId` and a variant here
+pub enum Lender {
+    Swaylend: SwaylendAdapter,
+    Basic: BasicLenderAdapter,
+}
+
+impl Lender {
+    pub fn new(lender_id: LenderId, market: ContractId) -> Lender {
+        match lender_id {
+            LenderId::SwaylendUSDC => Lender::Swaylend(SwaylendAdapter { market }),
+            LenderId::BasicLender => Lender::Basic(BasicLenderAdapter { market }),
+        }
+    }
+}
+
+impl LenderAdapter for Lender {
+    fn deposit_collateral(self, asset: AssetId, amount: u64) {
+        match self {
+            Lender::Swaylend(adapter) => adapter.deposit_collateral(asset, amount),
+            Lender::Basic(adapter) => adapter.deposit_collateral(asset, amount),
+        }
+    }
+
+    fn withdraw_collateral(self, asset: AssetId, amount: u64, data: Option<PriceDataUpdate>) {
+        match self {
+            Lender::Swaylend(adapter) => adapter.withdraw_collateral(asset, amount, data),
+            Lender::Basic(adapter) => adapter.withdraw_collateral(asset, amount, data),
+        }
+    }
+
+    fn borrow(self, asset: AssetId, amount: u64, data: Option<PriceDataUpdate>) {
+        match self {
+            Lender::Swaylend(adapter) => adapter.borrow(asset, amount, data),
+            Lender::Basic(adapter) => adapter.borrow(asset, amount, data),
+        }
+    }
+
+    fn repay(self, asset: AssetId, amount: u64) {
+        match self {
+            Lender::Swaylend(adapter) => adapter.repay(asset, amount),
+            Lender::Basic(adapter) => adapter.repay(asset, amount),
+        }
+    }
+
+    fn deposit_base(self, asset: AssetId, amount: u64) {
+        match self {
+            Lender::Swaylend(adapter) => adapter.deposit_base(asset, amount),
+            Lender::Basic(adapter) => adapter.deposit_base(asset, amount),
+        }
+    }
+
+    fn withdraw_base(self, asset: AssetId, amount: u64, data: Option<PriceDataUpdate>) {
+        match self {
+            Lender::Swaylend(adapter) => adapter.withdraw_base(asset, amount, data),
+            Lender::Basic(adapter) => adapter.withdraw_base(asset, amount, data),
+        }
+    }
+
+    fn get_supply_borrow(self, account: Identity) -> (u64, u64) {
+        match self {
+            Lender::Swaylend(adapter) => adapter.get_supply_borrow(account),
+            Lender::Basic(adapter) => adapter.get_supply_borrow(account),
+        }
+    }
+
+    fn available_to_borrow(self, account: Identity) -> u64 {
+        match self {
+            Lender::Swaylend(adapter) => adapter.available_to_borrow(account),
+            Lender::Basic(adapter) => adapter.available_to_borrow(account),
+        }
+    }
+
+    fn is_liquidatable(self, account: Identity) -> bool {
+        match self {
+            Lender::Swaylend(adapter) => adapter.is_liquidatable(account),
+            Lender::Basic(adapter) => adapter.is_liquidatable(account),
+        }
+    }
+
+    fn price_update_fee(self, data: Option<PriceDataUpdate>) -> u64 {
+        match self {
+            Lender::Swaylend(adapter) => adapter.price_update_fee(data),
+            Lender::Basic(adapter) => adapter.price_update_fee(data),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+library;
+
+use basic_lender_abi::BasicLender;
+use market_abi::structs::PriceDataUpdate;
+use ::lenders::LenderAdapter;
+
+/// Lender implementing the `BasicLender` interface, no price data is used
+pub struct BasicLenderAdapter {
+    pub market: ContractId,
+}
+
+impl LenderAdapter for BasicLenderAdapter {
+    fn deposit_collateral(self, asset: AssetId, amount: u64) {
+        abi(BasicLender, self.market.into())
+            .supply_collateral {
+                asset_id: asset.into(),
+                coins: amount,
+            }();
+    }
+
+    fn withdraw_collateral(self, asset: AssetId, amount: u64, _data: Option<PriceDataUpdate>) {
+        abi(BasicLender, self.market.into()).withdraw_collateral(asset, amount);
+    }
+
+    fn borrow(self, _asset: AssetId, amount: u64, _data: Option<PriceDataUpdate>) {
+        abi(BasicLender, self.market.into()).borrow(amount);
+    }
+
+    fn repay(self, asset: AssetId, amount: u64) {
+        abi(BasicLender, self.market.into())
+            .repay {
+                asset_id: asset.into(),
+                coins: amount,
+            }();
+    }
+
+    fn deposit_base(self, asset: AssetId, amount: u64) {
+        abi(BasicLender, self.market.into())
+            .supply {
+                asset_id: asset.into(),
+                coins: amount,
+            }();
+    }
+
+    fn withdraw_base(self, _asset: AssetId, amount: u64, _data: Option<PriceDataUpdate>) {
+        abi(BasicLender, self.market.into()).withdraw(amount);
+    }
+
+    fn get_supply_borrow(self, account: Identity) -> (u64, u64) {
+        abi(BasicLender, self.market.into()).get_supply_borrow(account)
+    }
+
+    fn available_to_borrow(self, account: Identity) -> u64 {
+        abi(BasicLender, self.market.into()).available_to_borrow(account)
+    }
+
+    fn is_liquidatable(self, account: Identity) -> bool {
+        abi(BasicLender, self.market.into()).is_liquidatable(account)
+    }
+
+    fn price_update_fee(self, _data: Option<PriceDataUpdate>) -> u64 {
+        0
+    }
+}
@@ -0,0 +1,79 @@
+library;
+
+use market_abi::{Market, structs::PriceDataUpdate};
+use ::lenders::LenderAdapter;
+
+/// Swaylend market, any base asset market can be used via `market`
+pub struct SwaylendAdapter {
+    pub market: ContractId,
+}
+
+impl LenderAdapter for SwaylendAdapter {
+    fn deposit_collateral(self, asset: AssetId, amount: u64) {
+        abi(Market, self.market.into())
+            .supply_collateral {
+                asset_id: asset.into(),
+                coins: amount,
+            }();
+    }
+
+    fn withdraw_collateral(self, asset: AssetId, amount: u64, data: Option<PriceDataUpdate>) {
+        abi(Market, self.market.into())
+            .withdraw_collateral {
+                asset_id: AssetId::base().bits(),
+                coins: self.price_update_fee(data),
+            }(asset, amount, data.unwrap());
+    }
+
+    fn borrow(self, _asset: AssetId, amount: u64, data: Option<PriceDataUpdate>) {
+        abi(Market, self.market.into())
+            .withdraw_base {
+                asset_id: AssetId::base().bits(),
+                coins: self.price_update_fee(data),
+            }(amount, data.unwrap());
+    }
+
+    fn repay(self, asset: AssetId, amount: u64) {
+        abi(Market, self.market.into())
+            .supply_base {
+                asset_id: asset.into(),
+                coins: amount,
+            }();
+    }
+
+    fn deposit_base(self, asset: AssetId, amount: u64) {
+        abi(Market, self.market.into())
+            .supply_base {
+                asset_id: asset.into(),
+                coins: amount,
+            }();
+    }
+
+    fn withdraw_base(self, _asset: AssetId, amount: u64, data: Option<PriceDataUpdate>) {
+        abi(Market, self.market.into())
+            .withdraw_base {
+                asset_id: AssetId::base().bits(),
+                coins: self.price_update_fee(data),
+            }(amount, data.unwrap());
+    }
+
+    fn get_supply_borrow(self, account: Identity) -> (u64, u64) {
+        let (supply, borrow) = abi(Market, self.market.into()).get_user_supply_borrow(account);
+        (u64::try_from(supply).unwrap(), u64::try_from(borrow).unwrap())
+    }
+
+    fn available_to_borrow(self, account: Identity) -> u64 {
+        u64::try_from(abi(Market, self.market.into()).available_to_borrow(account)).unwrap()
+    }
+
+    fn is_liquidatable(self, account: Identity) -> bool {
+        abi(Market, self.market.into()).is_liquidatable(account)
+    }
+
+    /// Swaylend requires price data for any action that reduces collateralization
+    /// and the update fee is paid in the base asset of the chain
+    fn price_update_fee(self, data: Option<PriceDataUpdate>) -> u64 {
+        require(data.is_some(), "price data not defined");
+        data.unwrap().update_fee
+    }
+}
@@ -0,0 +1,437 @@
+library;
+
+pub mod structs;
+pub mod lenders;
+pub mod flash;
+
+use executor::{execute_exact_in, get_dex_input_receiver};
+use interfaces::mira_amm::MiraAMM;
+use math::pool_math::get_deposit_amounts;
+use utils::blockchain_utils::get_lp_asset;
+use std::{asset::transfer, context::balance_of, revert::revert};
+use lenders::{Lender, LenderAdapter};
+use flash::{start_flash_loop, start_migration};
+use structs::{
+    Action,
+    AddLiquidityAction,
+    AmountType,
+    HealthCheck,
+    LenderAction,
+    LenderActionType,
+    LenderId,
+    RemoveLiquidityAction,
+    SwapPath,
+    TransferAction,
+};
+
+////////////////////////////////////////////////////
+// Error codes
+////////////////////////////////////////////////////
+const EMPTY_PATH_ENTRY: u64 = 100;
+const EMPTY_ACTION_ENTRY: u64 = 101;
+const INVALID_LENDER_ID: u64 = 102;
+const INVALID_ACTION_TYPE: u64 = 103;
+const INVALID_AMOUNT_TYPE: u64 = 104;
+const INVALID_BALANCE: u64 = 105;
+const WITHDRAW_EXCEEDS_SUPPLY: u64 = 106;
+const INSUFFICIENT_HEALTH: u64 = 107;
+const FLASH_LOOP_UNSUPPORTED: u64 = 108;
+const INVALID_POOL: u64 = 113;
+const INVALID_LIQUIDITY_AMOUNT: u64 = 114;
+
+const HEALTH_SCALE: u256 = 1_000_000_000_000_000_000u256;
+
+/// Returns the full balance of `asset` that can be used by `self_identity`
+/// a contract uses its own balance, a script has no queryable balance
+/// and uses (and resets) the amount received from the previous action
+fn full_balance(self_identity: Identity, asset: AssetId, ref mut amount_cached: u64) -> u64 {
+    match self_identity {
+        Identity::ContractId(contract_id) => balance_of(contract_id, asset),
+        Identity::Address(_) => {
+            // TEMP: make sure that assignment is via values
+            let am = amount_cached + 0;
+            // reset amount cached after it was used
+            amount_cached = 0;
+            am
+        },
+    }
+}
+
+/// Reverts if the position of `account` is liquidatable or its health,
+/// the borrow capacity (debt plus available to borrow) over the debt, is below `min_health`
+/// positions without debt are always healthy
+fn check_health(lender: Lender, account: Identity, min_health: u64) {
+    if lender.is_liquidatable(account) {
+        revert(INSUFFICIENT_HEALTH);
+    }
+    let (_, debt) = lender.get_supply_borrow(account);
+    if debt == 0 {
+        return;
+    }
+    let debt_u256: u256 = debt.into();
+    let available_u256: u256 = lender.available_to_borrow(account).into();
+    let min_health_u256: u256 = min_health.into();
+    if (available_u256 + debt_u256) * HEALTH_SCALE / debt_u256 < min_health_u256 {
+        revert(INSUFFICIENT_HEALTH);
+    }
+}
+
+/// Executes a list of actions
+/// `self_identity` is the entity that holds funds and lender positions,
+/// i.e. the account contract or the address running the composer script
+pub fn execute_actions(
+    actions: Vec<Action>,
+    self_identity: Identity,
+    mira_amm_contract_id: ContractId,
+    one_delta_orders_contract_id: ContractId,
+) {
+    // use cached amount for split swaps
+    let mut amount_cached = 0u64;
+
+    // health checks are deferred until all actions are executed
+    let mut health_checks: Vec<HealthCheck> = Vec::new();
+
+    // start to go through actions
+    let mut j = 0;
+    while j < actions.len() {
+        match actions.get(j) {
+            Some(Action::Swap(swap_path_list)) => {
+                // start to swap through paths
+                let mut i = 0;
+                while i < swap_path_list.paths.len() {
+                    // get current path, input amount, slippage_check, transfer_in flag and path
+                    let (current_amount_in, minimum_out, transfer_in, current_path) = match swap_path_list.paths.get(i) {
+                        Option::Some(SwapPath {
+                            amount_in,
+                            min_amount_out,
+                            transfer_in,
+                            steps,
+                        }) => (amount_in, min_amount_out, transfer_in, steps),
+                        Option::None => revert(EMPTY_PATH_ENTRY),
+                    };
+
+                    // get the amount to be used
+                    // if zero, we use the last cached amount to swap splits
+                    // after a single swap
+                    // if the cached amount is used, we reset it to zero
+                    let mut amount_in_used = if current_amount_in != 0 {
+                        current_amount_in
+                    } else {
+                        // TEMP: make sure that assignment is via values
+                        let am = amount_cached + 0;
+                        // reset amount cached after it was used
+                        amount_cached = 0;
+                        am
+                    };
+
+                    // get path length for iteration
+                    let path_length = current_path.len();
+
+                    // initialize first swap step (from action j)
+                    let mut swap_step = current_path.get(0).unwrap();
+
+                    // transfer to first DEX if needed
+                    if transfer_in {
+                        transfer(
+                            get_dex_input_receiver(
+                                swap_step
+                                    .dex_id,
+                                swap_step
+                                    .data,
+                                mira_amm_contract_id,
+                                one_delta_orders_contract_id,
+                            ),
+                            swap_step
+                                .asset_in,
+                            amount_in_used,
+                        );
+                    }
+                    // start swapping the path via index k
+                    let mut k = 0;
+                    while true {
+                        //=============================================
+                        //      DEX swap execution
+                        //=============================================
+
+                        // execute swap
+                        amount_in_used = execute_exact_in(
+                            u64::try_from(amount_in_used)
+                                .unwrap(),
+                            swap_step,
+                            mira_amm_contract_id,
+                            one_delta_orders_contract_id,
+                        );
+
+                        //=============================================
+                        //      DEX swap end
+                        //=============================================
+
+                        // increment swap step index within path
+                        k += 1;
+
+                        // check if we need to continue
+                        if k < path_length {
+                            // get next swap_step
+                            swap_step = current_path.get(k).unwrap();
+                        } else {
+                            // in this block, we completed a path
+                            // we record / increment the cached amount and check for slippage
+                            // increment cache
+                            amount_cached += amount_in_used;
+                            // check for slippage on path
+                            require(amount_in_used > minimum_out, "Insufficient output amount");
+                            // break and start next path
+                            break;
+                        }
+                    }
+                    // increment path index
+                    i += 1;
+                }
+                // increment action index
+                j += 1;
+            },
+            Some(Action::Lending(LenderAction {
+                lender_id,
+                action_id,
+                asset,
+                amount_in,
+                amount_type_id,
+                data,
+                market,
+                additional_params,
+            })) => {
+                let lender = match LenderId::from_u64(lender_id) {
+                    Some(lender) => lender,
+                    None => revert(INVALID_LENDER_ID),
+                };
+                let action = match LenderActionType::from_u16(action_id) {
+                    Some(action) => action,
+                    None => revert(INVALID_ACTION_TYPE),
+                };
+                let amount_type = match AmountType::from_u8(amount_type_id) {
+                    Some(amount_type) => amount_type,
+                    None => revert(INVALID_AMOUNT_TYPE),
+                };
+                let mut amount = match amount_type {
+                    AmountType::Received => {
+                        // TEMP: make sure that assignment is via values
+                        let am = amount_cached + 0;
+                        // reset amount cached after it was used
+                        amount_cached = 0;
+                        am
+                    },
+                    AmountType::Defined => amount_in,
+                };
+
+                // increment operation index
+                j += 1;
+
+                // get lending market adapter
+                let lender = Lender::new(lender, market);
+
+                match action {
+                    LenderActionType::Deposit => {
+                        lender.deposit_collateral(asset, amount);
+                    },
+                    LenderActionType::Borrow => {
+                        // 0 indicates full base deposit withdrawal
+                        if amount == 0 {
+                            let (base_deposit, _) = lender.get_supply_borrow(self_identity);
+                            if base_deposit == 0u64 {
+                                revert(INVALID_BALANCE);
+                            } else {
+                                amount = base_deposit;
+                            }
+                        }
+                        lender.borrow(asset, amount, data);
+                    },
+                    LenderActionType::Withdraw => {
+                        lender.withdraw_collateral(asset, amount, data);
+                    },
+                    LenderActionType::Repay => {
+                        // 0 indicates full balance repay
+                        if amount == 0 {
+                            let (_, user_borrow) = lender.get_supply_borrow(self_identity);
+                            if user_borrow == 0u64 {
+                                revert(INVALID_BALANCE);
+                            } else {
+                                amount = user_borrow;
+                            }
+                        }
+                        lender.repay(asset, amount);
+                    },
+                    LenderActionType::DepositBase => {
+                        // supplying base may repay debt first, a pure deposit requires no debt
+                        let (_, user_borrow) = lender.get_supply_borrow(self_identity);
+                        require(user_borrow == 0, "Outstanding base borrow");
+
+                        // max indicates full balance deposit
+                        if amount == u64::max() {
+                            amount = full_balance(self_identity, asset, amount_cached);
+                        }
+                        lender.deposit_base(asset, amount);
+                    },
+                    LenderActionType::WithdrawBase => {
+                        // withdrawing base may borrow beyond the supplied amount
+                        // so we never withdraw more than supplied
+                        let (base_deposit, _) = lender.get_supply_borrow(self_identity);
+                        if base_deposit == 0u64 {
+                            revert(INVALID_BALANCE);
+                        }
+                        // max indicates full supply withdrawal
+                        if amount == u64::max() {
+                            amount = base_deposit;
+                        } else if amount > base_deposit {
+                            revert(WITHDRAW_EXCEEDS_SUPPLY);
+                        }
+                        lender.withdraw_base(asset, amount, data);
+                    },
+                }
+            },
+            Some(Action::Transfer(TransferAction {
+                asset,
+                amount,
+                receiver,
+            })) => {
+                // zero for using the full balance
+                let amount_used = if amount != 0 {
+                    amount
+                } else {
+                    full_balance(self_identity, asset, amount_cached)
+                };
+                transfer(receiver, asset, amount_used);
+                j += 1;
+            },
+            Some(Action::AssertHealth(health_check)) => {
+                health_checks.push(health_check);
+                j += 1;
+            },
+            Some(Action::FlashLoop(flash_loop)) => {
+                // the flash callback can only be received by a contract
+                if let Identity::Address(_) = self_identity {
+                    revert(FLASH_LOOP_UNSUPPORTED);
+                }
+                let lender = match LenderId::from_u64(flash_loop.lender_id) {
+                    Some(lender) => Lender::new(lender, flash_loop.market),
+                    None => revert(INVALID_LENDER_ID),
+                };
+                start_flash_loop(lender, flash_loop, self_identity, mira_amm_contract_id);
+                j += 1;
+            },
+            Some(Action::Migrate(migration)) => {
+                // the flash callback can only be received by a contract
+                if let Identity::Address(_) = self_identity {
+                    revert(FLASH_LOOP_UNSUPPORTED);
+                }
+                let lender = match LenderId::from_u64(migration.lender_id) {
+                    Some(lender) => Lender::new(lender, migration.market),
+                    None => revert(INVALID_LENDER_ID),
+                };
+                start_migration(lender, migration, self_identity, mira_amm_contract_id);
+                j += 1;
+            },
+            Some(Action::AddLiquidity(AddLiquidityAction {
+                pool_id,
+                amount_0_desired,
+                amount_1_desired,
+                amount_0_min,
+                amount_1_min,
+                receiver,
+            })) => {
+                // the cache holds a single amount
+                require(
+                    amount_0_desired != 0 || amount_1_desired != 0,
+                    INVALID_LIQUIDITY_AMOUNT,
+                );
+                // zero for using the cached amount
+                let amount_0_used = if amount_0_desired != 0 {
+                    amount_0_desired
+                } else {
+                    // TEMP: make sure that assignment is via values
+                    let am = amount_cached + 0;
+                    // reset amount cached after it was used
+                    amount_cached = 0;
+                    am
+                };
+                let amount_1_used = if amount_1_desired != 0 {
+                    amount_1_desired
+                } else {
+                    // TEMP: make sure that assignment is via values
+                    let am = amount_cached + 0;
+                    // reset amount cached after it was used
+                    amount_cached = 0;
+                    am
+                };
+
+                let amm = abi(MiraAMM, mira_amm_contract_id.into());
+                let pool_meta = match amm.pool_metadata(pool_id) {
+                    Some(pool_meta) => pool_meta,
+                    None => revert(INVALID_POOL),
+                };
+                let (amount_0, amount_1) = get_deposit_amounts(
+                    amount_0_used,
+                    amount_1_used,
+                    amount_0_min,
+                    amount_1_min,
+                    pool_meta
+                        .reserve_0,
+                    pool_meta
+                        .reserve_1,
+                );
+
+                transfer(Identity::ContractId(mira_amm_contract_id), pool_id.0, amount_0);
+                transfer(Identity::ContractId(mira_amm_contract_id), pool_id.1, amount_1);
+
+                // the minted liquidity can be used by the next action
+                amount_cached += amm.mint(pool_id, receiver).amount;
+                j += 1;
+            },
+            Some(Action::RemoveLiquidity(RemoveLiquidityAction {
+                pool_id,
+                liquidity,
+                amount_0_min,
+                amount_1_min,
+                receiver,
+                cache_amount_1,
+            })) => {
+                // zero for using the cached amount
+                let liquidity_used = if liquidity != 0 {
+                    liquidity
+                } else {
+                    // TEMP: make sure that assignment is via values
+                    let am = amount_cached + 0;
+                    // reset amount cached after it was used
+                    amount_cached = 0;
+                    am
+                };
+
+                let (_, lp_asset) = get_lp_asset(mira_amm_contract_id, pool_id);
+                transfer(Identity::ContractId(mira_amm_contract_id), lp_asset, liquidity_used);
+
+                let (amount_0, amount_1) = abi(MiraAMM, mira_amm_contract_id.into()).burn(pool_id, receiver);
+                require(
+                    amount_0 >= amount_0_min && amount_1 >= amount_1_min,
+                    "Insufficient amount",
+                );
+                // one of the received amounts can be used by the next action
+                amount_cached += if cache_amount_1 { amount_1 } else { amount_0 };
+                j += 1;
+            },
+            None => {
+                revert(EMPTY_ACTION_ENTRY);
+            }
+        };
+    }
+
+    // check the final state of the positions
+    let mut i = 0;
+    while i < health_checks.len() {
+        let health_check = health_checks.get(i).unwrap();
+        let lender = match LenderId::from_u64(health_check.lender_id) {
+            Some(lender) => Lender::new(lender, health_check.market),
+            None => revert(INVALID_LENDER_ID),
+        };
+        check_health(lender, self_identity, health_check.min_health);
+        i += 1;
+    }
+}
@@ -0,0 +1,196 @@
+library;
+
+use executor::BatchSwapStep;
+use interfaces::data_structures::PoolId;
+use market_abi::structs::PriceDataUpdate;
+use std::bytes::Bytes;
+
+////////////////////////////////////////////////////
+// Types
+////////////////////////////////////////////////////
+pub struct SwapPath {
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    pub transfer_in: bool,
+    pub steps: Vec<BatchSwapStep>,
+}
+
+pub enum LenderId {
+    SwaylendUSDC: (),
+    BasicLender: (),
+}
+
+impl LenderId {
+    pub fn from_u64(value: u64) -> Option<LenderId> {
+        match value {
+            0 => Some(LenderId::SwaylendUSDC),
+            1 => Some(LenderId::BasicLender),
+            _ => None,
+        }
+    }
+
+    pub fn to_u64(self) -> u64 {
+        match self {
+            LenderId::SwaylendUSDC => 0,
+            LenderId::BasicLender => 1,
+        }
+    }
+}
+
+pub enum LenderActionType {
+    Deposit: (),
+    Borrow: (),
+    Withdraw: (),
+    Repay: (),
+    DepositBase: (),
+    WithdrawBase: (),
+}
+
+impl LenderActionType {
+    pub fn from_u16(value: u16) -> Option<LenderActionType> {
+        match value {
+            0 => Some(LenderActionType::Deposit),
+            1 => Some(LenderActionType::Borrow),
+            2 => Some(LenderActionType::Withdraw),
+            3 => Some(LenderActionType::Repay),
+            4 => Some(LenderActionType::DepositBase),
+            5 => Some(LenderActionType::WithdrawBase),
+            _ => None,
+        }
+    }
+
+    pub fn to_u16(self) -> u16 {
+        match self {
+            LenderActionType::Deposit => 0,
+            LenderActionType::Borrow => 1,
+            LenderActionType::Withdraw => 2,
+            LenderActionType::Repay => 3,
+            LenderActionType::DepositBase => 4,
+            LenderActionType::WithdrawBase => 5,
+        }
+    }
+}
+
+pub enum AmountType {
+    Received: (),
+    Defined: (),
+}
+
+impl AmountType {
+    pub fn from_u8(value: u8) -> Option<AmountType> {
+        match value {
+            0 => Some(AmountType::Received),
+            1 => Some(AmountType::Defined),
+            _ => None,
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            AmountType::Received => 0,
+            AmountType::Defined => 1,
+        }
+    }
+}
+
+pub struct LenderAction {
+    pub lender_id: u64,
+    pub action_id: u16,
+    pub asset: AssetId,
+    pub amount_in: u64,
+    pub amount_type_id: u8,
+    pub market: ContractId,
+    pub data: Option<PriceDataUpdate>,
+    pub additional_params: Option<Bytes>,
+}
+
+pub struct SwapPathList {
+    pub paths: Vec<SwapPath>,
+}
+
+pub struct TransferAction {
+    pub asset: AssetId,
+    pub amount: u64,
+    pub receiver: Identity,
+}
+
+/// Minimum health of a lender position, checked after all actions
+/// health is the borrow capacity over the debt, scaled by 1e18
+pub struct HealthCheck {
+    pub lender_id: u64,
+    pub market: ContractId,
+    pub min_health: u64,
+}
+
+/// Leverages or deleverages a lender position in one transaction
+/// the asset received is flash swapped from a Mira pool and the swap
+/// is paid from the lender position in the flash callback
+pub struct FlashLoopAction {
+    pub lender_id: u64,
+    pub market: ContractId,
+    pub collateral_asset: AssetId,
+    pub base_asset: AssetId,
+    pub is_stable: bool,
+    pub fee: u64,
+    /// open: flash collateral and borrow base, close: flash base and withdraw collateral
+    pub open: bool,
+    /// open: collateral to deposit, close: base to repay, zero for the full debt
+    pub amount: u64,
+    /// open: maximum base to borrow, close: maximum collateral to withdraw
+    pub maximum_in: u64,
+    pub data: Option<PriceDataUpdate>,
+}
+
+/// Moves collateral and debt of a lender position to another account of the same owner
+/// the debt is repaid with base flash borrowed from the Mira pool `flash_pool`
+/// and borrowed again by `target` to repay the flash swap
+pub struct MigrateAction {
+    pub lender_id: u64,
+    pub market: ContractId,
+    pub collateral_asset: AssetId,
+    pub base_asset: AssetId,
+    /// collateral to move, zero to only move debt
+    pub collateral_amount: u64,
+    /// base debt to move, zero for the full debt
+    pub debt_amount: u64,
+    pub target: ContractId,
+    pub flash_pool: PoolId,
+    pub fee: u64,
+    pub data: Option<PriceDataUpdate>,
+}
+
+/// Adds liquidity to a Mira pool, the LP tokens are minted to `receiver`
+/// a zero desired amount uses the amount received from the previous action,
+/// at most one of the desired amounts can be zero
+pub struct AddLiquidityAction {
+    pub pool_id: PoolId,
+    pub amount_0_desired: u64,
+    pub amount_1_desired: u64,
+    pub amount_0_min: u64,
+    pub amount_1_min: u64,
+    pub receiver: Identity,
+}
+
+/// Removes liquidity from a Mira pool, the pool assets are sent to `receiver`
+/// a zero liquidity uses the amount received from the previous action
+/// the received amount of `pool_id.0`, or of `pool_id.1` if `cache_amount_1`
+/// is set, is cached for the next action
+pub struct RemoveLiquidityAction {
+    pub pool_id: PoolId,
+    pub liquidity: u64,
+    pub amount_0_min: u64,
+    pub amount_1_min: u64,
+    pub receiver: Identity,
+    pub cache_amount_1: bool,
+}
+
+pub enum Action {
+    Swap: SwapPathList,
+    Lending: LenderAction,
+    Transfer: TransferAction,
+    AssertHealth: HealthCheck,
+    FlashLoop: FlashLoopAction,
+    Migrate: MigrateAction,
+    AddLiquidity: AddLiquidityAction,
+    RemoveLiquidity: RemoveLiquidityAction,
+}
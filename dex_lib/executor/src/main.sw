@@ -41,6 +41,40 @@ const ONE_DELTA_ORDERS_ID: u64 = 100;
 const INVALID_DEX = 1u64;
 const ORDER_OUTPUT_TOO_HIGH = 2u64;
 const ORDER_INCOMPLETE_FILL = 3u64;
+const INVALID_STEP_DATA = 4u64;
+const STEP_OUTPUT_TOO_LOW = 5u64;
+const STEP_PRICE_TOO_HIGH = 6u64;
+
+////////////////////////////////////////////////////
+// step data format
+////////////////////////////////////////////////////
+// Legacy step data has no header and is identified by its length
+// (3 or 35 bytes for Mira, 160 bytes for orders).
+// Versioned step data is laid out as follows
+// 1 byte   - the version
+// 1 byte   - flags
+// n bytes  - the dex parameters (the fee for Mira, the order for orders)
+// optional fields in the order of their flags
+// The header, the Mira fee and all optional fields (32, 8 and 32 bytes) have
+// even lengths, so versioned Mira data is even and at least 4 bytes long and
+// never equals the legacy 3 or 35 bytes. Versioned order data is at least
+// 162 bytes long and never equals the legacy 160 bytes.
+pub const STEP_DATA_VERSION_1: u8 = 1;
+// Mira: the pool is stable
+pub const STEP_FLAG_STABLE: u8 = 1;
+// a 32 byte dex address follows the parameters, e.g. a Mira fork
+pub const STEP_FLAG_DEX_ADDRESS: u8 = 2;
+// an 8 byte minimum output of the step
+pub const STEP_FLAG_MINIMUM_OUT: u8 = 4;
+// a 32 byte maximum price of the step as input per output scaled by 1e18
+pub const STEP_FLAG_MAXIMUM_PRICE: u8 = 8;
+// all flags known to version 1
+const STEP_FLAGS_V1: u8 = 15;
+
+const STEP_HEADER_LENGTH: u64 = 2;
+const MIRA_FEE_LENGTH: u64 = 2;
+const ORDER_PARAMS_LENGTH: u64 = 160;
+const PRICE_SCALE: u256 = 1_000_000_000_000_000_000u256;
 
 ////////////////////////////////////////////////////
 // swap functions - general
@@ -52,7 +86,7 @@ pub fn execute_exact_in(
     MIRA_AMM_CONTRACT_ID: ContractId,
     ONE_DELTA_ORDERS_CONTRACT_ID: ContractId,
 ) -> u64 {
-    match swap_step.dex_id {
+    let amount_out = match swap_step.dex_id {
         MIRA_V1_ID => execute_mira_v1_exact_in(
             amount_in,
             swap_step
@@ -78,7 +112,10 @@ pub fn execute_exact_in(
             ONE_DELTA_ORDERS_CONTRACT_ID,
         ),
         _ => revert(INVALID_DEX),
-    }
+    };
+    // check the optional limits of the step
+    check_step_limits(swap_step.dex_id, swap_step.data, amount_in, amount_out);
+    amount_out
 }
 
 // temporary function to calculate swap input amounts
@@ -140,8 +177,10 @@ pub fn calculate_amounts_exact_out_and_fund(
         };
     };
 
-    // check slippage
-    require(current_amount_out <= maximum_in, "Exceeding input amount");
+    // check slippage, a maximum of zero leaves it to the batch totals
+    require(maximum_in == 0 || current_amount_out <= maximum_in, "Exceeding input amount");
+    // the funded input amount is appended for the step limits
+    amounts.push(current_amount_out);
     // transfer first funds
     transfer(
         get_dex_input_receiver(
@@ -168,10 +207,13 @@ pub fn forward_swap_exact_out(
 ) {
     let path_length = current_path.len();
     let mut i = path_length - 1;
+    // the input of the first swap is the funded amount, the input
+    // of every other swap is the output of the previous one
+    let mut amount_in = computed_amounts.get(path_length).unwrap();
     while true {
         let swap_step = current_path.get(i).unwrap();
         let current_amount = computed_amounts.get(i).unwrap();
-        match swap_step.dex_id {
+        let amount_out = match swap_step.dex_id {
             MIRA_V1_ID => {
                 let (is_stable, amm_contract) = get_mira_is_stable_and_pool(swap_step.data, MIRA_AMM_CONTRACT_ID);
                 let (amount0, amount1, pool_id) = if swap_step.asset_in.bits() < swap_step.asset_out.bits() {
@@ -188,8 +230,12 @@ pub fn forward_swap_exact_out(
                     amount1,
                     amm_contract,
                 );
+                // for Mira, the amount is the output
+                current_amount
             },
             ONE_DELTA_ORDERS_ID => {
+                // for orders, the amount is the input
+                amount_in = current_amount;
                 execute_one_delta_orders_exact_in(
                     current_amount,
                     swap_step
@@ -201,10 +247,13 @@ pub fn forward_swap_exact_out(
                     swap_step
                         .data,
                     ONE_DELTA_ORDERS_CONTRACT_ID,
-                );
+                )
             },
             _ => revert(INVALID_DEX),
-        }
+        };
+        // check the optional limits of the step
+        check_step_limits(swap_step.dex_id, swap_step.data, amount_in, amount_out);
+        amount_in = amount_out;
         if i != 0 { i -= 1; } else { break; }
     };
 }
@@ -272,6 +321,112 @@ pub fn forward_swap_exact_out(
 //     };
 // }
 
+////////////////////////////////////////////////////
+// batch totals
+////////////////////////////////////////////////////
+
+// adds an amount to the total of an asset
+pub fn add_to_total(ref mut totals: Vec<(AssetId, u64)>, asset: AssetId, amount: u64) {
+    let mut i = 0;
+    while i < totals.len() {
+        let (total_asset, total) = totals.get(i).unwrap();
+        if total_asset == asset {
+            totals.set(i, (asset, total + amount));
+            return;
+        }
+        i += 1;
+    }
+    totals.push((asset, amount));
+}
+
+// deducts an amount from the total of an asset, floored at zero
+pub fn deduct_from_total(ref mut totals: Vec<(AssetId, u64)>, asset: AssetId, amount: u64) {
+    let mut i = 0;
+    while i < totals.len() {
+        let (total_asset, total) = totals.get(i).unwrap();
+        if total_asset == asset {
+            let new_total = if total > amount { total - amount } else { 0 };
+            totals.set(i, (asset, new_total));
+            return;
+        }
+        i += 1;
+    }
+}
+
+pub fn get_total(totals: Vec<(AssetId, u64)>, asset: AssetId) -> u64 {
+    let mut i = 0;
+    while i < totals.len() {
+        let (total_asset, total) = totals.get(i).unwrap();
+        if total_asset == asset {
+            return total;
+        }
+        i += 1;
+    }
+    0
+}
+
+pub fn has_limit(limits: Vec<(AssetId, u64)>, asset: AssetId) -> bool {
+    for limit in limits.iter() {
+        if limit.0 == asset {
+            return true;
+        }
+    }
+    false
+}
+
+// every limit is the minimum total output of its asset
+// `unlimited_assets` are the outputs of paths without a minimum, these
+// need a limit unless they are swapped further by later paths
+pub fn check_minimum_totals(
+    output_totals: Vec<(AssetId, u64)>,
+    minimum_totals: Vec<(AssetId, u64)>,
+    unlimited_assets: Vec<AssetId>,
+) {
+    for asset in unlimited_assets.iter() {
+        require(
+            get_total(output_totals, asset) == 0 || has_limit(minimum_totals, asset),
+            "Missing output limit",
+        );
+    }
+    for limit in minimum_totals.iter() {
+        require(get_total(output_totals, limit.0) >= limit.1, "Insufficient total output");
+    }
+}
+
+fn get_net_input(
+    input_totals: Vec<(AssetId, u64)>,
+    output_totals: Vec<(AssetId, u64)>,
+    asset: AssetId,
+) -> u64 {
+    let input = get_total(input_totals, asset);
+    let output = get_total(output_totals, asset);
+    if input > output { input - output } else { 0 }
+}
+
+// every limit is the maximum net input of its asset, outputs of
+// the same asset that are used by other paths are deducted
+// `unlimited_assets` are the inputs of paths without a maximum, these
+// need a limit unless they are funded by other paths
+pub fn check_maximum_totals(
+    input_totals: Vec<(AssetId, u64)>,
+    output_totals: Vec<(AssetId, u64)>,
+    maximum_totals: Vec<(AssetId, u64)>,
+    unlimited_assets: Vec<AssetId>,
+) {
+    for asset in unlimited_assets.iter() {
+        require(
+            get_net_input(input_totals, output_totals, asset) == 0 || has_limit(maximum_totals, asset),
+            "Missing input limit",
+        );
+    }
+    for limit in maximum_totals.iter() {
+        require(
+            get_net_input(input_totals, output_totals, limit.0) <= limit.1,
+            "Exceeding total input",
+        );
+    }
+}
+
 ////////////////////////////////////////////////////
 // get dex address
 ////////////////////////////////////////////////////
@@ -325,7 +480,7 @@ pub fn execute_one_delta_orders_exact_in(
     ONE_DELTA_ORDERS_CONTRACT_ID: ContractId,
 ) -> u64 {
     // decode order and signature
-    let (order, signature) = to_order(data, asset_in, asset_out);
+    let (order, signature) = to_order(get_order_data(data), asset_in, asset_out);
 
     // execute order fill
     let (taker_fill_amount, maker_fill_amount) = abi(OneDeltaOrders, ONE_DELTA_ORDERS_CONTRACT_ID.into()).fill(order, signature, amount_in, receiver, Option::None);
@@ -339,9 +494,8 @@ pub fn execute_one_delta_orders_exact_in(
     maker_fill_amount
 }
 
-// expect the data of 3 bytes be laid out as follows
-// 2 bytes  - for the fee as u16
-// 1 byte   - for a flag as u8
+// expect either legacy data of 3 or 35 bytes (with the fork address)
+// or versioned data with the fee as parameter
 pub fn get_mira_like_params(
     data: Bytes,
     MIRA_AMM_CONTRACT_ID: ContractId,
@@ -355,25 +509,23 @@ pub fn get_mira_like_params(
             let (fee, is_stable, forked_pool) = get_mira_and_fork_params(data);
             (fee, is_stable, ContractId::from(forked_pool))
         },
-        _ => revert(INVALID_DEX),
+        _ => {
+            let (flags, fee_bytes, fields) = split_step_data(data, MIRA_FEE_LENGTH);
+            let fee = u64::from(first_be_bytes_to_u16(fee_bytes));
+            let is_stable = (flags & STEP_FLAG_STABLE) != 0;
+            let (dex_address, _, _) = read_step_fields(flags, fields);
+            let amm_contract = match dex_address {
+                Option::Some(v) => ContractId::from(v),
+                Option::None => MIRA_AMM_CONTRACT_ID,
+            };
+            (fee, is_stable, amm_contract)
+        },
     }
 }
 
-// expect the data of 3 bytes be laid out as follows
-// 2 bytes  - for the fee as u16
-// 1 byte   - for a flag as u8
 pub fn get_mira_like_pool(data: Bytes, MIRA_AMM_CONTRACT_ID: ContractId) -> ContractId {
-    match data.len() {
-        3 => {
-            MIRA_AMM_CONTRACT_ID
-        },
-        35 => {
-            let (_, pool_id) = data.split_at(3);
-            // return parsed fees as u64 and flag
-           ContractId::from(b256::from_be_bytes(pool_id))
-        },
-        _ => revert(INVALID_DEX),
-    }
+    let (_, _, amm_contract) = get_mira_like_params(data, MIRA_AMM_CONTRACT_ID);
+    amm_contract
 }
 
 // expect the data of 3 bytes be laid out as follows
@@ -416,28 +568,128 @@ pub fn get_mira_is_stable(data: Bytes) -> bool {
     }
 }
 
-// expect the data of 3 bytes be laid out as follows
-// 2 bytes  - for the fee as u16
-// 1 byte   - for a flag as u8
 pub fn get_mira_is_stable_and_pool(data: Bytes, MIRA_AMM_CONTRACT_ID: ContractId) -> (bool, ContractId) {
-    // read 3rd byte - default to `false` if not provided
-   let is_stable = match data.get(2) {
-        Option::Some(v) => v != 0, // check if value is nonzero
-        Option::None => false,
+    let (_, is_stable, amm_contract) = get_mira_like_params(data, MIRA_AMM_CONTRACT_ID);
+    (is_stable, amm_contract)
+}
+
+////////////////////////////////////////////////////
+// versioned step data
+////////////////////////////////////////////////////
+
+// splits versioned step data into the flags, the dex parameters
+// of `params_length` bytes and the optional fields
+pub fn split_step_data(data: Bytes, params_length: u64) -> (u8, Bytes, Bytes) {
+    if data.len() < STEP_HEADER_LENGTH + params_length {
+        revert(INVALID_STEP_DATA);
+    }
+    let (header, rest) = data.split_at(STEP_HEADER_LENGTH);
+    if header.get(0).unwrap() != STEP_DATA_VERSION_1 {
+        revert(INVALID_STEP_DATA);
+    }
+    let flags = header.get(1).unwrap();
+    // reject flags of later versions
+    if (flags | STEP_FLAGS_V1) != STEP_FLAGS_V1 {
+        revert(INVALID_STEP_DATA);
+    }
+    let (params, fields) = rest.split_at(params_length);
+    (flags, params, fields)
+}
+
+// reads the optional fields in flag order, they have to match the flags exactly
+// returns (dex_address, minimum_out, maximum_price)
+pub fn read_step_fields(flags: u8, fields: Bytes) -> (Option<b256>, Option<u64>, Option<u256>) {
+    let mut rest = fields;
+    let dex_address = if (flags & STEP_FLAG_DEX_ADDRESS) != 0 {
+        let (address, remaining) = split_field(rest, 32);
+        rest = remaining;
+        Option::Some(b256::from_be_bytes(address))
+    } else {
+        Option::None
     };
-        match data.len() {
-        3 => {
-            (is_stable, MIRA_AMM_CONTRACT_ID)
+    let minimum_out = if (flags & STEP_FLAG_MINIMUM_OUT) != 0 {
+        let (amount, remaining) = split_field(rest, 8);
+        rest = remaining;
+        Option::Some(u64::from_be_bytes(amount))
+    } else {
+        Option::None
+    };
+    let maximum_price = if (flags & STEP_FLAG_MAXIMUM_PRICE) != 0 {
+        let (price, remaining) = split_field(rest, 32);
+        rest = remaining;
+        Option::Some(u256::from_be_bytes(price))
+    } else {
+        Option::None
+    };
+    if rest.len() != 0 {
+        revert(INVALID_STEP_DATA);
+    }
+    (dex_address, minimum_out, maximum_price)
+}
+
+fn split_field(fields: Bytes, length: u64) -> (Bytes, Bytes) {
+    if fields.len() < length {
+        revert(INVALID_STEP_DATA);
+    }
+    fields.split_at(length)
+}
+
+// (minimum_out, maximum_price) of a step, legacy data has no limits
+pub fn get_step_limits(dex_id: u64, data: Bytes) -> (Option<u64>, Option<u256>) {
+    let params_length = match dex_id {
+        MIRA_V1_ID => {
+            if data.len() == 3 || data.len() == 35 {
+                return (Option::None, Option::None);
+            }
+            MIRA_FEE_LENGTH
         },
-        35 => {
-            let (_, pool_id) = data.split_at(3);
-            // return parsed fees as u64 and flag
-           (is_stable, ContractId::from(b256::from_be_bytes(pool_id)))
+        ONE_DELTA_ORDERS_ID => {
+            if data.len() == ORDER_PARAMS_LENGTH {
+                return (Option::None, Option::None);
+            }
+            ORDER_PARAMS_LENGTH
         },
         _ => revert(INVALID_DEX),
+    };
+    let (flags, _, fields) = split_step_data(data, params_length);
+    let (_, minimum_out, maximum_price) = read_step_fields(flags, fields);
+    (minimum_out, maximum_price)
+}
+
+// reverts if a swap violates the limits of its step
+pub fn check_step_limits(dex_id: u64, data: Bytes, amount_in: u64, amount_out: u64) {
+    let (minimum_out, maximum_price) = get_step_limits(dex_id, data);
+    if let Option::Some(minimum) = minimum_out {
+        if amount_out < minimum {
+            revert(STEP_OUTPUT_TOO_LOW);
+        }
+    }
+    if let Option::Some(maximum) = maximum_price {
+        if amount_out == 0 {
+            revert(STEP_PRICE_TOO_HIGH);
+        }
+        let amount_in_u256: u256 = amount_in.into();
+        let amount_out_u256: u256 = amount_out.into();
+        if amount_in_u256 * PRICE_SCALE / amount_out_u256 > maximum {
+            revert(STEP_PRICE_TOO_HIGH);
+        }
     }
 }
 
+// the order encoding of legacy or versioned step data
+pub fn get_order_data(data: Bytes) -> Bytes {
+    if data.len() == ORDER_PARAMS_LENGTH {
+        return data;
+    }
+    let (flags, order_bytes, fields) = split_step_data(data, ORDER_PARAMS_LENGTH);
+    // orders are not routed via other contracts
+    if (flags & (STEP_FLAG_STABLE | STEP_FLAG_DEX_ADDRESS)) != 0 {
+        revert(INVALID_STEP_DATA);
+    }
+    let _ = read_step_fields(flags, fields);
+    order_bytes
+}
+
 
 ////////////////////////////////////////////////////
 // encoding functions (mainly for tests)
@@ -478,6 +730,58 @@ pub fn encode_mira_and_fork_params(fee: u16, is_stable: bool, forked_pool: b256)
     bytes
 }
 
+// versioned Mira data, the dex address is set for forks
+pub fn encode_mira_params_v1(fee: u16, is_stable: bool, dex_address: Option<b256>) -> Bytes {
+    let mut flags = 0u8;
+    if is_stable {
+        flags = flags | STEP_FLAG_STABLE;
+    }
+    if dex_address.is_some() {
+        flags = flags | STEP_FLAG_DEX_ADDRESS;
+    }
+    let mut bytes = Bytes::with_capacity(36);
+    bytes.push(STEP_DATA_VERSION_1);
+    bytes.push(flags);
+    bytes.append(fee.to_be_bytes());
+    if let Option::Some(address) = dex_address {
+        bytes.append(address.to_be_bytes());
+    }
+    bytes
+}
+
+// versioned order data with the 160 byte order encoding
+pub fn encode_order_params_v1(order_bytes: Bytes) -> Bytes {
+    let mut bytes = Bytes::with_capacity(162);
+    bytes.push(STEP_DATA_VERSION_1);
+    bytes.push(0u8);
+    bytes.append(order_bytes);
+    bytes
+}
+
+// adds the limit fields to versioned step data
+// they are the last fields, so they can be appended to any versioned data
+pub fn with_step_limits(data: Bytes, minimum_out: Option<u64>, maximum_price: Option<u256>) -> Bytes {
+    let mut flags = data.get(1).unwrap();
+    if minimum_out.is_some() {
+        flags = flags | STEP_FLAG_MINIMUM_OUT;
+    }
+    if maximum_price.is_some() {
+        flags = flags | STEP_FLAG_MAXIMUM_PRICE;
+    }
+    let (_, rest) = data.split_at(STEP_HEADER_LENGTH);
+    let mut bytes = Bytes::with_capacity(data.len() + 40);
+    bytes.push(data.get(0).unwrap());
+    bytes.push(flags);
+    bytes.append(rest);
+    if let Option::Some(amount) = minimum_out {
+        bytes.append(amount.to_be_bytes());
+    }
+    if let Option::Some(price) = maximum_price {
+        bytes.append(price.to_be_bytes());
+    }
+    bytes
+}
+
 ////////////////////////////////////////////////////
 // decoding functions
 ////////////////////////////////////////////////////
@@ -541,7 +845,7 @@ pub fn to_order(bytes: Bytes, asset_in: AssetId, asset_out: AssetId) -> (Order,
 // quote an order exact out
 pub fn quote_order_exact_out(bytes: Bytes, amount_out: u64) -> u64 {
     // we only read the two first fields in the order
-    let (maker_amount_bytes, rest) = bytes.split_at(8);
+    let (maker_amount_bytes, rest) = get_order_data(bytes).split_at(8);
     let (taker_amount_bytes, _) = rest.split_at(8);
     let maker_amount = u64::from_be_bytes(maker_amount_bytes);
     let taker_amount = u64::from_be_bytes(taker_amount_bytes);
@@ -661,3 +965,173 @@ fn test_get_order_params() {
     assert_eq(order.maker_amount, maker_amount);
     assert_eq(signature, signature_expected);
 }
+
+#[test]
+fn test_get_mira_params_v1() {
+    let mira: ContractId = ContractId::from(0x4d3a44b2e2e53a5a452f3acac85bdd4f0e38a170a5cfbe4dfce2c79bf21a0f07);
+    let fork: b256 = 0x7c293b054938bedca41354203be4c08aec2c3466412cac803f4ad62abf22e476;
+
+    // default AMM
+    let data0 = encode_mira_params_v1(30, true, Option::None);
+    assert_eq(data0.len(), 4);
+    let (fee0, is_stable0, amm0) = get_mira_like_params(data0, mira);
+    assert_eq(fee0, 30);
+    assert_eq(is_stable0, true);
+    assert_eq(amm0, mira);
+
+    // fork
+    let data1 = encode_mira_params_v1(65533, false, Option::Some(fork));
+    assert_eq(data1.len(), 36);
+    let (fee1, is_stable1, amm1) = get_mira_like_params(data1, mira);
+    assert_eq(fee1, 65533);
+    assert_eq(is_stable1, false);
+    assert_eq(amm1, ContractId::from(fork));
+    assert_eq(get_mira_like_pool(data1, mira), ContractId::from(fork));
+
+    // legacy data still decodes the same
+    let (fee2, is_stable2, amm2) = get_mira_like_params(encode_mira_and_fork_params(30, true, fork), mira);
+    assert_eq(fee2, 30);
+    assert_eq(is_stable2, true);
+    assert_eq(amm2, ContractId::from(fork));
+}
+
+#[test(should_revert)]
+fn test_get_mira_params_v1_unknown_version() {
+    let mut data = encode_mira_params_v1(30, true, Option::None);
+    data.set(0, 2u8);
+    let _ = get_mira_like_params(data, ContractId::zero());
+}
+
+#[test(should_revert)]
+fn test_get_mira_params_v1_missing_dex_address() {
+    let mut data = encode_mira_params_v1(30, true, Option::None);
+    data.set(1, STEP_FLAG_STABLE | STEP_FLAG_DEX_ADDRESS);
+    let _ = get_mira_like_params(data, ContractId::zero());
+}
+
+#[test]
+fn test_get_order_data_v1() {
+    let maker_amount = 7843213424u64;
+    let taker_amount = 32758324u64;
+    let mut encoded_order: Bytes = maker_amount.to_be_bytes();
+    encoded_order.append(taker_amount.to_be_bytes());
+    encoded_order.append(b256::zero().to_be_bytes());
+    encoded_order.append(89u64.to_be_bytes());
+    encoded_order.append(9999u64.to_be_bytes());
+    encoded_order.append(b256::zero().to_be_bytes());
+    encoded_order.append(b256::zero().to_be_bytes());
+    encoded_order.append(b256::zero().to_be_bytes());
+
+    let data = encode_order_params_v1(encoded_order);
+    assert_eq(data.len(), 162);
+    assert_eq(get_order_data(data), encoded_order);
+    // legacy data is returned as is
+    assert_eq(get_order_data(encoded_order), encoded_order);
+    assert_eq(quote_order_exact_out(data, maker_amount), taker_amount);
+}
+
+#[test]
+fn test_get_step_limits() {
+    let fork: b256 = 0x7c293b054938bedca41354203be4c08aec2c3466412cac803f4ad62abf22e476;
+    let price = 2_000_000_000_000_000_000u256;
+
+    // legacy data has no limits
+    let (minimum0, price0) = get_step_limits(MIRA_V1_ID, encode_mira_params(30, false));
+    assert(minimum0.is_none());
+    assert(price0.is_none());
+
+    // limits follow the dex address
+    let data1 = with_step_limits(encode_mira_params_v1(30, false, Option::Some(fork)), Option::Some(100), Option::Some(price));
+    assert_eq(data1.len(), 4 + 32 + 8 + 32);
+    let (minimum1, price1) = get_step_limits(MIRA_V1_ID, data1);
+    assert_eq(minimum1.unwrap(), 100);
+    assert_eq(price1.unwrap(), price);
+    let (fee1, _, amm1) = get_mira_like_params(data1, ContractId::zero());
+    assert_eq(fee1, 30);
+    assert_eq(amm1, ContractId::from(fork));
+
+    // input per output at most 2
+    check_step_limits(MIRA_V1_ID, data1, 200, 100);
+}
+
+#[test]
+fn test_versioned_step_data_never_has_legacy_length() {
+    let fork: b256 = 0x7c293b054938bedca41354203be4c08aec2c3466412cac803f4ad62abf22e476;
+    let mut order_bytes = Bytes::new();
+    let mut j = 0;
+    while j < ORDER_PARAMS_LENGTH {
+        order_bytes.push(0u8);
+        j += 1;
+    }
+
+    // every combination of dex address, minimum output and maximum price
+    let mut i = 0;
+    while i < 8 {
+        let dex_address = if (i & 1) != 0 {
+            Option::Some(fork)
+        } else {
+            Option::None
+        };
+        let minimum_out = if (i & 2) != 0 {
+            Option::Some(100u64)
+        } else {
+            Option::None
+        };
+        let maximum_price = if (i & 4) != 0 {
+            Option::Some(PRICE_SCALE)
+        } else {
+            Option::None
+        };
+
+        let mira_data = with_step_limits(encode_mira_params_v1(30, true, dex_address), minimum_out, maximum_price);
+        assert(mira_data.len() % 2 == 0);
+        assert(mira_data.len() != 3 && mira_data.len() != 35);
+
+        let order_data = with_step_limits(encode_order_params_v1(order_bytes), minimum_out, maximum_price);
+        assert(order_data.len() > ORDER_PARAMS_LENGTH);
+        i += 1;
+    }
+}
+
+#[test(should_revert)]
+fn test_check_step_limits_minimum_out() {
+    let data = with_step_limits(encode_mira_params_v1(30, false, Option::None), Option::Some(100), Option::None);
+    check_step_limits(MIRA_V1_ID, data, 100, 99);
+}
+
+#[test(should_revert)]
+fn test_check_step_limits_maximum_price() {
+    let data = with_step_limits(encode_mira_params_v1(30, false, Option::None), Option::None, Option::Some(2_000_000_000_000_000_000u256));
+    check_step_limits(MIRA_V1_ID, data, 201, 100);
+}
+
+#[test]
+fn test_batch_totals() {
+    let asset_a = AssetId::from(0x4d3a44b2e2e53a5a452f3acac85bdd4f0e38a170a5cfbe4dfce2c79bf21a0f07);
+    let asset_b = AssetId::from(0xa1e88e8fba0e93b94bee471d7447dcc86967389e0a8bf875a0f638c631627127);
+
+    let mut totals: Vec<(AssetId, u64)> = Vec::new();
+    add_to_total(totals, asset_a, 100);
+    add_to_total(totals, asset_b, 50);
+    add_to_total(totals, asset_a, 20);
+    assert_eq(totals.len(), 2);
+    assert_eq(get_total(totals, asset_a), 120);
+
+    deduct_from_total(totals, asset_b, 80);
+    assert_eq(get_total(totals, asset_b), 0);
+
+    let mut minimums: Vec<(AssetId, u64)> = Vec::new();
+    minimums.push((asset_a, 120));
+    check_minimum_totals(totals, minimums);
+}
+
+#[test(should_revert)]
+fn test_check_minimum_totals() {
+    let asset_a = AssetId::from(0x4d3a44b2e2e53a5a452f3acac85bdd4f0e38a170a5cfbe4dfce2c79bf21a0f07);
+
+    let mut totals: Vec<(AssetId, u64)> = Vec::new();
+    add_to_total(totals, asset_a, 100);
+    let mut minimums: Vec<(AssetId, u64)> = Vec::new();
+    minimums.push((asset_a, 101));
+    check_minimum_totals(totals, minimums);
+}
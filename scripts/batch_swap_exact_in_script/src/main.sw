@@ -2,7 +2,14 @@ script;
 
 use interfaces::mira_amm::MiraAMM;
 use utils::blockchain_utils::check_deadline;
-use executor::{BatchSwapStep, execute_exact_in, get_dex_input_receiver};
+use executor::{
+    add_to_total,
+    BatchSwapStep,
+    check_minimum_totals,
+    deduct_from_total,
+    execute_exact_in,
+    get_dex_input_receiver,
+};
 use std::asset::transfer;
 use logger_abi::Logger;
 
@@ -21,15 +28,22 @@ configurable {
 }
 
 // Swap split paths exact in
+// `minimum_totals` are optional minimum total outputs per asset across all paths
 fn main(
     swap_path: Vec<(u64, u64, bool, Vec<BatchSwapStep>)>,
     deadline: u32,
+    minimum_totals: Vec<(AssetId, u64)>,
 ) {
     check_deadline(deadline);
 
     // use cached amount for split swaps
     let mut amount_cached = 0u64;
 
+    // path outputs per asset that are not swapped further
+    let mut output_totals: Vec<(AssetId, u64)> = Vec::new();
+    // outputs of paths without minimum, checked against the totals
+    let mut unlimited_assets: Vec<AssetId> = Vec::new();
+
     // start to swap through paths
     let mut i = 0;
     while i < swap_path.len() {
@@ -62,6 +76,12 @@ fn main(
         // initialize first swap step
         let mut swap_step = current_path.get(0).unwrap();
 
+        // inputs from previous paths are cached or already at the dex,
+        // so they no longer count as output
+        if current_amount_in == 0 || !transfer_in {
+            deduct_from_total(output_totals, swap_step.asset_in, amount_in_used);
+        }
+
         // transfer to first DEX if needed
         if transfer_in {
             transfer(
@@ -109,8 +129,13 @@ fn main(
                 // we record / increment the cached amount and check for slippage
                 // increment cache
                 amount_cached += amount_in_used;
-                // check for slippage on path
-                require(amount_in_used > minimum_out, "Insufficient output amount");
+                add_to_total(output_totals, swap_step.asset_out, amount_in_used);
+                // check for slippage on path, zero leaves it to the totals
+                if minimum_out == 0 {
+                    unlimited_assets.push(swap_step.asset_out);
+                } else {
+                    require(amount_in_used > minimum_out, "Insufficient output amount");
+                }
                 // break and start next path
                 break;
             }
@@ -119,6 +144,9 @@ fn main(
         i += 1;
     }
 
+    // check for slippage across all paths
+    check_minimum_totals(output_totals, minimum_totals, unlimited_assets);
+
     // call dead_call on logger to make this TX traceable
     let logger = abi(Logger, LOGGER_CONTRACT_ID.into());
     logger.dead_call();
@@ -1,10 +1,14 @@
 use crate::utils::setup;
 use fuels::prelude::VariableOutputPolicy;
 use fuels::types::Bits256;
+use fuels::types::U256;
 use test_harness::interface::amm::pool_metadata;
 use test_harness::interface::scripts::get_transaction_inputs_outputs;
 use test_harness::interface::BatchSwapStep;
-use test_harness::types::{encode_mira_params, encode_mira_params_with_dex_address};
+use test_harness::types::{
+    encode_mira_params, encode_mira_params_v1, encode_mira_params_with_dex_address,
+    with_step_limits, STEP_PRICE_SCALE,
+};
 use test_harness::utils::common::{asset_balance, pool_assets_balance};
 
 #[tokio::test]
@@ -46,7 +50,7 @@ async fn exact_in_swap_between_two_volatile_tokens() {
         }],
     )];
     swap_exact_input_script
-        .main(path, deadline)
+        .main(path, deadline, vec![(token_1_id, token_1_expected)])
         .with_contracts(&[&amm.instance, &logger])
         .with_inputs(inputs)
         .with_outputs(outputs)
@@ -78,6 +82,121 @@ async fn exact_in_swap_between_two_volatile_tokens() {
     );
 }
 
+#[tokio::test]
+async fn exact_in_swap_with_versioned_step_data() {
+    let (
+        _,
+        swap_exact_input_script,
+        amm,
+        logger,
+        (pool_id_0_1, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, _, _),
+        swap_fees,
+    ) = setup().await;
+
+    let token_0_to_swap = 1_000;
+    let token_1_expected = 996;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_to_swap)]).await;
+    let wallet_balances_before = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+
+    // versioned data with the AMM given as dex address
+    let data = encode_mira_params_v1(swap_fees.0, false, Some(Bits256(*amm.id)));
+    assert_eq!(data.0.len(), 36);
+
+    let path = vec![(
+        token_0_to_swap,
+        0u64,
+        true,
+        vec![BatchSwapStep {
+            dex_id: 0,
+            asset_in: token_0_id,
+            asset_out: token_1_id,
+            receiver: wallet.address().into(),
+            data,
+        }],
+    )];
+    swap_exact_input_script
+        .main(path, deadline, vec![(token_1_id, token_1_expected)])
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let wallet_balances_after = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+    assert_eq!(
+        wallet_balances_after.asset_a,
+        wallet_balances_before.asset_a - token_0_to_swap
+    );
+    assert_eq!(
+        wallet_balances_after.asset_b,
+        wallet_balances_before.asset_b + token_1_expected
+    );
+}
+
+#[tokio::test]
+async fn exact_in_swap_within_step_limits() {
+    let (
+        _,
+        swap_exact_input_script,
+        amm,
+        logger,
+        (pool_id_0_1, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, _, _),
+        swap_fees,
+    ) = setup().await;
+
+    let token_0_to_swap = 1_000;
+    let token_1_expected = 996;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_to_swap)]).await;
+    let wallet_balances_before = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+
+    // the exact output as minimum and a price of at most 1.01
+    let data = with_step_limits(
+        encode_mira_params_v1(swap_fees.0, false, None),
+        Some(token_1_expected),
+        Some(U256::from(STEP_PRICE_SCALE / 100 * 101)),
+    );
+
+    let path = vec![(
+        token_0_to_swap,
+        0u64,
+        true,
+        vec![BatchSwapStep {
+            dex_id: 0,
+            asset_in: token_0_id,
+            asset_out: token_1_id,
+            receiver: wallet.address().into(),
+            data,
+        }],
+    )];
+    swap_exact_input_script
+        .main(path, deadline, vec![(token_1_id, token_1_expected)])
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let wallet_balances_after = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+    assert_eq!(
+        wallet_balances_after.asset_b,
+        wallet_balances_before.asset_b + token_1_expected
+    );
+}
+
 #[tokio::test]
 async fn exact_in_swap_between_three_volatile_tokens() {
     let (
@@ -133,7 +252,7 @@ async fn exact_in_swap_between_three_volatile_tokens() {
     )];
 
     swap_exact_input_script
-        .main(path, deadline)
+        .main(path, deadline, vec![(token_2_id, token_2_expected)])
         .with_contracts(&[&amm.instance, &logger])
         .with_inputs(inputs)
         .with_outputs(outputs)
@@ -247,7 +366,7 @@ async fn exact_in_swap_split_routes() {
     ];
 
     swap_exact_input_script
-        .main(path, deadline)
+        .main(path, deadline, vec![(token_2_id, token_2_expected)])
         .with_contracts(&[&amm.instance, &logger])
         .with_inputs(inputs)
         .with_outputs(outputs)
@@ -355,7 +474,120 @@ async fn exact_in_swap_split_segment() {
     ];
 
     swap_exact_input_script
-        .main(path, deadline)
+        .main(path, deadline, vec![(token_3_id, token_3_expected)])
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap()
+        .value;
+
+    let wallet_balances_0_after = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+
+    assert_eq!(
+        wallet_balances_0_after.asset_a,
+        wallet_balances_0_before.asset_a - token_0_to_swap
+    );
+    assert_eq!(
+        wallet_balances_0_after.asset_b,
+        wallet_balances_0_before.asset_b
+    );
+
+    let wallet_balances_3_after = asset_balance(&wallet, &token_3_id).await;
+
+    assert_eq!(
+        wallet_balances_3_after,
+        wallet_balances_3_before + token_3_expected
+    );
+}
+
+#[tokio::test]
+async fn exact_in_swap_split_segment_with_minimum_total() {
+    let (
+        _,
+        swap_exact_input_script,
+        amm,
+        logger,
+        (pool_id_0_1, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, token_2_id, token_3_id),
+        swap_fees,
+    ) = setup().await;
+
+    let token_0_to_swap = 1_000;
+
+    let token_0_to_swap_split_0 = token_0_to_swap / 4;
+    let token_0_to_swap_split_1 = token_0_to_swap * 3 / 4;
+    let token_3_expected: u64 = 986;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_to_swap)]).await;
+
+    let wallet_balances_0_before = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+    let wallet_balances_3_before = asset_balance(&wallet, &token_3_id).await;
+
+    // swap
+    // 0->2
+    // 0->1->2
+    // then
+    // 2->3
+    let path = vec![
+        (
+            token_0_to_swap_split_0, // 25%
+            0u64,
+            true,
+            vec![BatchSwapStep {
+                dex_id: 0,
+                asset_in: token_0_id,
+                asset_out: token_2_id,
+                receiver: amm.id.into(),
+                data: encode_mira_params_with_dex_address(swap_fees.0, false, Bits256(*amm.id)),
+            }],
+        ),
+        (
+            token_0_to_swap_split_1, // 75%
+            0u64,
+            true,
+            vec![
+                BatchSwapStep {
+                    dex_id: 0,
+                    asset_in: token_0_id,
+                    asset_out: token_1_id,
+                    receiver: amm.id.into(),
+                    data: encode_mira_params_with_dex_address(swap_fees.0, false, Bits256(*amm.id)),
+                },
+                BatchSwapStep {
+                    dex_id: 0,
+                    asset_in: token_1_id,
+                    asset_out: token_2_id,
+                    receiver: amm.id.into(),
+                    data: encode_mira_params(swap_fees.0, false),
+                },
+            ],
+        ),
+        (
+            0, // 100%
+            0u64,
+            false,
+            vec![BatchSwapStep {
+                dex_id: 0,
+                asset_in: token_2_id,
+                asset_out: token_3_id,
+                receiver: wallet.address().into(),
+                data: encode_mira_params_with_dex_address(swap_fees.0, false, Bits256(*amm.id)),
+            }],
+        ),
+    ];
+
+    // no minimums per path, only on the final output
+    // the intermediate token 2 is used by the last path and is no output
+    let minimum_totals = vec![(token_3_id, token_3_expected), (token_2_id, 0)];
+
+    swap_exact_input_script
+        .main(path, deadline, minimum_totals)
         .with_contracts(&[&amm.instance, &logger])
         .with_inputs(inputs)
         .with_outputs(outputs)
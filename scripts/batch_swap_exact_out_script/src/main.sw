@@ -1,6 +1,12 @@
 script;
 
-use executor::{BatchSwapStep, calculate_amounts_exact_out_and_fund, forward_swap_exact_out};
+use executor::{
+    add_to_total,
+    BatchSwapStep,
+    calculate_amounts_exact_out_and_fund,
+    check_maximum_totals,
+    forward_swap_exact_out,
+};
 use utils::blockchain_utils::check_deadline;
 use std::asset::transfer;
 use logger_abi::Logger;
@@ -19,26 +25,58 @@ configurable {
     LOGGER_CONTRACT_ID: ContractId = ContractId::from(0x60caa3fe777329cd32a66a4c7ac5840e4eb10441a1f8331cd00d45fb0341a7a6),
 }
 
+// `maximum_totals` are optional maximum total inputs per asset across all paths
 fn main(
     swap_path: Vec<(u64, u64, bool, Vec<BatchSwapStep>)>,
     deadline: u32,
+    maximum_totals: Vec<(AssetId, u64)>,
 ) {
     check_deadline(deadline);
 
+    // funded inputs and outputs per asset
+    let mut input_totals: Vec<(AssetId, u64)> = Vec::new();
+    let mut output_totals: Vec<(AssetId, u64)> = Vec::new();
+    // inputs of paths without maximum, checked against the totals
+    let mut unlimited_assets: Vec<AssetId> = Vec::new();
+
     let mut i = 0;
     while i < swap_path.len() {
-        let (current_amount_out, minimum_out, _, current_path) = match swap_path.get(i) {
+        let (current_amount_out, maximum_in, _, current_path) = match swap_path.get(i) {
             Option::Some(v) => v,
             Option::None => revert(EMPTY_PATH_ENTRY),
         };
         // compute path input amounts
         let amounts_in = calculate_amounts_exact_out_and_fund(
             current_amount_out,
-            minimum_out,
+            maximum_in,
             current_path,
             MIRA_AMM_CONTRACT_ID,
             ONE_DELTA_ORDERS_CONTRACT_ID,
         );
+        // the funded input is the last amount, the first step
+        // is the last swap
+        let path_length = current_path.len();
+        if maximum_in == 0 {
+            unlimited_assets.push(current_path.get(path_length - 1).unwrap().asset_in);
+        }
+        add_to_total(
+            input_totals,
+            current_path
+                .get(path_length - 1)
+                .unwrap()
+                .asset_in,
+            amounts_in
+                .get(path_length)
+                .unwrap(),
+        );
+        add_to_total(
+            output_totals,
+            current_path
+                .get(0)
+                .unwrap()
+                .asset_out,
+            current_amount_out,
+        );
         // swap amounts forward
         forward_swap_exact_out(
             current_path,
@@ -49,6 +87,9 @@ fn main(
         i += 1;
     }
 
+    // check for slippage across all paths
+    check_maximum_totals(input_totals, output_totals, maximum_totals, unlimited_assets);
+
     // call dead_call on logger to make this TX traceable
     let logger = abi(Logger, LOGGER_CONTRACT_ID.into());
     logger.dead_call();
@@ -0,0 +1,207 @@
+use crate::utils::setup;
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::errors::{transaction::Reason, Error};
+use fuels::types::U256;
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::BatchSwapStep;
+use test_harness::types::{
+    encode_mira_params, encode_mira_params_v1, with_step_limits, STEP_PRICE_SCALE,
+};
+
+// executor error codes
+const STEP_PRICE_TOO_HIGH: u64 = 6;
+
+fn assert_revert_reason<T: std::fmt::Debug>(result: Result<T, Error>, expected: &str) {
+    match result {
+        Err(Error::Transaction(Reason::Reverted { reason, .. })) => {
+            assert!(
+                reason.contains(expected),
+                "expected {expected}, got {reason}"
+            )
+        }
+        Err(e) => panic!("expected revert {expected}, got {e:?}"),
+        Ok(value) => panic!("expected revert {expected}, got {value:?}"),
+    }
+}
+
+fn assert_revert<T: std::fmt::Debug>(result: Result<T, Error>, expected: u64) {
+    match result {
+        Err(Error::Transaction(Reason::Reverted { revert_id, .. })) => {
+            assert_eq!(revert_id, expected)
+        }
+        Err(e) => panic!("expected revert {expected}, got {e:?}"),
+        Ok(value) => panic!("expected revert {expected}, got {value:?}"),
+    }
+}
+
+#[tokio::test]
+async fn cannot_swap_above_step_maximum_price() {
+    let (
+        _,
+        swap_exact_output_script,
+        amm,
+        logger,
+        (_, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, _, _),
+        swap_fees,
+    ) = setup().await;
+
+    // 1000 out cost 1006 in, a price above 1.005
+    let token_1_output = 1_000;
+    let token_0_input_max = 1030;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_input_max)]).await;
+
+    let path: Vec<(u64, u64, bool, Vec<BatchSwapStep>)> = vec![(
+        token_1_output,
+        token_0_input_max,
+        true,
+        vec![BatchSwapStep {
+            dex_id: 0,
+            asset_in: token_0_id,
+            asset_out: token_1_id,
+            receiver: wallet.address().into(),
+            data: with_step_limits(
+                encode_mira_params_v1(swap_fees.0, false, None),
+                None,
+                Some(U256::from(STEP_PRICE_SCALE / 1000 * 1005)),
+            ),
+        }],
+    )];
+    let result = swap_exact_output_script
+        .main(path, deadline, vec![])
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await;
+
+    assert_revert(result, STEP_PRICE_TOO_HIGH);
+}
+
+#[tokio::test]
+async fn cannot_swap_split_routes_above_maximum_total() {
+    let (
+        _,
+        swap_exact_output_script,
+        amm,
+        logger,
+        (_, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, token_2_id, _),
+        swap_fees,
+    ) = setup().await;
+
+    let token_0_input_expected = 1008;
+    let token_0_maximum_in = 1020;
+    let token_0_maximum_in_split_0 = token_0_maximum_in / 4;
+    let token_0_maximum_in_split_1 = token_0_maximum_in * 3 / 4;
+    let token_2_output = 1000;
+    let token_2_output_split_0 = token_2_output / 4;
+    let token_2_output_split_1 = token_2_output * 3 / 4;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_maximum_in)]).await;
+
+    let path = vec![
+        (
+            token_2_output_split_0,
+            token_0_maximum_in_split_0,
+            true,
+            vec![
+                BatchSwapStep {
+                    dex_id: 0,
+                    asset_in: token_1_id,
+                    asset_out: token_2_id,
+                    receiver: wallet.address().into(),
+                    data: encode_mira_params(swap_fees.0, false),
+                },
+                BatchSwapStep {
+                    dex_id: 0,
+                    asset_in: token_0_id,
+                    asset_out: token_1_id,
+                    receiver: amm.id.into(),
+                    data: encode_mira_params(swap_fees.0, false),
+                },
+            ],
+        ),
+        (
+            token_2_output_split_1,
+            token_0_maximum_in_split_1,
+            true,
+            vec![BatchSwapStep {
+                dex_id: 0,
+                asset_in: token_0_id,
+                asset_out: token_2_id,
+                receiver: wallet.address().into(),
+                data: encode_mira_params(swap_fees.0, false),
+            }],
+        ),
+    ];
+
+    let result = swap_exact_output_script
+        .main(
+            path,
+            deadline,
+            vec![(token_0_id, token_0_input_expected - 1)],
+        )
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Exceeding total input");
+}
+
+#[tokio::test]
+async fn cannot_swap_without_maximum_input() {
+    let (
+        _,
+        swap_exact_output_script,
+        amm,
+        logger,
+        (_, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, token_2_id, _),
+        swap_fees,
+    ) = setup().await;
+
+    let token_0_input_max = 1030;
+    let token_1_output = 1000;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_input_max)]).await;
+
+    let path: Vec<(u64, u64, bool, Vec<BatchSwapStep>)> = vec![(
+        token_1_output,
+        0,
+        true,
+        vec![BatchSwapStep {
+            dex_id: 0,
+            asset_in: token_0_id,
+            asset_out: token_1_id,
+            receiver: wallet.address().into(),
+            data: encode_mira_params(swap_fees.0, false),
+        }],
+    )];
+
+    // no maximum on the path and only a total for another asset
+    let result = swap_exact_output_script
+        .main(path, deadline, vec![(token_2_id, 0)])
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await;
+
+    assert_revert_reason(result, "Missing input limit");
+}
@@ -1,10 +1,14 @@
 use crate::utils::setup;
 use fuels::prelude::VariableOutputPolicy;
 use fuels::types::Bits256;
+use fuels::types::U256;
 use test_harness::interface::amm::pool_metadata;
 use test_harness::interface::scripts::get_transaction_inputs_outputs;
 use test_harness::interface::BatchSwapStep;
-use test_harness::types::{encode_mira_params, encode_mira_params_with_dex_address};
+use test_harness::types::{
+    encode_mira_params, encode_mira_params_v1, encode_mira_params_with_dex_address,
+    with_step_limits, STEP_PRICE_SCALE,
+};
 use test_harness::utils::common::{asset_balance, pool_assets_balance};
 
 #[tokio::test]
@@ -48,7 +52,7 @@ async fn exact_out_swap_between_two_volatile_tokens() {
         }],
     )];
     swap_exact_output_script
-        .main(path, deadline)
+        .main(path, deadline, vec![])
         .with_contracts(&[&amm.instance, &logger])
         .with_inputs(inputs)
         .with_outputs(outputs)
@@ -81,6 +85,66 @@ async fn exact_out_swap_between_two_volatile_tokens() {
     );
 }
 
+#[tokio::test]
+async fn exact_out_swap_within_step_limits() {
+    let (
+        _,
+        swap_exact_output_script,
+        amm,
+        logger,
+        (pool_id_0_1, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, _, _),
+        swap_fees,
+    ) = setup().await;
+
+    let token_1_output = 1_000;
+    let token_0_input_max = 1030;
+    let token_0_input_expected = 1006;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_input_max)]).await;
+    let wallet_balances_before = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+
+    // a price of at most 1.01 input per output
+    let path: Vec<(u64, u64, bool, Vec<BatchSwapStep>)> = vec![(
+        token_1_output,
+        token_0_input_max,
+        true,
+        vec![BatchSwapStep {
+            dex_id: 0,
+            asset_in: token_0_id,
+            asset_out: token_1_id,
+            receiver: wallet.address().into(),
+            data: with_step_limits(
+                encode_mira_params_v1(swap_fees.0, false, None),
+                Some(token_1_output),
+                Some(U256::from(STEP_PRICE_SCALE / 100 * 101)),
+            ),
+        }],
+    )];
+    swap_exact_output_script
+        .main(path, deadline, vec![])
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let wallet_balances_after = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+    assert_eq!(
+        wallet_balances_after.asset_a,
+        wallet_balances_before.asset_a - token_0_input_expected
+    );
+    assert_eq!(
+        wallet_balances_after.asset_b,
+        wallet_balances_before.asset_b + token_1_output
+    );
+}
+
 #[tokio::test]
 async fn exact_out_swap_between_three_volatile_tokens() {
     let (
@@ -136,7 +200,7 @@ async fn exact_out_swap_between_three_volatile_tokens() {
     )];
 
     swap_exact_output_script
-        .main(path, deadline)
+        .main(path, deadline, vec![])
         .with_contracts(&[&amm.instance, &logger])
         .with_inputs(inputs)
         .with_outputs(outputs)
@@ -253,7 +317,102 @@ async fn exact_out_swap_split_routes() {
     let wallet_balances_2_before = asset_balance(&wallet, &token_2_id).await;
 
     swap_exact_output_script
-        .main(path, deadline)
+        .main(path, deadline, vec![])
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+
+    let wallet_balances_0_after = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+
+    assert_eq!(
+        wallet_balances_0_after.asset_a,
+        wallet_balances_0_before.asset_a - token_0_input_expected
+    );
+    assert_eq!(
+        wallet_balances_0_after.asset_b,
+        wallet_balances_0_before.asset_b
+    );
+
+    let wallet_balances_2_after = asset_balance(&wallet, &token_2_id).await;
+
+    assert_eq!(
+        wallet_balances_2_after,
+        wallet_balances_2_before + token_2_output
+    );
+}
+
+#[tokio::test]
+async fn exact_out_swap_split_routes_with_maximum_total() {
+    let (
+        _,
+        swap_exact_output_script,
+        amm,
+        logger,
+        (pool_id_0_1, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, token_2_id, _),
+        swap_fees,
+    ) = setup().await;
+
+    let token_0_input_expected = 1008;
+    let token_0_maximum_in = 1020;
+    let token_2_output = 1000;
+    let token_2_output_split_0 = token_2_output / 4;
+    let token_2_output_split_1 = token_2_output * 3 / 4;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_maximum_in)]).await;
+
+    let wallet_balances_0_before = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+
+    let path = vec![
+        (
+            token_2_output_split_0,
+            0,
+            true,
+            vec![
+                BatchSwapStep {
+                    dex_id: 0,
+                    asset_in: token_1_id,
+                    asset_out: token_2_id,
+                    receiver: wallet.address().into(),
+                    data: encode_mira_params(swap_fees.0, false),
+                },
+                BatchSwapStep {
+                    dex_id: 0,
+                    asset_in: token_0_id,
+                    asset_out: token_1_id,
+                    receiver: amm.id.into(),
+                    data: encode_mira_params(swap_fees.0, false),
+                },
+            ],
+        ),
+        (
+            token_2_output_split_1,
+            0,
+            true,
+            vec![BatchSwapStep {
+                dex_id: 0,
+                asset_in: token_0_id,
+                asset_out: token_2_id,
+                receiver: wallet.address().into(),
+                data: encode_mira_params(swap_fees.0, false),
+            }],
+        ),
+    ];
+
+    let wallet_balances_2_before = asset_balance(&wallet, &token_2_id).await;
+
+    // no maximums per path, only on the total input
+    let maximum_totals = vec![(token_0_id, token_0_input_expected)];
+
+    swap_exact_output_script
+        .main(path, deadline, maximum_totals)
         .with_contracts(&[&amm.instance, &logger])
         .with_inputs(inputs)
         .with_outputs(outputs)
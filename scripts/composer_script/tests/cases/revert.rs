@@ -0,0 +1,187 @@
+use crate::cases::success::{get_swaylend_base_balances, setup_basic_lender};
+use crate::utils::setup;
+use fuels::prelude::VariableOutputPolicy;
+use fuels::types::errors::{transaction::Reason, Error};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::{
+    Action, AddLiquidityAction, HealthCheck, LenderAction, PriceDataUpdate,
+};
+
+// composer error codes
+const WITHDRAW_EXCEEDS_SUPPLY: u64 = 106;
+const INSUFFICIENT_HEALTH: u64 = 107;
+const INVALID_LIQUIDITY_AMOUNT: u64 = 114;
+
+fn assert_revert<T: std::fmt::Debug>(result: Result<T, Error>, expected: u64) {
+    match result {
+        Err(Error::Transaction(Reason::Reverted { revert_id, .. })) => {
+            assert_eq!(revert_id, expected)
+        }
+        Err(e) => panic!("expected revert {expected}, got {e:?}"),
+        Ok(value) => panic!("expected revert {expected}, got {value:?}"),
+    }
+}
+
+#[tokio::test]
+async fn cannot_withdraw_base_beyond_supply() {
+    let (
+        _,
+        composer_script,
+        amm,
+        swaylend,
+        logger,
+        _,
+        wallet,
+        deadline,
+        (base_token_id, _, _, _),
+        _,
+    ) = setup().await;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(base_token_id, 1)]).await;
+
+    let (base_deposits, _) = get_swaylend_base_balances(&swaylend, &wallet).await;
+
+    // withdrawing more than supplied would borrow the difference
+    let withdraw_base = LenderAction {
+        lender_id: 0,
+        action_id: 5,
+        asset: base_token_id,
+        amount_in: base_deposits + 1,
+        amount_type_id: 1,
+        data: Some(PriceDataUpdate {
+            update_fee: 0u64,
+            publish_times: vec![],
+            price_feed_ids: vec![],
+            update_data: vec![],
+        }),
+        market: swaylend.contract_id().into(),
+        additional_params: None,
+    };
+
+    let result = composer_script
+        .main(vec![Action::Lending(withdraw_base)], deadline)
+        .with_contracts(&[&amm.instance, &logger, &swaylend])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await;
+
+    assert_revert(result, WITHDRAW_EXCEEDS_SUPPLY);
+}
+
+#[tokio::test]
+async fn cannot_open_below_min_health() {
+    let (
+        _,
+        composer_script,
+        amm,
+        _,
+        logger,
+        _,
+        wallet,
+        deadline,
+        (base_token_id, token_1_id, _, _),
+        _,
+    ) = setup().await;
+
+    let (lender_id, lender) = setup_basic_lender(&wallet, base_token_id, token_1_id).await;
+
+    // borrow capacity is 1_600, the health is 1.33
+    let token_1_to_deposit = 2_000;
+    let base_token_to_borrow = 1_200;
+
+    let deposit = LenderAction {
+        lender_id: 1,
+        action_id: 0,
+        asset: token_1_id,
+        amount_in: token_1_to_deposit,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let borrow = LenderAction {
+        lender_id: 1,
+        action_id: 1,
+        asset: base_token_id,
+        amount_in: base_token_to_borrow,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let health_check = HealthCheck {
+        lender_id: 1,
+        market: lender_id,
+        min_health: 1_500_000_000_000_000_000,
+    };
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_1_id, token_1_to_deposit)]).await;
+
+    // the check is evaluated after all actions
+    let result = composer_script
+        .main(
+            vec![
+                Action::AssertHealth(health_check),
+                Action::Lending(deposit),
+                Action::Lending(borrow),
+            ],
+            deadline,
+        )
+        .with_contracts(&[&amm.instance, &logger, &lender])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await;
+
+    assert_revert(result, INSUFFICIENT_HEALTH);
+}
+
+#[tokio::test]
+async fn cannot_add_liquidity_with_both_amounts_cached() {
+    let (
+        _,
+        composer_script,
+        amm,
+        _,
+        logger,
+        (pool_id_0_1, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, _, _),
+        _,
+    ) = setup().await;
+
+    let amount = 10_000;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, amount), (token_1_id, amount)])
+            .await;
+
+    // the cache only holds the amount of one asset
+    let actions = vec![Action::AddLiquidity(AddLiquidityAction {
+        pool_id: pool_id_0_1,
+        amount_0_desired: 0,
+        amount_1_desired: 0,
+        amount_0_min: 0,
+        amount_1_min: 0,
+        receiver: wallet.address().into(),
+    })];
+
+    let result = composer_script
+        .main(actions, deadline)
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await;
+
+    assert_revert(result, INVALID_LIQUIDITY_AMOUNT);
+}
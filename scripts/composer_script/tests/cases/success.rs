@@ -2,19 +2,22 @@ use std::str::FromStr;
 
 use crate::utils::setup;
 use fuels::accounts::wallet::WalletUnlocked;
+use fuels::accounts::Account;
 use fuels::accounts::ViewOnlyAccount;
-use fuels::prelude::VariableOutputPolicy;
+use fuels::prelude::{TxPolicies, VariableOutputPolicy};
 use fuels::programs::calls::Execution;
-use fuels::types::{AssetId, Identity};
+use fuels::types::{Address, AssetId, ContractId, Identity};
 use test_harness::data_structures::MiraAMMContract;
 use test_harness::interface::amm::pool_metadata;
+use test_harness::interface::mock::deploy_mock_basic_lender_contract;
 use test_harness::interface::scripts::get_transaction_inputs_outputs;
 use test_harness::interface::{
-    Action, BatchSwapStep, ComposerScript, LenderAction, Logger, MockSwaylend, PriceDataUpdate,
-    SwapPath, SwapPathList,
+    Action, AddLiquidityAction, BatchSwapStep, ComposerScript, HealthCheck, LenderAction, Logger,
+    MockBasicLender, MockSwaylend, PriceDataUpdate, RemoveLiquidityAction, SwapPath, SwapPathList,
+    TransferAction,
 };
 use test_harness::types::encode_mira_params;
-use test_harness::utils::common::pool_assets_balance;
+use test_harness::utils::common::{get_lp_asset_id, pool_assets_balance};
 
 /** Simple swap test to ensure that it still works */
 #[tokio::test]
@@ -93,6 +96,138 @@ async fn composer_exact_in_swap_between_two_volatile_tokens() {
     );
 }
 
+/** Transfer test to ensure that the script forwards funds */
+#[tokio::test]
+async fn composer_transfer() {
+    let (_, composer_script, amm, _, logger, _, wallet, deadline, (token_0_id, _, _, _), _) =
+        setup().await;
+
+    let token_0_to_transfer = 500;
+    let receiver = Address::from([7u8; 32]);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_0_id, token_0_to_transfer)]).await;
+
+    let actions = vec![Action::Transfer(TransferAction {
+        asset: token_0_id,
+        amount: token_0_to_transfer,
+        receiver: Identity::Address(receiver),
+    })];
+
+    composer_script
+        .main(actions, deadline)
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let receiver_balance = wallet
+        .provider()
+        .unwrap()
+        .get_asset_balance(&receiver.into(), token_0_id)
+        .await
+        .unwrap();
+
+    assert_eq!(receiver_balance, token_0_to_transfer);
+}
+
+/** Liquidity test to ensure that the script adds and removes liquidity */
+#[tokio::test]
+async fn composer_add_and_remove_liquidity() {
+    let (
+        _,
+        composer_script,
+        amm,
+        _,
+        logger,
+        (pool_id_0_1, _, _, _, _),
+        wallet,
+        deadline,
+        (token_0_id, token_1_id, _, _),
+        _,
+    ) = setup().await;
+
+    // the pool is balanced, so the liquidity matches the amounts
+    let amount = 10_000;
+
+    let wallet_balances_before = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &wallet,
+        &vec![(token_0_id, amount), (token_1_id, amount)],
+    )
+    .await;
+
+    let actions = vec![Action::AddLiquidity(AddLiquidityAction {
+        pool_id: pool_id_0_1,
+        amount_0_desired: amount,
+        amount_1_desired: amount,
+        amount_0_min: 0,
+        amount_1_min: 0,
+        receiver: wallet.address().into(),
+    })];
+
+    composer_script
+        .main(actions, deadline)
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let wallet_balances_added = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+    assert_eq!(
+        wallet_balances_added.asset_a,
+        wallet_balances_before.asset_a - amount
+    );
+    assert_eq!(
+        wallet_balances_added.asset_b,
+        wallet_balances_before.asset_b - amount
+    );
+    assert_eq!(
+        wallet_balances_added.liquidity_pool_asset,
+        wallet_balances_before.liquidity_pool_asset + amount
+    );
+
+    let (inputs, outputs) = get_transaction_inputs_outputs(
+        &wallet,
+        &vec![(get_lp_asset_id(amm.id, &pool_id_0_1), amount)],
+    )
+    .await;
+
+    let actions = vec![Action::RemoveLiquidity(RemoveLiquidityAction {
+        pool_id: pool_id_0_1,
+        liquidity: amount,
+        amount_0_min: amount,
+        amount_1_min: amount,
+        receiver: wallet.address().into(),
+        cache_amount_1: false,
+    })];
+
+    composer_script
+        .main(actions, deadline)
+        .with_contracts(&[&amm.instance, &logger])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+
+    let wallet_balances_after = pool_assets_balance(&wallet, &pool_id_0_1, amm.id).await;
+    assert_eq!(wallet_balances_after.asset_a, wallet_balances_before.asset_a);
+    assert_eq!(wallet_balances_after.asset_b, wallet_balances_before.asset_b);
+    assert_eq!(
+        wallet_balances_after.liquidity_pool_asset,
+        wallet_balances_before.liquidity_pool_asset
+    );
+}
+
 /** Open test */
 #[tokio::test]
 async fn composer_open() {
@@ -144,6 +279,7 @@ async fn composer_open_loop() {
             update_data: vec![],
         }),
         market: swaylend.contract_id().into(),
+        additional_params: None,
     };
     // execute swap
     let paths0 = vec![SwapPath {
@@ -182,6 +318,7 @@ async fn composer_open_loop() {
         amount_type_id: 0,
         data: None,
         market: swaylend.contract_id().into(),
+        additional_params: None,
     };
 
     let actions = vec![
@@ -255,6 +392,7 @@ async fn composer_close() {
             update_data: vec![],
         }),
         market: swaylend.contract_id().into(),
+        additional_params: None,
     };
 
     let repay = LenderAction {
@@ -265,6 +403,7 @@ async fn composer_close() {
         amount_type_id: 0,
         data: None,
         market: swaylend.contract_id().into(),
+        additional_params: None,
     };
 
     // execute swap
@@ -322,7 +461,438 @@ async fn composer_close() {
     assert_eq!(expected_diff, a - a0);
 }
 
-async fn get_swaylend_base_balances(
+/** Base deposit test */
+#[tokio::test]
+async fn composer_deposit_base() {
+    let (
+        _,
+        composer_script,
+        amm,
+        swaylend,
+        logger,
+        _,
+        wallet,
+        deadline,
+        (base_token_id, _, _, _),
+        _,
+    ) = setup().await;
+
+    let base_token_to_deposit = 1_000;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(base_token_id, base_token_to_deposit)])
+            .await;
+
+    let (base_deposits_before, debt_before) = get_swaylend_base_balances(&swaylend, &wallet).await;
+
+    let deposit_base = LenderAction {
+        lender_id: 0,
+        action_id: 4,
+        asset: base_token_id,
+        amount_in: base_token_to_deposit,
+        amount_type_id: 1,
+        data: None,
+        market: swaylend.contract_id().into(),
+        additional_params: None,
+    };
+
+    composer_script
+        .main(vec![Action::Lending(deposit_base)], deadline)
+        .with_contracts(&[&amm.instance, &logger, &swaylend])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let (base_deposits_after, debt_after) = get_swaylend_base_balances(&swaylend, &wallet).await;
+
+    assert_eq!(
+        base_deposits_after - base_deposits_before,
+        base_token_to_deposit
+    );
+    assert_eq!(debt_after, debt_before);
+}
+
+/** Base withdrawal test */
+#[tokio::test]
+async fn composer_withdraw_base() {
+    let (
+        _,
+        composer_script,
+        amm,
+        swaylend,
+        logger,
+        _,
+        wallet,
+        deadline,
+        (base_token_id, _, _, _),
+        _,
+    ) = setup().await;
+
+    let base_token_to_withdraw = 1_000;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(base_token_id, 1)]).await;
+
+    let (base_deposits_before, debt_before) = get_swaylend_base_balances(&swaylend, &wallet).await;
+    let base_balance_before = wallet.get_asset_balance(&base_token_id).await.unwrap();
+
+    let withdraw_base = LenderAction {
+        lender_id: 0,
+        action_id: 5,
+        asset: base_token_id,
+        amount_in: base_token_to_withdraw,
+        amount_type_id: 1,
+        data: Some(PriceDataUpdate {
+            update_fee: 0u64,
+            publish_times: vec![],
+            price_feed_ids: vec![],
+            update_data: vec![],
+        }),
+        market: swaylend.contract_id().into(),
+        additional_params: None,
+    };
+
+    composer_script
+        .main(vec![Action::Lending(withdraw_base)], deadline)
+        .with_contracts(&[&amm.instance, &logger, &swaylend])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let (base_deposits_after, debt_after) = get_swaylend_base_balances(&swaylend, &wallet).await;
+    let base_balance_after = wallet.get_asset_balance(&base_token_id).await.unwrap();
+
+    assert_eq!(
+        base_deposits_before - base_deposits_after,
+        base_token_to_withdraw
+    );
+    assert_eq!(debt_after, debt_before);
+    assert_eq!(
+        base_balance_after - base_balance_before,
+        base_token_to_withdraw
+    );
+}
+
+/** Full base withdrawal test using the max amount */
+#[tokio::test]
+async fn composer_withdraw_base_max() {
+    let (
+        _,
+        composer_script,
+        amm,
+        swaylend,
+        logger,
+        _,
+        wallet,
+        deadline,
+        (base_token_id, _, _, _),
+        _,
+    ) = setup().await;
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(base_token_id, 1)]).await;
+
+    let (base_deposits_before, _) = get_swaylend_base_balances(&swaylend, &wallet).await;
+    let base_balance_before = wallet.get_asset_balance(&base_token_id).await.unwrap();
+
+    let withdraw_base = LenderAction {
+        lender_id: 0,
+        action_id: 5,
+        asset: base_token_id,
+        amount_in: u64::MAX,
+        amount_type_id: 1,
+        data: Some(PriceDataUpdate {
+            update_fee: 0u64,
+            publish_times: vec![],
+            price_feed_ids: vec![],
+            update_data: vec![],
+        }),
+        market: swaylend.contract_id().into(),
+        additional_params: None,
+    };
+
+    composer_script
+        .main(vec![Action::Lending(withdraw_base)], deadline)
+        .with_contracts(&[&amm.instance, &logger, &swaylend])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let (base_deposits_after, debt_after) = get_swaylend_base_balances(&swaylend, &wallet).await;
+    let base_balance_after = wallet.get_asset_balance(&base_token_id).await.unwrap();
+
+    // the full supply is withdrawn without borrowing
+    assert_eq!(base_deposits_after, 0);
+    assert_eq!(debt_after, 0);
+    assert_eq!(
+        base_balance_after - base_balance_before,
+        base_deposits_before
+    );
+}
+
+/** Open and close test on a second lender via the adapter interface */
+#[tokio::test]
+async fn composer_basic_lender_open_and_close() {
+    let (
+        _,
+        composer_script,
+        amm,
+        _,
+        logger,
+        _,
+        wallet,
+        deadline,
+        (base_token_id, token_1_id, _, _),
+        _,
+    ) = setup().await;
+
+    let (lender_id, lender) = setup_basic_lender(&wallet, base_token_id, token_1_id).await;
+
+    let token_1_to_deposit = 2_000;
+    let base_token_to_borrow = 1_000;
+
+    let deposit = LenderAction {
+        lender_id: 1,
+        action_id: 0,
+        asset: token_1_id,
+        amount_in: token_1_to_deposit,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let borrow = LenderAction {
+        lender_id: 1,
+        action_id: 1,
+        asset: base_token_id,
+        amount_in: base_token_to_borrow,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_1_id, token_1_to_deposit)]).await;
+
+    composer_script
+        .main(
+            vec![Action::Lending(deposit), Action::Lending(borrow)],
+            deadline,
+        )
+        .with_contracts(&[&amm.instance, &logger, &lender])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let caller = Identity::Address(wallet.address().into());
+    let (supply, debt) = lender
+        .methods()
+        .get_supply_borrow(caller)
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+    let collateral = lender
+        .methods()
+        .get_collateral(caller, token_1_id)
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(supply, 0);
+    assert_eq!(debt, base_token_to_borrow);
+    assert_eq!(collateral, token_1_to_deposit);
+
+    // 0 repays the full debt
+    let repay = LenderAction {
+        lender_id: 1,
+        action_id: 3,
+        asset: base_token_id,
+        amount_in: 0,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let withdraw = LenderAction {
+        lender_id: 1,
+        action_id: 2,
+        asset: token_1_id,
+        amount_in: token_1_to_deposit,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(base_token_id, base_token_to_borrow)]).await;
+
+    composer_script
+        .main(
+            vec![Action::Lending(repay), Action::Lending(withdraw)],
+            deadline,
+        )
+        .with_contracts(&[&amm.instance, &logger, &lender])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let (_, debt) = lender
+        .methods()
+        .get_supply_borrow(caller)
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+    let collateral = lender
+        .methods()
+        .get_collateral(caller, token_1_id)
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(debt, 0);
+    assert_eq!(collateral, 0);
+}
+
+/** Health check after opening a position on the basic lender */
+#[tokio::test]
+async fn composer_basic_lender_open_with_health_check() {
+    let (
+        _,
+        composer_script,
+        amm,
+        _,
+        logger,
+        _,
+        wallet,
+        deadline,
+        (base_token_id, token_1_id, _, _),
+        _,
+    ) = setup().await;
+
+    let (lender_id, lender) = setup_basic_lender(&wallet, base_token_id, token_1_id).await;
+
+    // borrow capacity is 1_600, the health is 1.6
+    let token_1_to_deposit = 2_000;
+    let base_token_to_borrow = 1_000;
+
+    let deposit = LenderAction {
+        lender_id: 1,
+        action_id: 0,
+        asset: token_1_id,
+        amount_in: token_1_to_deposit,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let borrow = LenderAction {
+        lender_id: 1,
+        action_id: 1,
+        asset: base_token_id,
+        amount_in: base_token_to_borrow,
+        amount_type_id: 1,
+        data: None,
+        market: lender_id,
+        additional_params: None,
+    };
+
+    let health_check = HealthCheck {
+        lender_id: 1,
+        market: lender_id,
+        min_health: 1_500_000_000_000_000_000,
+    };
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&wallet, &vec![(token_1_id, token_1_to_deposit)]).await;
+
+    composer_script
+        .main(
+            vec![
+                Action::Lending(deposit),
+                Action::Lending(borrow),
+                Action::AssertHealth(health_check),
+            ],
+            deadline,
+        )
+        .with_contracts(&[&amm.instance, &logger, &lender])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+        .call()
+        .await
+        .unwrap();
+
+    let (_, debt) = lender
+        .methods()
+        .get_supply_borrow(Identity::Address(wallet.address().into()))
+        .simulate(Execution::StateReadOnly)
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(debt, base_token_to_borrow);
+}
+
+/**
+ * Deploys the basic lender with base liquidity and a collateral factor of 0.8
+ */
+pub async fn setup_basic_lender(
+    wallet: &WalletUnlocked,
+    base_token_id: AssetId,
+    collateral_token_id: AssetId,
+) -> (ContractId, MockBasicLender<WalletUnlocked>) {
+    let (lender_id, lender) = deploy_mock_basic_lender_contract(wallet).await;
+    lender
+        .methods()
+        .mock_set_base_asset(base_token_id)
+        .call()
+        .await
+        .unwrap();
+    lender
+        .methods()
+        .mock_set_collateral_factor(collateral_token_id, 800_000_000_000_000_000)
+        .call()
+        .await
+        .unwrap();
+    // provide base liquidity to borrow from
+    wallet
+        .force_transfer_to_contract(
+            &lender_id.into(),
+            1_000_000,
+            base_token_id,
+            TxPolicies::default(),
+        )
+        .await
+        .unwrap();
+
+    (lender_id, lender)
+}
+
+pub async fn get_swaylend_base_balances(
     swaylend: &MockSwaylend<WalletUnlocked>,
     wallet: &WalletUnlocked,
 ) -> (u64, u64) {
@@ -403,6 +973,7 @@ async fn setup_and_composer_open() -> (
         amount_type_id: 1,
         data: None,
         market: swaylend.contract_id().into(),
+        additional_params: None,
     };
 
     let borrow = LenderAction {
@@ -418,6 +989,7 @@ async fn setup_and_composer_open() -> (
             update_data: vec![],
         }),
         market: swaylend.contract_id().into(),
+        additional_params: None,
     };
 
     let actions = vec![Action::Lending(deposit), Action::Lending(borrow)];
@@ -1,56 +1,20 @@
 use fuels::{
     prelude::*,
-    programs::responses::CallResponse,
+    programs::{calls::Execution, responses::CallResponse},
     types::{input::Input, output::Output, Bits256},
 };
 
 use crate::paths::{
-    LOGGER_CONTRACT_BINARY_PATH, MOCK_SWAYLEND_CONTRACT_BINARY_PATH,
-    MOCK_TOKEN_CONTRACT_BINARY_PATH,
+    ACCOUNT_FACTORY_CONTRACT_BINARY_PATH, ACCOUNT_LOGIC_CONTRACT_BINARY_PATH,
+    ACCOUNT_PROXY_CONTRACT_BINARY_PATH, BEACON_CONTRACT_BINARY_PATH, LOGGER_CONTRACT_BINARY_PATH,
+    MOCK_BASIC_LENDER_CONTRACT_BINARY_PATH, MOCK_SWAYLEND_CONTRACT_BINARY_PATH,
+    MOCK_TOKEN_CONTRACT_BINARY_PATH, ONE_DELTA_ORDERS_CONTRACT_BINARY_PATH,
+    ORDER_ROUTER_CONTRACT_BINARY_PATH,
 };
 
 use crate::types::PoolId;
 
-abigen!(
-    Contract(
-        name = "MockToken",
-        abi = "./contracts/mocks/mock_token/out/debug/mock_token-abi.json"
-    ),
-    Contract(
-        name = "MockSwaylend",
-        abi = "./contracts/mocks/mock_swaylend/out/debug/mock_swaylend-abi.json"
-    ),
-    Contract(
-        name = "Logger",
-        abi = "./contracts/logger/out/debug/logger-abi.json"
-    ),
-    Contract(
-        name = "SwaylendMarket",
-        abi = "./fixtures/swaylend/market-abi.json"
-    ),
-    Contract(
-        name = "MiraAMM",
-        abi = "./fixtures/mira-amm/mira_amm_contract-abi.json"
-    ),
-    Script(
-        name = "AddLiquidityScript",
-        abi = "./scripts/add_liquidity_script/out/debug/add_liquidity_script-abi.json"
-    ),
-    // 1delta script
-    Script(
-        name = "BatchSwapExactInScript",
-        abi = "./scripts/batch_swap_exact_in_script/out/debug/batch_swap_exact_in_script-abi.json"
-    ),
-    Script(
-        name = "BatchSwapExactOutScript",
-        abi =
-            "./scripts/batch_swap_exact_out_script/out/debug/batch_swap_exact_out_script-abi.json"
-    ),
-    Script(
-        name = "ComposerScript",
-        abi = "./scripts/composer_script/out/debug/composer_script-abi.json"
-    ),
-);
+pub use bindings::*;
 
 pub mod amm {
     use super::*;
@@ -109,6 +73,177 @@ pub mod amm {
     }
 }
 
+pub mod account {
+    use super::*;
+    use fuels::core::codec::ABIEncoder;
+    use fuels::core::traits::{Signer, Tokenizable};
+    use fuels::crypto::Message;
+    use fuels::types::{Identity, B512};
+    use sha2::{Digest, Sha256};
+
+    pub async fn deploy_beacon_contract(
+        wallet: &WalletUnlocked,
+    ) -> (ContractId, Beacon<WalletUnlocked>) {
+        let contract_id =
+            Contract::load_from(BEACON_CONTRACT_BINARY_PATH, LoadConfiguration::default())
+                .unwrap()
+                .deploy(wallet, TxPolicies::default())
+                .await
+                .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = Beacon::new(contract_id, wallet.clone());
+
+        (id, instance)
+    }
+
+    /// Returns the proxy and its bytecode root that the factory accepts
+    pub async fn deploy_account_proxy_contract(
+        wallet: &WalletUnlocked,
+        beacon: ContractId,
+        salt: [u8; 32],
+    ) -> (ContractId, AccountProxy<WalletUnlocked>, Bits256) {
+        let configurables = AccountProxyConfigurables::default()
+            .with_BEACON(Bits256(*beacon))
+            .unwrap();
+        let contract = Contract::load_from(
+            ACCOUNT_PROXY_CONTRACT_BINARY_PATH,
+            LoadConfiguration::default()
+                .with_configurables(configurables)
+                .with_salt(salt),
+        )
+        .unwrap();
+        let root = Bits256(*contract.code_root());
+        let contract_id = contract
+            .deploy(wallet, TxPolicies::default())
+            .await
+            .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = AccountProxy::new(contract_id, wallet.clone());
+
+        (id, instance, root)
+    }
+
+    pub async fn deploy_account_factory_contract(
+        wallet: &WalletUnlocked,
+        account_bytecode_root: Bits256,
+    ) -> (ContractId, AccountFactory<WalletUnlocked>) {
+        let configurables = AccountFactoryConfigurables::default()
+            .with_ACCOUNT_BYTECODE_ROOT(account_bytecode_root)
+            .unwrap();
+        let contract_id = Contract::load_from(
+            ACCOUNT_FACTORY_CONTRACT_BINARY_PATH,
+            LoadConfiguration::default().with_configurables(configurables),
+        )
+        .unwrap()
+        .deploy(wallet, TxPolicies::default())
+        .await
+        .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = AccountFactory::new(contract_id, wallet.clone());
+
+        (id, instance)
+    }
+
+    pub async fn deploy_account_logic_contract(
+        wallet: &WalletUnlocked,
+        factory: ContractId,
+        mira_amm: ContractId,
+    ) -> (ContractId, AccountLogic<WalletUnlocked>) {
+        let configurables = AccountLogicConfigurables::default()
+            .with_FACTORY_ID(Bits256(*factory))
+            .unwrap()
+            .with_MIRA_AMM_CONTRACT_ID(mira_amm)
+            .unwrap();
+        let contract_id = Contract::load_from(
+            ACCOUNT_LOGIC_CONTRACT_BINARY_PATH,
+            LoadConfiguration::default().with_configurables(configurables),
+        )
+        .unwrap()
+        .deploy(wallet, TxPolicies::default())
+        .await
+        .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = AccountLogic::new(contract_id, wallet.clone());
+
+        (id, instance)
+    }
+
+    // the packed intent that is hashed and signed by the account owner
+    pub fn pack_intent(
+        actions: &Vec<Action>,
+        nonce: u64,
+        deadline: u32,
+        account: ContractId,
+    ) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        encoded.extend_from_slice(&*account);
+        encoded.extend_from_slice(&nonce.to_be_bytes());
+        encoded.extend_from_slice(&deadline.to_be_bytes());
+        // the actions are appended in their abi encoding
+        encoded.extend(
+            ABIEncoder::default()
+                .encode(&[actions.clone().into_token()])
+                .unwrap(),
+        );
+        encoded
+    }
+
+    pub async fn sign_intent(
+        wallet: &WalletUnlocked,
+        actions: &Vec<Action>,
+        nonce: u64,
+        deadline: u32,
+        account: ContractId,
+    ) -> B512 {
+        let signature = wallet
+            .sign(Message::new(pack_intent(actions, nonce, deadline, account)))
+            .await
+            .unwrap();
+        let mut first = [0u8; 32];
+        let mut second = [0u8; 32];
+        first.copy_from_slice(&signature[..32]);
+        second.copy_from_slice(&signature[32..]);
+        B512::from((Bits256(first), Bits256(second)))
+    }
+
+    // the packed proposal that shared account owners approve by its hash
+    pub fn pack_proposal(proposal: &Proposal, nonce: u64, account: ContractId) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        encoded.extend_from_slice(&*account);
+        encoded.extend_from_slice(&nonce.to_be_bytes());
+        // the proposal is appended in its abi encoding
+        encoded.extend(
+            ABIEncoder::default()
+                .encode(&[proposal.clone().into_token()])
+                .unwrap(),
+        );
+        encoded
+    }
+
+    pub fn get_proposal_hash(proposal: &Proposal, nonce: u64, account: ContractId) -> Bits256 {
+        let hash: [u8; 32] = Sha256::digest(pack_proposal(proposal, nonce, account)).into();
+        Bits256(hash)
+    }
+
+    pub async fn register_account(
+        factory: &AccountFactory<WalletUnlocked>,
+        account: ContractId,
+        owner: Identity,
+    ) -> CallResponse<()> {
+        factory
+            .methods()
+            .register_and_call(account, owner, None)
+            .with_contract_ids(&[account.into()])
+            .call()
+            .await
+            .unwrap()
+    }
+}
+
 pub mod mock {
     use super::*;
 
@@ -147,6 +282,23 @@ pub mod mock {
         (id, instance)
     }
 
+    pub async fn deploy_mock_basic_lender_contract(
+        wallet: &WalletUnlocked,
+    ) -> (ContractId, MockBasicLender<WalletUnlocked>) {
+        let contract_id: Bech32ContractId = Contract::load_from(
+            MOCK_BASIC_LENDER_CONTRACT_BINARY_PATH,
+            LoadConfiguration::default(),
+        )
+        .unwrap()
+        .deploy(wallet, TxPolicies::default())
+        .await
+        .unwrap();
+        let id = ContractId::from(contract_id.clone());
+        let instance = MockBasicLender::new(contract_id, wallet.clone());
+
+        (id, instance)
+    }
+
     pub async fn deploy_logger_contract(
         wallet: &WalletUnlocked,
     ) -> (ContractId, Logger<WalletUnlocked>) {
@@ -204,6 +356,112 @@ pub mod mock {
     }
 }
 
+pub mod orders {
+    use super::*;
+    use fuels::core::traits::Signer;
+    use fuels::crypto::Message;
+    use fuels::types::{Identity, B512};
+
+    pub use bindings::orders::{get_order_hash, pack_order};
+
+    pub async fn deploy_one_delta_orders_contract(
+        wallet: &WalletUnlocked,
+    ) -> (ContractId, OneDeltaOrders<WalletUnlocked>) {
+        let configurables = OneDeltaOrdersConfigurables::default()
+            .with_INITIAL_OWNER(Identity::Address(wallet.address().into()))
+            .unwrap();
+        let contract_id = Contract::load_from(
+            ONE_DELTA_ORDERS_CONTRACT_BINARY_PATH,
+            LoadConfiguration::default().with_configurables(configurables),
+        )
+        .unwrap()
+        .deploy(wallet, TxPolicies::default())
+        .await
+        .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = OneDeltaOrders::new(contract_id, wallet.clone());
+
+        (id, instance)
+    }
+
+    pub async fn deploy_order_router_contract(
+        wallet: &WalletUnlocked,
+        orders: ContractId,
+        amm: ContractId,
+    ) -> (ContractId, OrderRouter<WalletUnlocked>) {
+        let configurables = OrderRouterConfigurables::default()
+            .with_ONE_DELTA_ORDERS_CONTRACT_ID(orders)
+            .unwrap()
+            .with_MIRA_AMM_CONTRACT_ID(amm)
+            .unwrap();
+        let contract_id = Contract::load_from(
+            ORDER_ROUTER_CONTRACT_BINARY_PATH,
+            LoadConfiguration::default().with_configurables(configurables),
+        )
+        .unwrap()
+        .deploy(wallet, TxPolicies::default())
+        .await
+        .unwrap();
+
+        let id = ContractId::from(contract_id.clone());
+        let instance = OrderRouter::new(contract_id, wallet.clone());
+
+        (id, instance)
+    }
+
+    // the packed order that is hashed and signed by the maker
+    pub async fn sign_order(
+        wallet: &WalletUnlocked,
+        order: &Order,
+        verifying_contract: ContractId,
+    ) -> B512 {
+        let signature = wallet
+            .sign(Message::new(pack_order(order, verifying_contract)))
+            .await
+            .unwrap();
+        let mut first = [0u8; 32];
+        let mut second = [0u8; 32];
+        first.copy_from_slice(&signature[..32]);
+        second.copy_from_slice(&signature[32..]);
+        B512::from((Bits256(first), Bits256(second)))
+    }
+
+    pub async fn deposit(
+        contract: &OneDeltaOrders<WalletUnlocked>,
+        asset_id: AssetId,
+        amount: u64,
+        receiver: Identity,
+    ) -> CallResponse<()> {
+        contract
+            .methods()
+            .deposit(Bits256(*asset_id), receiver)
+            .call_params(
+                CallParameters::default()
+                    .with_amount(amount)
+                    .with_asset_id(asset_id),
+            )
+            .unwrap()
+            .call()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_order_fill_status(
+        contract: &OneDeltaOrders<WalletUnlocked>,
+        order: &Order,
+    ) -> (bool, u64) {
+        let order_hash = get_order_hash(order, contract.contract_id().into());
+        contract
+            .methods()
+            .get_order_fill_status(order_hash)
+            .simulate(Execution::StateReadOnly)
+            .await
+            .unwrap()
+            .value
+    }
+}
+
 pub mod scripts {
     use super::*;
 
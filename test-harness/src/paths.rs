@@ -13,5 +13,14 @@ pub const COMPOSER_SCRIPT_BINARY_PATH: &str =
 pub const AMM_CONTRACT_BINARY_PATH: &str = "../../fixtures/mira-amm/mira_amm_contract.bin";
 pub const SWAYLEND_CONTRACT_BINARY_PATH: &str = "../../fixtures/swaylend/market.bin";
 pub const MOCK_SWAYLEND_CONTRACT_BINARY_PATH: &str = "../../contracts/mocks/mock_swaylend/out/debug/mock_swaylend.bin";
+pub const MOCK_BASIC_LENDER_CONTRACT_BINARY_PATH: &str = "../../contracts/mocks/mock_basic_lender/out/debug/mock_basic_lender.bin";
 pub const MOCK_TOKEN_CONTRACT_BINARY_PATH: &str = "../../contracts/mocks/mock_token/out/debug/mock_token.bin";
 pub const LOGGER_CONTRACT_BINARY_PATH: &str = "../../contracts/logger/out/debug/logger.bin";
+pub const ONE_DELTA_ORDERS_CONTRACT_BINARY_PATH: &str = "../../contracts/one_delta_orders/out/debug/one_delta_orders.bin";
+pub const ORDER_ROUTER_CONTRACT_BINARY_PATH: &str = "../../contracts/order_router/out/debug/order_router.bin";
+
+// accounts
+pub const ACCOUNT_LOGIC_CONTRACT_BINARY_PATH: &str = "../../contracts/account_logic/out/debug/account_logic.bin";
+pub const ACCOUNT_PROXY_CONTRACT_BINARY_PATH: &str = "../../contracts/account_proxy/out/debug/account_proxy.bin";
+pub const ACCOUNT_FACTORY_CONTRACT_BINARY_PATH: &str = "../../contracts/account_factory/out/debug/account_factory.bin";
+pub const BEACON_CONTRACT_BINARY_PATH: &str = "../../contracts/beacon/out/debug/beacon.bin";
@@ -1,9 +1,9 @@
 import { launchTestNode } from 'fuels/test-utils';
 import { describe, test, expect } from 'vitest';
 import { addressInput } from '../ts-scripts/utils';
-import { OrderInput } from '../ts-scripts/typegen/OneDeltaOrders';
+import { OrderInput, PriceConditionInput } from '../ts-scripts/typegen/OneDeltaOrders';
 import { OrderTestUtils } from './utils';
-import { ZeroBytes32 } from 'fuels';
+import { hashMessage, ZeroBytes32 } from 'fuels';
 
 describe('Order fill via `msg_amount`', async () => {
 
@@ -787,4 +787,134 @@ describe('Order fill via `msg_amount`', async () => {
       taker_fill_amount.toString()
     )
   });
+  test('Facilitates take-profit order fill once triggered', async () => {
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, taker]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const oracle = await OrderTestUtils.deployOracle(deployer)
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [maker, taker],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    const maker_amount = OrderTestUtils.getRandomAmount()
+
+    await OrderTestUtils.getOrders(maker, OrderTestUtils.contractIdBits(Orders)).functions.deposit(maker_asset, addressInput(maker.address))
+      .callParams({ forward: { assetId: maker_asset, amount: maker_amount } })
+      .call()
+
+    const taker_amount = OrderTestUtils.getRandomAmount()
+
+    const price_feed_id = hashMessage("ETH/USD")
+
+    // take-profit at 3000 without decimals
+    const condition: PriceConditionInput = {
+      market: oracle.id.toB256(),
+      price_feed_id,
+      trigger_price: 3000,
+      trigger_exponent: 0,
+      is_above: true,
+    }
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount,
+      taker_amount,
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, false, false, true),
+      maker_receiver: ZeroBytes32
+    })
+
+    const signatureRaw = await maker.signMessage(OrderTestUtils.packConditionalOrder(order, condition, Orders))
+
+    // the plain fill does not accept conditional orders
+    let reason: string | undefined = undefined
+    try {
+      await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill(
+        order,
+        signatureRaw,
+        taker_amount,
+        addressInput(taker.address)
+      )
+        .callParams({ forward: { assetId: taker_asset, amount: taker_amount } })
+        .call()
+    } catch (e) {
+      reason = String(e)
+    }
+    expect(reason).to.toBeDefined()
+    expect(reason).to.include(OrderTestUtils.ErrorCodes.MISSING_PRICE_CONDITION)
+
+    // 2999.99 with 2 decimals - not yet triggered
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 2, price: 299999, publish_time: OrderTestUtils.getPublishTime() }
+    ).call()
+
+    reason = undefined
+    try {
+      await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill_conditional(
+        order,
+        condition,
+        signatureRaw,
+        taker_amount,
+        addressInput(taker.address)
+      )
+        .callParams({ forward: { assetId: taker_asset, amount: taker_amount } })
+        .addContracts([oracle])
+        .call()
+    } catch (e) {
+      reason = String(e)
+    }
+    expect(reason).to.toBeDefined()
+    expect(reason).to.include(OrderTestUtils.ErrorCodes.PRICE_CONDITION_NOT_MET)
+
+    // 3000 with 2 decimals - triggered
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 2, price: 300000, publish_time: OrderTestUtils.getPublishTime() }
+    ).call()
+
+    const [
+      taker_maker_asset_balance_before,
+    ] = await OrderTestUtils.getConventionalBalances(
+      taker,
+      [maker_asset]
+    )
+
+    await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill_conditional(
+      order,
+      condition,
+      signatureRaw,
+      taker_amount,
+      addressInput(taker.address)
+    )
+      .callParams({ forward: { assetId: taker_asset, amount: taker_amount } })
+      .addContracts([oracle])
+      .call()
+
+    const [
+      taker_maker_asset_balance_after,
+    ] = await OrderTestUtils.getConventionalBalances(
+      taker,
+      [maker_asset]
+    )
+
+    expect(
+      taker_maker_asset_balance_after.sub(taker_maker_asset_balance_before).toString()
+    ).to.equal(
+      maker_amount.toString()
+    )
+  });
 });
@@ -3,7 +3,7 @@ import { describe, test, expect } from 'vitest';
 import { addressInput, contractIdInput, prepareRequest } from '../ts-scripts/utils';
 import { OrderInput } from '../ts-scripts/typegen/OneDeltaOrders';
 import { OrderTestUtils } from './utils';
-import { BigNumberish, CoinQuantity } from 'fuels';
+import { BigNumberish, CoinQuantity, ZeroBytes32 } from 'fuels';
 import { BatchSwapStepInput } from '../ts-scripts/typegen/BatchSwapExactInScript';
 import { txParams } from '../ts-scripts/utils/constants';
 
@@ -396,4 +396,280 @@ describe('Order fill with custom maker_receiver', async () => {
       taker_fill_amount.toString()
     )
   });
+  test('Facilitates order fill of contract maker', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [signer, deployer, taker]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const contractMaker = await OrderTestUtils.deployContractMaker(deployer, signer.address.toB256())
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [signer, taker],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    const maker_amount = OrderTestUtils.getRandomAmount()
+
+    // the signer funds the contract maker's balance
+    await OrderTestUtils.getOrders(signer, OrderTestUtils.contractIdBits(Orders)).functions.deposit(maker_asset, contractIdInput(contractMaker.id))
+      .callParams({ forward: { assetId: maker_asset, amount: maker_amount } })
+      .call()
+
+    const taker_amount = OrderTestUtils.getRandomAmount()
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount,
+      taker_amount,
+      maker: contractMaker.id.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(true, false, OrderTestUtils.MAX_EXPIRY, true),
+      maker_receiver: ZeroBytes32
+    })
+
+    const signatureRaw = await signer.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill(
+      order,
+      signatureRaw,
+      taker_amount,
+      addressInput(taker.address)
+    )
+      .callParams({ forward: { assetId: taker_asset, amount: taker_amount } })
+      .addContracts([contractMaker])
+      .call()
+
+    await OrderTestUtils.testFillStatus(order, Orders, order.taker_amount, false)
+
+    const [
+      maker_maker_asset_balance_after,
+    ] = await OrderTestUtils.getMakerBalances(
+      contractMaker.id.toB256(),
+      [maker_asset],
+      Orders
+    )
+
+    // the full maker balance is consumed
+    expect(maker_maker_asset_balance_after.toString()).to.equal("0")
+
+    // the contract maker receives the taker asset
+    const contract_maker_taker_asset_balance = await contractMaker.getBalance(taker_asset)
+    expect(contract_maker_taker_asset_balance.toString()).to.equal(taker_amount.toString())
+  });
+  test('Contract maker receives without contract receiver flag', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [signer, deployer, taker]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const contractMaker = await OrderTestUtils.deployContractMaker(deployer, signer.address.toB256())
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [signer, taker],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    const maker_amount = OrderTestUtils.getRandomAmount()
+
+    // the signer funds the contract maker's balance
+    await OrderTestUtils.getOrders(signer, OrderTestUtils.contractIdBits(Orders)).functions.deposit(maker_asset, contractIdInput(contractMaker.id))
+      .callParams({ forward: { assetId: maker_asset, amount: maker_amount } })
+      .call()
+
+    const taker_amount = OrderTestUtils.getRandomAmount()
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount,
+      taker_amount,
+      maker: contractMaker.id.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      // the contract receiver flag is not set, the contract signer flag suffices
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, true),
+      maker_receiver: ZeroBytes32
+    })
+
+    const signatureRaw = await signer.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill(
+      order,
+      signatureRaw,
+      taker_amount,
+      addressInput(taker.address)
+    )
+      .callParams({ forward: { assetId: taker_asset, amount: taker_amount } })
+      .addContracts([contractMaker])
+      .call()
+
+    await OrderTestUtils.testFillStatus(order, Orders, order.taker_amount, false)
+
+    const [
+      maker_maker_asset_balance_after,
+    ] = await OrderTestUtils.getMakerBalances(
+      contractMaker.id.toB256(),
+      [maker_asset],
+      Orders
+    )
+
+    // the full maker balance is consumed
+    expect(maker_maker_asset_balance_after.toString()).to.equal("0")
+
+    // the contract maker receives the taker asset
+    const contract_maker_taker_asset_balance = await contractMaker.getBalance(taker_asset)
+    expect(contract_maker_taker_asset_balance.toString()).to.equal(taker_amount.toString())
+  });
+  test('Maker hook provides liquidity just in time', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, taker]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const hook = await OrderTestUtils.deployMakerHook(deployer, Orders.id.toB256(), maker.address.toB256())
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [maker, taker],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    const maker_amount = OrderTestUtils.getRandomAmount()
+    const taker_amount = OrderTestUtils.getRandomAmount()
+
+    // the maker has no deposit, funds are held by the hook
+    const tx = await maker.transferToContract(hook.id, maker_amount, maker_asset)
+    await tx.waitForResult()
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount,
+      taker_amount,
+      maker: hook.id.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, true, true),
+      maker_receiver: hook.id.toB256()
+    })
+
+    const signatureRaw = await maker.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill(
+      order,
+      signatureRaw,
+      taker_amount,
+      addressInput(taker.address)
+    )
+      .callParams({ forward: { assetId: taker_asset, amount: taker_amount } })
+      .addContracts([hook])
+      .call()
+
+    await OrderTestUtils.testFillStatus(order, Orders, order.taker_amount, false)
+
+    const [maker_maker_asset_balance_after] = await OrderTestUtils.getMakerBalances(
+      hook.id.toB256(),
+      [maker_asset],
+      Orders
+    )
+
+    // the just in time deposit is fully consumed
+    expect(maker_maker_asset_balance_after.toString()).to.equal("0")
+
+    // the hook receives the taker asset
+    const hook_taker_asset_balance = await hook.getBalance(taker_asset)
+    expect(hook_taker_asset_balance.toString()).to.equal(taker_amount.toString())
+
+    // both hooks were called
+    const [before_count, after_count] = (await hook.functions.get_call_counts().simulate()).value
+    expect(before_count.toNumber()).to.equal(1)
+    expect(after_count.toNumber()).to.equal(1)
+  });
+  test('Cannot fill order that names a foreign maker hook', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 4 } });
+
+    const {
+      wallets: [maker, deployer, taker, attacker]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const hook = await OrderTestUtils.deployMakerHook(deployer, Orders.id.toB256(), maker.address.toB256())
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [maker, taker],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    const maker_amount = OrderTestUtils.getRandomAmount()
+    const taker_amount = OrderTestUtils.getRandomAmount()
+
+    // the hook holds funds of its owner
+    const tx = await maker.transferToContract(hook.id, maker_amount, maker_asset)
+    await tx.waitForResult()
+
+    // a third party signs an order that names the hook
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount,
+      taker_amount,
+      maker: attacker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, false, true),
+      maker_receiver: hook.id.toB256()
+    })
+
+    const signatureRaw = await attacker.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    let reason: string | undefined = undefined
+    try {
+      await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill(
+        order,
+        signatureRaw,
+        taker_amount,
+        addressInput(taker.address)
+      )
+        .callParams({ forward: { assetId: taker_asset, amount: taker_amount } })
+        .addContracts([hook])
+        .call()
+    } catch (e) {
+      reason = String(e)
+    }
+    expect(reason).to.toBeDefined()
+    expect(reason).to.include(OrderTestUtils.ErrorCodes.INVALID_MAKER_HOOK)
+
+    // the hook was not called and kept its funds
+    const [before_count] = (await hook.functions.get_call_counts().simulate()).value
+    expect(before_count.toNumber()).to.equal(0)
+    expect((await hook.getBalance(maker_asset)).toString()).to.equal(maker_amount.toString())
+  });
 });
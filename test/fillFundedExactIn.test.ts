@@ -256,6 +256,99 @@ describe('Order fill via `fill_funded` through BatchSwapExactInScript', async ()
     )
   });
 
+  test('Facilitates full order fill exact input with versioned step data', async () => {
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, taker]
+    } = launched;
+
+    const { Orders, tokens, loggerId } = await OrderTestUtils.fixture(deployer)
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [maker, taker],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    const maker_amount = OrderTestUtils.getRandomAmount()
+    const taker_amount = OrderTestUtils.getRandomAmount()
+
+    await OrderTestUtils.getOrders(maker, OrderTestUtils.contractIdBits(Orders)).functions.deposit(maker_asset, addressInput(maker.address))
+      .callParams({ forward: { assetId: maker_asset, amount: maker_amount } })
+      .call()
+
+    const [
+      taker_maker_asset_balance_before,
+    ] = await OrderTestUtils.getConventionalBalances(
+      taker,
+      [maker_asset]
+    )
+
+    /** DEFINE PARAMETERS */
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount,
+      taker_amount,
+      maker: maker.address.toB256(),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+      maker_receiver: ZeroBytes32
+    })
+
+    const signatureRaw = await maker.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    // header with version and flags before the order
+    const swap_step = OrderTestUtils.createRfqBatchSwapStep(order, signatureRaw, addressInput(taker.address), true)
+    expect(swap_step.data.length).to.equal(162)
+
+    const path: [BigNumberish, BigNumberish, boolean, BatchSwapStepInput[]][] = [
+      [
+        taker_amount, maker_amount.sub(1), true, [swap_step]
+      ]
+    ]
+
+    const deadline = OrderTestUtils.MAX_EXPIRY
+
+    const request = await (await OrderTestUtils.callExactInScriptScope(path, deadline, taker, Orders.id.toB256(), loggerId))
+      .addContracts([Orders])
+      .txParams(txParams)
+      .getTransactionRequest()
+
+    const inputAssets: CoinQuantity[] = [
+      {
+        assetId: taker_asset,
+        amount: taker_amount,
+      }
+    ];
+
+    const finalRequest = await prepareRequest(taker, request, 3, inputAssets, [Orders.id.toB256()])
+
+    /** EXECUTE TXN */
+
+    const tx = await taker.sendTransaction(finalRequest, { estimateTxDependencies: true })
+    await tx.waitForResult()
+
+    await OrderTestUtils.testFillStatus(order, Orders, taker_amount, false)
+
+    const [
+      taker_maker_asset_balance_after,
+    ] = await OrderTestUtils.getConventionalBalances(
+      taker,
+      [maker_asset]
+    )
+
+    expect(
+      taker_maker_asset_balance_after.sub(taker_maker_asset_balance_before).toString()
+    ).to.equal(
+      maker_amount.toString()
+    )
+  });
+
   test('Facilitates partial order fill exact input', async () => {
     const launched = await launchTestNode({ walletsConfig: { count: 3 } });
 
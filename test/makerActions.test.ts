@@ -1,9 +1,9 @@
 import { launchTestNode } from 'fuels/test-utils';
 import { describe, test, expect } from 'vitest';
 import { OrderTestUtils } from './utils';
-import { OrderInput } from '../ts-scripts/typegen/OneDeltaOrders';
+import { OrderInput, PriceConditionInput } from '../ts-scripts/typegen/OneDeltaOrders';
 import { addressInput } from '../ts-scripts/utils';
-import { ZeroBytes32 } from 'fuels';
+import { hashMessage, toBytes, ZeroBytes32 } from 'fuels';
 
 describe('Maker Actions', async () => {
   test('Maker can deposit', async () => {
@@ -451,6 +451,371 @@ describe('Maker Actions', async () => {
     ).to.include(OrderTestUtils.ErrorCodes.INVALID_CANCEL)
   });
 
+  test('Maker can scope delegation to pair and amount', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, delegate]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    const max_maker_amount = OrderTestUtils.getRandomAmount(2)
+
+    // set scoped delegate
+    await OrderTestUtils.getOrders(maker, OrderTestUtils.contractIdBits(Orders)).functions.register_order_signer_delegate_scoped(
+      delegate.address.toB256(),
+      {
+        expiry: OrderTestUtils.MAX_EXPIRY,
+        maker_asset,
+        taker_asset,
+        max_maker_amount
+      }
+    )
+      .call()
+
+    const delegation = await Orders.functions.get_order_signer_delegation(
+      maker.address.toB256(),
+      delegate.address.toB256(),
+    )
+      .simulate()
+
+    expect(delegation.value?.max_maker_amount.toString()).to.equal(max_maker_amount.toString())
+
+    const getValidationError = async (order: OrderInput) => {
+      const delegateSig = await delegate.signMessage(OrderTestUtils.packOrder(order, Orders))
+      const result = await Orders.functions.validate_order(order, delegateSig).simulate()
+      return result.value[1].toNumber()
+    }
+
+    // within scope
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount: max_maker_amount,
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+      maker_receiver: ZeroBytes32
+    })
+    expect(await getValidationError(order)).to.equal(OrderTestUtils.ErrorCodes.NO_ERROR)
+
+    // notional too large
+    const orderTooLarge: OrderInput = { ...order, maker_amount: max_maker_amount.add(1) }
+    expect(await getValidationError(orderTooLarge)).to.equal(OrderTestUtils.ErrorCodes.INVALID_ORDER_SIGNATURE)
+
+    // other pair
+    const orderOtherPair: OrderInput = { ...order, maker_asset: taker_asset, taker_asset: maker_asset }
+    expect(await getValidationError(orderOtherPair)).to.equal(OrderTestUtils.ErrorCodes.INVALID_ORDER_SIGNATURE)
+  });
+
+  test('Delegation expires', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, delegate],
+      provider
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const current_height = (await provider.getBlockNumber()).toNumber()
+
+    // set delegate that is already expired
+    await OrderTestUtils.getOrders(maker, OrderTestUtils.contractIdBits(Orders)).functions.register_order_signer_delegate_scoped(
+      delegate.address.toB256(),
+      {
+        expiry: current_height - 1,
+        maker_asset: ZeroBytes32,
+        taker_asset: ZeroBytes32,
+        max_maker_amount: 0
+      }
+    )
+      .call()
+
+    const isDelegate = await Orders.functions.is_order_signer_delegate(
+      maker.address.toB256(),
+      delegate.address.toB256(),
+    )
+      .simulate()
+
+    expect(isDelegate.value).to.be.false
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+    })
+
+    const delegateSig = await delegate.signMessage(OrderTestUtils.packOrder(order, Orders))
+    const result = await Orders.functions.validate_order(order, delegateSig).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.INVALID_ORDER_SIGNATURE)
+
+    // expired delegates cannot cancel either
+    let reason: string | undefined = undefined
+    try {
+      await OrderTestUtils.getOrders(delegate, OrderTestUtils.contractIdBits(Orders)).functions.cancel_order(
+        order,
+      )
+        .call()
+    }
+    catch (e) {
+      reason = String(e)
+    }
+
+    expect(reason).to.toBeDefined()
+
+    expect(
+      reason
+    ).to.include(OrderTestUtils.ErrorCodes.INVALID_CANCEL)
+  });
+
+  test('Relayer can withdraw with maker signature', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, relayer]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const [maker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens), ["test"])
+
+    await OrderTestUtils.fundWallets([maker], OrderTestUtils.contractIdBits(tokens), [maker_asset], [OrderTestUtils.DEFAULT_MINT_AMOUNT])
+
+    const deposit_amount = OrderTestUtils.getRandomAmount(1, 10000)
+
+    await OrderTestUtils.getOrders(maker, OrderTestUtils.contractIdBits(Orders)).functions.deposit(maker_asset, addressInput(maker.address))
+      .callParams({ forward: { assetId: maker_asset, amount: deposit_amount } })
+      .call()
+
+    const withdraw_amount = OrderTestUtils.getRandomAmount(1, deposit_amount.toNumber())
+
+    const operation_nonce = (await Orders.functions.get_operation_nonce(maker.address.toB256()).simulate()).value
+
+    const signature = await maker.signMessage(
+      OrderTestUtils.packOperation(
+        OrderTestUtils.Operations.WITHDRAW,
+        maker.address.toB256(),
+        operation_nonce,
+        OrderTestUtils.packWithdraw(maker_asset, withdraw_amount, maker.address.toB256()),
+        Orders
+      )
+    )
+
+    const [maker_balance_before_withdraw] = await OrderTestUtils.getConventionalBalances(maker, [maker_asset])
+
+    await OrderTestUtils.getOrders(relayer, OrderTestUtils.contractIdBits(Orders)).functions.withdraw_signed(
+      maker.address.toB256(),
+      maker_asset,
+      withdraw_amount,
+      addressInput(maker.address),
+      operation_nonce,
+      signature
+    )
+      .call()
+
+    const [balance_after_withdraw] = await OrderTestUtils.getMakerBalances(maker, [maker_asset], Orders)
+    const [maker_balance_after_withdraw] = await OrderTestUtils.getConventionalBalances(maker, [maker_asset])
+
+    expect(
+      deposit_amount.sub(balance_after_withdraw).toString()
+    ).to.equal(withdraw_amount.toString())
+
+    expect(
+      maker_balance_after_withdraw.sub(maker_balance_before_withdraw).toString()
+    ).to.equal(withdraw_amount.toString())
+
+    // the signature cannot be replayed
+    let reason: string | undefined = undefined
+    try {
+      await OrderTestUtils.getOrders(relayer, OrderTestUtils.contractIdBits(Orders)).functions.withdraw_signed(
+        maker.address.toB256(),
+        maker_asset,
+        withdraw_amount,
+        addressInput(maker.address),
+        operation_nonce,
+        signature
+      )
+        .call()
+    } catch (e) {
+      reason = String(e)
+    }
+
+    expect(reason).to.toBeDefined()
+
+    expect(
+      reason
+    ).to.include(OrderTestUtils.ErrorCodes.INVALID_OPERATION_NONCE)
+  });
+
+  test('Relayer cannot redirect signed withdrawal', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, relayer]
+    } = launched;
+
+    const { Orders, tokens } = await OrderTestUtils.fixture(deployer)
+
+    const [maker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens), ["test"])
+
+    await OrderTestUtils.fundWallets([maker], OrderTestUtils.contractIdBits(tokens), [maker_asset], [OrderTestUtils.DEFAULT_MINT_AMOUNT])
+
+    const deposit_amount = OrderTestUtils.getRandomAmount(1, 10000)
+
+    await OrderTestUtils.getOrders(maker, OrderTestUtils.contractIdBits(Orders)).functions.deposit(maker_asset, addressInput(maker.address))
+      .callParams({ forward: { assetId: maker_asset, amount: deposit_amount } })
+      .call()
+
+    const signature = await maker.signMessage(
+      OrderTestUtils.packOperation(
+        OrderTestUtils.Operations.WITHDRAW,
+        maker.address.toB256(),
+        0,
+        OrderTestUtils.packWithdraw(maker_asset, deposit_amount, maker.address.toB256()),
+        Orders
+      )
+    )
+
+    let reason: string | undefined = undefined
+    try {
+      await OrderTestUtils.getOrders(relayer, OrderTestUtils.contractIdBits(Orders)).functions.withdraw_signed(
+        maker.address.toB256(),
+        maker_asset,
+        deposit_amount,
+        addressInput(relayer.address),
+        0,
+        signature
+      )
+        .call()
+    } catch (e) {
+      reason = String(e)
+    }
+
+    expect(reason).to.toBeDefined()
+
+    expect(
+      reason
+    ).to.include(OrderTestUtils.ErrorCodes.INVALID_OPERATION_SIGNATURE)
+  });
+
+  test('Relayer can invalidate nonce and cancel order with maker signature', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, relayer]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+    })
+
+    const new_nonce = OrderTestUtils.getRandomAmount(1)
+
+    const nonceSignature = await maker.signMessage(
+      OrderTestUtils.packOperation(
+        OrderTestUtils.Operations.INVALIDATE_NONCE,
+        maker.address.toB256(),
+        0,
+        OrderTestUtils.packInvalidateNonce(order.maker_asset as string, order.taker_asset as string, new_nonce),
+        Orders
+      )
+    )
+
+    await OrderTestUtils.getOrders(relayer, OrderTestUtils.contractIdBits(Orders)).functions.invalidate_nonce_signed(
+      maker.address.toB256(),
+      order.maker_asset,
+      order.taker_asset,
+      new_nonce,
+      0,
+      nonceSignature
+    )
+      .call()
+
+    expect((await OrderTestUtils.getNonce(order, Orders)).toString()).to.equal(new_nonce.toString())
+
+    const cancelSignature = await maker.signMessage(
+      OrderTestUtils.packOperation(
+        OrderTestUtils.Operations.CANCEL_ORDER,
+        maker.address.toB256(),
+        1,
+        toBytes(OrderTestUtils.getHash(order, Orders), 32),
+        Orders
+      )
+    )
+
+    await OrderTestUtils.getOrders(relayer, OrderTestUtils.contractIdBits(Orders)).functions.cancel_order_signed(
+      order,
+      1,
+      cancelSignature
+    )
+      .call()
+
+    await OrderTestUtils.testFillStatus(order, Orders, 0, true)
+  });
+
+  test('Relayer can cancel conditional order with maker signature', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [maker, deployer, relayer]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, false, false, true),
+    })
+
+    const condition: PriceConditionInput = {
+      market: ZeroBytes32,
+      price_feed_id: hashMessage("ETH/USD"),
+      trigger_price: 3000,
+      trigger_exponent: 0,
+      is_above: true,
+    }
+
+    const conditionalHash = hashMessage(OrderTestUtils.packConditionalOrder(order, condition, Orders))
+
+    const cancelSignature = await maker.signMessage(
+      OrderTestUtils.packOperation(
+        OrderTestUtils.Operations.CANCEL_ORDER,
+        maker.address.toB256(),
+        0,
+        toBytes(conditionalHash, 32),
+        Orders
+      )
+    )
+
+    await OrderTestUtils.getOrders(relayer, OrderTestUtils.contractIdBits(Orders)).functions.cancel_conditional_order_signed(
+      order,
+      condition,
+      0,
+      cancelSignature
+    )
+      .call()
+
+    const [cancelled, filled] = (await Orders.functions.get_order_fill_status(conditionalHash).simulate()).value
+    expect(cancelled).to.equal(true)
+    expect(filled.toString()).to.equal("0")
+  });
+
   test('Maker can prevent partial fills', async () => {
 
     const launched = await launchTestNode({ walletsConfig: { count: 4 } });
@@ -201,5 +201,279 @@ describe('Order Rotuer', async () => {
     const profit = touter_balance_after.sub(touter_balance_before).toNumber()
     expect(profit).to.equal(expected_profit)
   });
-});
+  test('Can match crossing orders and pay spread to receiver', async () => {
+    const launched = await launchTestNode({ walletsConfig: { count: 5 } });
+
+    const {
+      wallets: [maker0, deployer, matcher, maker1, spreadReceiver]
+    } = launched;
+
+    const { Orders, tokens, Router } = await OrderTestUtils.fixtureWithRouter(deployer)
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [maker0, maker1],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    // left sells 10k for 20k, right buys 10k for 22k
+    const left_maker_amount = 10_000
+    const left_taker_amount = 20_000
+    const right_maker_amount = 22_000
+    const right_taker_amount = 10_000
+
+    await OrderTestUtils.getOrders(maker0, OrderTestUtils.contractIdBits(Orders))
+      .functions.deposit(maker_asset, addressInput(maker0.address))
+      .callParams({ forward: { assetId: maker_asset, amount: left_maker_amount } })
+      .call()
+
+    await OrderTestUtils.getOrders(maker1, OrderTestUtils.contractIdBits(Orders))
+      .functions.deposit(taker_asset, addressInput(maker1.address))
+      .callParams({ forward: { assetId: taker_asset, amount: right_maker_amount } })
+      .call()
+
+    const left: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount: left_maker_amount,
+      taker_amount: left_taker_amount,
+      maker: maker0.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+      maker_receiver: ZeroBytes32
+    })
+
+    const right: OrderInput = OrderTestUtils.getOrder({
+      maker_asset: taker_asset,
+      taker_asset: maker_asset,
+      maker_amount: right_maker_amount,
+      taker_amount: right_taker_amount,
+      maker: maker1.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+      maker_receiver: ZeroBytes32
+    })
+
+    const leftSignature = await maker0.signMessage(OrderTestUtils.packOrder(left, Orders))
+    const rightSignature = await maker1.signMessage(OrderTestUtils.packOrder(right, Orders))
+
+    const [receiver_balance_before] = await OrderTestUtils.getConventionalBalances(spreadReceiver, [taker_asset])
+
+    await OrderTestUtils.getRouter(matcher, OrderTestUtils.contractIdBits(Router)).functions.match_orders(
+      left,
+      leftSignature,
+      right,
+      rightSignature,
+      addressInput(spreadReceiver.address)
+    )
+      .addContracts([Orders])
+      .call()
+
+    const [receiver_balance_after] = await OrderTestUtils.getConventionalBalances(spreadReceiver, [taker_asset])
+
+    expect(receiver_balance_after.sub(receiver_balance_before).toNumber()).to.equal(right_maker_amount - left_taker_amount)
+
+    // both orders are filled completely
+    await OrderTestUtils.testFillStatus(left, Orders, left_taker_amount)
+    await OrderTestUtils.testFillStatus(right, Orders, right_taker_amount)
+  });
+
+  test('Matches partially filled order up to remaining amount', async () => {
+    const launched = await launchTestNode({ walletsConfig: { count: 5 } });
+
+    const {
+      wallets: [maker0, deployer, taker, maker1, spreadReceiver]
+    } = launched;
+
+    const { Orders, tokens, Router } = await OrderTestUtils.fixtureWithRouter(deployer)
+
+    const [maker_asset, taker_asset] = await OrderTestUtils.createTokens(deployer, OrderTestUtils.contractIdBits(tokens))
+
+    await OrderTestUtils.fundWallets(
+      [maker0, taker, maker1],
+      OrderTestUtils.contractIdBits(tokens),
+      [maker_asset, taker_asset, taker_asset],
+      [OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT, OrderTestUtils.DEFAULT_MINT_AMOUNT]
+    )
+
+    // left sells 10k for 20k, right buys 20k for 44k
+    const left_maker_amount = 10_000
+    const left_taker_amount = 20_000
+    const right_maker_amount = 44_000
+    const right_taker_amount = 20_000
+
+    await OrderTestUtils.getOrders(maker0, OrderTestUtils.contractIdBits(Orders))
+      .functions.deposit(maker_asset, addressInput(maker0.address))
+      .callParams({ forward: { assetId: maker_asset, amount: left_maker_amount } })
+      .call()
+
+    await OrderTestUtils.getOrders(maker1, OrderTestUtils.contractIdBits(Orders))
+      .functions.deposit(taker_asset, addressInput(maker1.address))
+      .callParams({ forward: { assetId: taker_asset, amount: right_maker_amount } })
+      .call()
 
+    const left: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount: left_maker_amount,
+      taker_amount: left_taker_amount,
+      maker: maker0.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+      maker_receiver: ZeroBytes32
+    })
+
+    const right: OrderInput = OrderTestUtils.getOrder({
+      maker_asset: taker_asset,
+      taker_asset: maker_asset,
+      maker_amount: right_maker_amount,
+      taker_amount: right_taker_amount,
+      maker: maker1.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+      maker_receiver: ZeroBytes32
+    })
+
+    const leftSignature = await maker0.signMessage(OrderTestUtils.packOrder(left, Orders))
+    const rightSignature = await maker1.signMessage(OrderTestUtils.packOrder(right, Orders))
+
+    // fill a quarter of the left order upfront
+    const prefill_amount = left_taker_amount / 4
+    await OrderTestUtils.getOrders(taker, OrderTestUtils.contractIdBits(Orders)).functions.fill(
+      left,
+      leftSignature,
+      prefill_amount,
+      addressInput(taker.address)
+    )
+      .callParams({ forward: { assetId: taker_asset, amount: prefill_amount } })
+      .call()
+
+    const result = await OrderTestUtils.getRouter(taker, OrderTestUtils.contractIdBits(Router)).functions.match_orders(
+      left,
+      leftSignature,
+      right,
+      rightSignature,
+      addressInput(spreadReceiver.address)
+    )
+      .addContracts([Orders])
+      .call()
+
+    const [left_taker_filled, left_maker_filled, spread] = (await result.waitForResult()).value
+
+    // the left remainder is matched, the right order is filled partially
+    const left_remaining = left_taker_amount - prefill_amount
+    const left_maker_remaining = OrderTestUtils.computeMakerFillAmount(left_remaining, left_maker_amount, left_taker_amount)
+    const right_maker_filled = OrderTestUtils.computeMakerFillAmount(left_maker_remaining, right_maker_amount, right_taker_amount)
+
+    expect(left_taker_filled.toNumber()).to.equal(left_remaining)
+    expect(left_maker_filled.toString()).to.equal(left_maker_remaining.toString())
+    expect(spread.toString()).to.equal(right_maker_filled.sub(left_remaining).toString())
+
+    await OrderTestUtils.testFillStatus(left, Orders, left_taker_amount)
+    await OrderTestUtils.testFillStatus(right, Orders, left_maker_remaining)
+  });
+
+  test('Cannot match orders that do not cross', async () => {
+    const launched = await launchTestNode({ walletsConfig: { count: 4 } });
+
+    const {
+      wallets: [maker0, deployer, matcher, maker1]
+    } = launched;
+
+    const { Orders, Router } = await OrderTestUtils.fixtureWithRouter(deployer)
+
+    const maker_asset = maker0.address.toB256()
+    const taker_asset = maker1.address.toB256()
+
+    // left sells 10k for 20k, right only pays 19.999k for 10k
+    const left: OrderInput = OrderTestUtils.getOrder({
+      maker_asset,
+      taker_asset,
+      maker_amount: 10_000,
+      taker_amount: 20_000,
+      maker: maker0.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+    })
+
+    const right: OrderInput = OrderTestUtils.getOrder({
+      maker_asset: taker_asset,
+      taker_asset: maker_asset,
+      maker_amount: 19_999,
+      taker_amount: 10_000,
+      maker: maker1.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+    })
+
+    const leftSignature = await maker0.signMessage(OrderTestUtils.packOrder(left, Orders))
+    const rightSignature = await maker1.signMessage(OrderTestUtils.packOrder(right, Orders))
+
+    let reason: string | undefined = undefined
+    try {
+      await OrderTestUtils.getRouter(matcher, OrderTestUtils.contractIdBits(Router)).functions.match_orders(
+        left,
+        leftSignature,
+        right,
+        rightSignature,
+        addressInput(matcher.address)
+      )
+        .addContracts([Orders])
+        .call()
+    } catch (e) {
+      reason = String(e)
+    }
+
+    expect(reason).to.toBeDefined()
+    expect(reason).to.include(OrderTestUtils.RouterErrorCodes.ORDERS_DO_NOT_CROSS)
+  });
+
+  test('Cannot match orders on different pairs', async () => {
+    const launched = await launchTestNode({ walletsConfig: { count: 4 } });
+
+    const {
+      wallets: [maker0, deployer, matcher, maker1]
+    } = launched;
+
+    const { Orders, Router } = await OrderTestUtils.fixtureWithRouter(deployer)
+
+    const left: OrderInput = OrderTestUtils.getOrder({
+      maker: maker0.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+    })
+
+    // same direction as the left order
+    const right: OrderInput = OrderTestUtils.getOrder({
+      maker_asset: left.maker_asset,
+      taker_asset: left.taker_asset,
+      maker: maker1.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.MAX_EXPIRY,
+    })
+
+    const leftSignature = await maker0.signMessage(OrderTestUtils.packOrder(left, Orders))
+    const rightSignature = await maker1.signMessage(OrderTestUtils.packOrder(right, Orders))
+
+    let reason: string | undefined = undefined
+    try {
+      await OrderTestUtils.getRouter(matcher, OrderTestUtils.contractIdBits(Router)).functions.match_orders(
+        left,
+        leftSignature,
+        right,
+        rightSignature,
+        addressInput(matcher.address)
+      )
+        .addContracts([Orders])
+        .call()
+    } catch (e) {
+      reason = String(e)
+    }
+
+    expect(reason).to.toBeDefined()
+    expect(reason).to.include(OrderTestUtils.RouterErrorCodes.INVALID_MATCH)
+  });
+});
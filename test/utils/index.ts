@@ -1,9 +1,10 @@
-import { BigNumberish, BN, concatBytes, Contract, hashMessage, randomBytes, toBytes, WalletUnlocked } from 'fuels';
+import { BigNumberish, BN, concatBytes, Contract, DateTime, hashMessage, randomBytes, toBytes, WalletUnlocked } from 'fuels';
 import { addressInput, assetIdInput, contractIdInput } from '../../ts-scripts/utils';
+import { encodeOrderParamsV1 } from '../../ts-scripts/utils/coder';
 
 import { MockTokenFactory } from '../../ts-scripts/typegen/MockTokenFactory';
 import { MockToken } from '../../ts-scripts/typegen/MockToken';
-import { OneDeltaOrders, OrderInput } from '../../ts-scripts/typegen/OneDeltaOrders';
+import { OneDeltaOrders, OrderInput, PriceConditionInput } from '../../ts-scripts/typegen/OneDeltaOrders';
 import { OneDeltaOrdersFactory } from '../../ts-scripts/typegen/OneDeltaOrdersFactory';
 import { BatchSwapStepInput, BatchSwapExactInScript, IdentityInput } from '../../ts-scripts/typegen/BatchSwapExactInScript';
 import { BatchSwapExactOutScript } from '../../ts-scripts/typegen/BatchSwapExactOutScript';
@@ -11,6 +12,9 @@ import { expect } from 'vitest';
 import { OrderRouterFactory } from '../../ts-scripts/typegen/OrderRouterFactory';
 import { OrderRouter } from '../../ts-scripts/typegen/OrderRouter';
 import { LoggerFactory } from '../../ts-scripts/typegen/LoggerFactory';
+import { MockContractMakerFactory } from '../../ts-scripts/typegen/MockContractMakerFactory';
+import { MockMakerHookFactory } from '../../ts-scripts/typegen/MockMakerHookFactory';
+import { MockSwaylendFactory } from '../../ts-scripts/typegen/MockSwaylendFactory';
 
 
 export namespace OrderTestUtils {
@@ -29,6 +33,30 @@ export namespace OrderTestUtils {
     INVALID_CANCEL = 0x9,
     NO_PARTIAL_FILL = 11,
     BALANCE_VIOLATION = 12,
+    INVALID_OPERATION_NONCE = 16,
+    INVALID_OPERATION_SIGNATURE = 17,
+    INVALID_MAKER_HOOK = 18,
+    MISSING_PRICE_CONDITION = 19,
+    PRICE_CONDITION_NOT_MET = 20,
+    NOT_OWNER = 21,
+    ALREADY_INITIALIZED = 22,
+    PAUSED = 23,
+    INVALID_PRICE = 24,
+    STALE_PRICE = 25,
+  }
+
+  export enum Operations {
+    WITHDRAW = 0,
+    INVALIDATE_NONCE = 1,
+    CANCEL_ORDER = 2,
+    REGISTER_DELEGATE = 3,
+  }
+
+  export enum RouterErrorCodes {
+    INVALID_SENDER = 101,
+    INVALID_MATCH = 102,
+    ORDERS_DO_NOT_CROSS = 103,
+    NOTHING_TO_MATCH = 104,
   }
 
   export enum ScriptErrorCodes {
@@ -42,6 +70,36 @@ export namespace OrderTestUtils {
 
   /** Utility functions */
 
+  // deploy a contract maker hook that funds fills from its own balance
+  export async function deployMakerHook(deployer: WalletUnlocked, orders: string, signer: string) {
+    const deployTx = await MockMakerHookFactory.deploy(deployer, {
+      configurableConstants: {
+        ONE_DELTA_ORDERS_CONTRACT_ID: { bits: orders },
+        SIGNER: signer
+      }
+    })
+    const { contract } = await deployTx.waitForResult()
+    return contract
+  }
+
+  // deploy a swaylend mock that serves as price oracle
+  export async function deployOracle(deployer: WalletUnlocked) {
+    const deployTx = await MockSwaylendFactory.deploy(deployer)
+    const { contract } = await deployTx.waitForResult()
+    return contract
+  }
+
+  // deploy a contract maker that accepts signatures of `signer`
+  export async function deployContractMaker(deployer: WalletUnlocked, signer: string) {
+    const deployTx = await MockContractMakerFactory.deploy(deployer, {
+      configurableConstants: {
+        SIGNER: signer
+      }
+    })
+    const { contract } = await deployTx.waitForResult()
+    return contract
+  }
+
   // deploy all relevant fixtures
   export async function fixture(deployer: WalletUnlocked) {
 
@@ -49,7 +107,11 @@ export namespace OrderTestUtils {
 
     const { contract: tokens } = await deployTokenTx.waitForResult()
 
-    const deployRfqTx = await OneDeltaOrdersFactory.deploy(deployer)
+    const deployRfqTx = await OneDeltaOrdersFactory.deploy(deployer, {
+      configurableConstants: {
+        INITIAL_OWNER: addressInput(deployer.address)
+      }
+    })
 
     const { contract: Orders } = await deployRfqTx.waitForResult()
 
@@ -69,7 +131,11 @@ export namespace OrderTestUtils {
 
     const { contract: tokens } = await deployTokenTx.waitForResult()
 
-    const deployRfqTx = await OneDeltaOrdersFactory.deploy(deployer)
+    const deployRfqTx = await OneDeltaOrdersFactory.deploy(deployer, {
+      configurableConstants: {
+        INITIAL_OWNER: addressInput(deployer.address)
+      }
+    })
 
     const { contract: Orders } = await deployRfqTx.waitForResult()
 
@@ -91,13 +157,19 @@ export namespace OrderTestUtils {
 
   const HIGH_BIT_0 = 1n << 63n;
   const HIGH_BIT_1 = 1n << 62n;
+  const HIGH_BIT_2 = 1n << 61n;
+  const HIGH_BIT_3 = 1n << 60n;
+  const HIGH_BIT_4 = 1n << 59n;
   const EXPIRY_MASK = BigInt("0x00000000ffffffff");
 
 
-  export function encodeTraits(contractReceiver = false, noPartialFills = false, expiry = OrderTestUtils.MAX_EXPIRY) {
+  export function encodeTraits(contractReceiver = false, noPartialFills = false, expiry = OrderTestUtils.MAX_EXPIRY, contractSigner = false, makerHook = false, conditional = false) {
     let traits = BigInt(expiry)
     if (contractReceiver) traits = (traits & ~HIGH_BIT_0) | HIGH_BIT_0
     if (noPartialFills) traits = (traits & ~HIGH_BIT_1) | HIGH_BIT_1
+    if (contractSigner) traits = (traits & ~HIGH_BIT_2) | HIGH_BIT_2
+    if (makerHook) traits = (traits & ~HIGH_BIT_3) | HIGH_BIT_3
+    if (conditional) traits = (traits & ~HIGH_BIT_4) | HIGH_BIT_4
     return traits.toString()
   }
 
@@ -133,7 +205,8 @@ export namespace OrderTestUtils {
     deadline: number,
     user: WalletUnlocked,
     Order: string,
-    loggerId: any
+    loggerId: any,
+    totals: any[] = []
   ) {
 
     return await new BatchSwapExactInScript(user).setConfigurableConstants(
@@ -144,7 +217,8 @@ export namespace OrderTestUtils {
       }
     ).functions.main(
       path,
-      deadline
+      deadline,
+      totals
     ) as any
   }
 
@@ -153,7 +227,8 @@ export namespace OrderTestUtils {
     deadline: number,
     user: WalletUnlocked,
     Order: string,
-    loggerId: any
+    loggerId: any,
+    totals: any[] = []
   ) {
 
     return await new BatchSwapExactOutScript(user).setConfigurableConstants(
@@ -164,11 +239,17 @@ export namespace OrderTestUtils {
       }
     ).functions.main(
       path,
-      deadline
+      deadline,
+      totals
     ) as any
   }
 
   /** We randomize the amounts used for tests */
+  // oracle publish time in TAI64, `age` seconds in the past
+  export function getPublishTime(age = 0) {
+    return DateTime.fromUnixSeconds(Math.floor(Date.now() / 1000) - age).toTai64()
+  }
+
   export function getRandomAmount(min = 1, max = DEFAULT_RANDOM_AMOUNT_LIMIT) {
     return new BN(Math.round((min + Math.random() * (max - min))))
   }
@@ -267,6 +348,47 @@ export namespace OrderTestUtils {
     ]) as any
   }
 
+  // conditional orders append the price condition to the packed order
+  export function packConditionalOrder(order: OrderInput, condition: PriceConditionInput, rfq: OneDeltaOrders | string) {
+    return concatBytes([
+      packOrder(order, rfq),
+      toBytes(condition.market, 32),
+      toBytes(condition.price_feed_id, 32),
+      toBytes(condition.trigger_price, 8),
+      toBytes(condition.trigger_exponent, 4),
+      toBytes(condition.is_above ? 1 : 0, 1),
+    ]) as any
+  }
+
+  // packs a maker operation that can be signed and relayed
+  export function packOperation(operation: Operations, maker: string, operationNonce: BigNumberish, payload: Uint8Array, rfq: OneDeltaOrders | string) {
+    const rfqAddress = typeof rfq === "string" ? rfq : rfq.id.toB256()
+    return concatBytes([
+      toBytes(rfqAddress, 32),
+      toBytes(operation, 1),
+      toBytes(maker, 32),
+      toBytes(operationNonce, 8),
+      payload,
+    ]) as any
+  }
+
+  export function packWithdraw(asset: string, amount: BigNumberish, receiver: string, receiverIsContract = false) {
+    return concatBytes([
+      toBytes(asset, 32),
+      toBytes(amount, 8),
+      toBytes(receiverIsContract ? 1 : 0, 1),
+      toBytes(receiver, 32),
+    ])
+  }
+
+  export function packInvalidateNonce(maker_asset: string, taker_asset: string, new_nonce: BigNumberish) {
+    return concatBytes([
+      toBytes(maker_asset, 32),
+      toBytes(taker_asset, 32),
+      toBytes(new_nonce, 8),
+    ])
+  }
+
   export function routerParams(order: OrderInput, signature: string) {
     return concatBytes([
       toBytes(order.maker_asset, 32),
@@ -333,20 +455,21 @@ export namespace OrderTestUtils {
     return bal
   }
 
-  export function createRfqBatchSwapStep(order: OrderInput, signature: string, receiver: IdentityInput) {
+  export function createRfqBatchSwapStep(order: OrderInput, signature: string, receiver: IdentityInput, versioned = false) {
+    const orderData = concatBytes([
+      toBytes(order.maker_amount, 8),
+      toBytes(order.taker_amount, 8),
+      toBytes(order.maker, 32),
+      toBytes(order.nonce, 8),
+      toBytes(order.maker_traits, 8),
+      toBytes(order.maker_receiver, 32),
+      toBytes(signature, 64),
+    ])
     const data: BatchSwapStepInput = {
       asset_in: assetIdInput(order.taker_asset),
       asset_out: assetIdInput(order.maker_asset),
       dex_id: RFQ_DEX_ID,
-      data: concatBytes([
-        toBytes(order.maker_amount, 8),
-        toBytes(order.taker_amount, 8),
-        toBytes(order.maker, 32),
-        toBytes(order.nonce, 8),
-        toBytes(order.maker_traits, 8),
-        toBytes(order.maker_receiver, 32),
-        toBytes(signature, 64),
-      ]) as any,
+      data: (versioned ? encodeOrderParamsV1(orderData) : orderData) as any,
       receiver
     }
     return data
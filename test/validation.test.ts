@@ -1,7 +1,7 @@
 import { launchTestNode } from 'fuels/test-utils';
 import { describe, test, expect } from 'vitest';
-import { hashMessage} from 'fuels';
-import { OrderInput } from '../ts-scripts/typegen/OneDeltaOrders';
+import { hashMessage, ZeroBytes32 } from 'fuels';
+import { OrderInput, PriceConditionInput } from '../ts-scripts/typegen/OneDeltaOrders';
 import { OrderTestUtils } from './utils';
 import { addressInput } from '../ts-scripts/utils';
 
@@ -259,5 +259,285 @@ describe('Order Validation', async () => {
     ).to.include(OrderTestUtils.ErrorCodes.INVALID_CANCEL)
 
   });
-});
+  test('Validates order of contract maker via callback', async () => {
+
+    const launched = await launchTestNode();
+
+    const {
+      wallets: [signer, deployer]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const contractMaker = await OrderTestUtils.deployContractMaker(deployer, signer.address.toB256())
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: contractMaker.id.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(true, false, OrderTestUtils.MAX_EXPIRY, true),
+      maker_receiver: contractMaker.id.toB256()
+    })
+
+    const signatureRaw = await signer.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    const result = await Orders.functions.validate_order(
+      order,
+      signatureRaw
+    ).addContracts([contractMaker]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.NO_ERROR)
+  });
+
+  test('Rejects order of contract maker if callback rejects', async () => {
+
+    const launched = await launchTestNode({ walletsConfig: { count: 3 } });
+
+    const {
+      wallets: [signer, deployer, other]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const contractMaker = await OrderTestUtils.deployContractMaker(deployer, signer.address.toB256())
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: contractMaker.id.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(true, false, OrderTestUtils.MAX_EXPIRY, true),
+      maker_receiver: contractMaker.id.toB256()
+    })
+
+    const signatureRaw = await other.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    const result = await Orders.functions.validate_order(
+      order,
+      signatureRaw
+    ).addContracts([contractMaker]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.INVALID_ORDER_SIGNATURE)
+  });
+
+  test('Contract maker order cannot be validated as signed order', async () => {
+
+    const launched = await launchTestNode();
+
+    const {
+      wallets: [signer, deployer]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const contractMaker = await OrderTestUtils.deployContractMaker(deployer, signer.address.toB256())
+
+    // the contract signer flag is not set, as such the signature is recovered
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: contractMaker.id.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(true),
+      maker_receiver: contractMaker.id.toB256()
+    })
+
+    const signatureRaw = await signer.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    const result = await Orders.functions.validate_order(
+      order,
+      signatureRaw
+    ).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.INVALID_ORDER_SIGNATURE)
+  });
+  test('Conditional order cannot be validated without condition', async () => {
+
+    const launched = await launchTestNode();
+
+    const {
+      wallets: [maker, deployer]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, false, false, true),
+    })
+
+    const signatureRaw = await maker.signMessage(OrderTestUtils.packOrder(order, Orders))
+
+    const result = await Orders.functions.validate_order(
+      order,
+      signatureRaw
+    ).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.MISSING_PRICE_CONDITION)
+  });
+
+  test('Stop-loss order is valid once price crossed trigger', async () => {
 
+    const launched = await launchTestNode();
+
+    const {
+      wallets: [maker, deployer]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const oracle = await OrderTestUtils.deployOracle(deployer)
+
+    const price_feed_id = hashMessage("ETH/USD")
+
+    // stop-loss at 2500 with 2 decimals
+    const condition: PriceConditionInput = {
+      market: oracle.id.toB256(),
+      price_feed_id,
+      trigger_price: 250000,
+      trigger_exponent: 2,
+      is_above: false,
+    }
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, false, false, true),
+      maker_receiver: ZeroBytes32
+    })
+
+    const signatureRaw = await maker.signMessage(OrderTestUtils.packConditionalOrder(order, condition, Orders))
+
+    // 2600 with 8 decimals
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 8, price: 260000000000, publish_time: OrderTestUtils.getPublishTime() }
+    ).call()
+
+    let result = await Orders.functions.validate_conditional_order(
+      order,
+      condition,
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[0]).toBe(hashMessage(OrderTestUtils.packConditionalOrder(order, condition, Orders)))
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.PRICE_CONDITION_NOT_MET)
+
+    // 2400 with 8 decimals
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 8, price: 240000000000, publish_time: OrderTestUtils.getPublishTime() }
+    ).call()
+
+    result = await Orders.functions.validate_conditional_order(
+      order,
+      condition,
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.NO_ERROR)
+
+    // the condition is part of the signed hash
+    result = await Orders.functions.validate_conditional_order(
+      order,
+      { ...condition, trigger_price: 300000 },
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.INVALID_ORDER_SIGNATURE)
+  });
+
+  test('Conditional order rejects unset, stale and future prices', async () => {
+
+    const launched = await launchTestNode();
+
+    const {
+      wallets: [maker, deployer]
+    } = launched;
+
+    const { Orders } = await OrderTestUtils.fixture(deployer)
+
+    const oracle = await OrderTestUtils.deployOracle(deployer)
+
+    const price_feed_id = hashMessage("ETH/USD")
+
+    // stop-loss at 2500 with 2 decimals
+    const condition: PriceConditionInput = {
+      market: oracle.id.toB256(),
+      price_feed_id,
+      trigger_price: 250000,
+      trigger_exponent: 2,
+      is_above: false,
+    }
+
+    const order: OrderInput = OrderTestUtils.getOrder({
+      maker: maker.address.toB256(),
+      nonce: OrderTestUtils.getRandomAmount(1),
+      maker_traits: OrderTestUtils.encodeTraits(false, false, OrderTestUtils.MAX_EXPIRY, false, false, true),
+      maker_receiver: ZeroBytes32
+    })
+
+    const signatureRaw = await maker.signMessage(OrderTestUtils.packConditionalOrder(order, condition, Orders))
+
+    // no price was published, a zero price would trigger the stop-loss
+    let result = await Orders.functions.validate_conditional_order(
+      order,
+      condition,
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.INVALID_PRICE)
+
+    // 2400 with 8 decimals, published 10 minutes ago
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 8, price: 240000000000, publish_time: OrderTestUtils.getPublishTime(600) }
+    ).call()
+
+    result = await Orders.functions.validate_conditional_order(
+      order,
+      condition,
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.STALE_PRICE)
+
+    // a price published 10 minutes from now is invalid
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 8, price: 240000000000, publish_time: OrderTestUtils.getPublishTime(-600) }
+    ).call()
+
+    result = await Orders.functions.validate_conditional_order(
+      order,
+      condition,
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.INVALID_PRICE)
+
+    // an exponent that cannot be scaled is invalid
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 80, price: 240000000000, publish_time: OrderTestUtils.getPublishTime() }
+    ).call()
+
+    result = await Orders.functions.validate_conditional_order(
+      order,
+      condition,
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.INVALID_PRICE)
+
+    // the same price published now triggers the order
+    await oracle.functions.mock_set_price(
+      price_feed_id,
+      { confidence: 0, exponent: 8, price: 240000000000, publish_time: OrderTestUtils.getPublishTime() }
+    ).call()
+
+    result = await Orders.functions.validate_conditional_order(
+      order,
+      condition,
+      signatureRaw
+    ).addContracts([oracle]).simulate()
+
+    expect(result.value[1].toNumber()).to.equal(OrderTestUtils.ErrorCodes.NO_ERROR)
+  });
+});
@@ -0,0 +1,100 @@
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::error::OrderBookError;
+use crate::service::OrderBookService;
+use crate::types::{
+    parse_bits256, to_hex, BookOrderJson, QuoteJson, QuoteRequest, SignedOrderJson,
+};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitResponse {
+    pub order_hash: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PairQuery {
+    pub maker_asset: String,
+    pub taker_asset: String,
+}
+
+impl IntoResponse for OrderBookError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            OrderBookError::Chain(_) => StatusCode::BAD_GATEWAY,
+            OrderBookError::DuplicateOrder => StatusCode::CONFLICT,
+            _ => StatusCode::UNPROCESSABLE_ENTITY,
+        };
+        (
+            status,
+            Json(ErrorResponse {
+                error: self.to_string(),
+            }),
+        )
+            .into_response()
+    }
+}
+
+// POST /orders
+// submit a signed order
+async fn submit_order(
+    State(service): State<Arc<OrderBookService>>,
+    Json(request): Json<SignedOrderJson>,
+) -> Result<Json<SubmitResponse>, OrderBookError> {
+    let (order, signature) = request.parse()?;
+    let order_hash = service.submit(order, signature).await?;
+    Ok(Json(SubmitResponse {
+        order_hash: to_hex(&order_hash.0),
+    }))
+}
+
+// GET /orders?maker_asset=..&taker_asset=..
+// the orders of a pair, best price first
+async fn get_orders(
+    State(service): State<Arc<OrderBookService>>,
+    Query(pair): Query<PairQuery>,
+) -> Result<Json<Vec<BookOrderJson>>, OrderBookError> {
+    let maker_asset = parse_bits256(&pair.maker_asset)?;
+    let taker_asset = parse_bits256(&pair.taker_asset)?;
+    let book = service.book().read().await;
+    Ok(Json(
+        book.orders(maker_asset, taker_asset)
+            .iter()
+            .map(BookOrderJson::from)
+            .collect(),
+    ))
+}
+
+// POST /quote
+// the best orders to sell an amount of an asset, including the executor step data
+async fn quote(
+    State(service): State<Arc<OrderBookService>>,
+    Json(request): Json<QuoteRequest>,
+) -> Result<Json<QuoteJson>, OrderBookError> {
+    let quote = service
+        .quote(
+            parse_bits256(&request.sell_asset)?,
+            parse_bits256(&request.buy_asset)?,
+            request.amount,
+        )
+        .await?;
+    Ok(Json(QuoteJson::from(&quote)))
+}
+
+pub fn router(service: Arc<OrderBookService>) -> Router {
+    Router::new()
+        .route("/orders", post(submit_order).get(get_orders))
+        .route("/quote", post(quote))
+        .with_state(service)
+}
@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use fuels::types::Bits256;
+
+use crate::error::OrderBookError;
+use crate::types::{BookOrder, Quote, QuoteFill};
+use crate::validation::{compute_maker_fill_amount, no_partial_fill};
+
+// maker_asset -> taker_asset
+type Pair = ([u8; 32], [u8; 32]);
+
+// In-memory book of signed orders, grouped by pair and
+// sorted by price, the best order for the taker first
+#[derive(Debug, Default)]
+pub struct OrderBook {
+    pairs: HashMap<Pair, Vec<BookOrder>>,
+}
+
+// compares the maker / taker price, higher is better for the taker
+fn compare_price(a: &BookOrder, b: &BookOrder) -> Ordering {
+    let a_price = a.order.maker_amount as u128 * b.order.taker_amount as u128;
+    let b_price = b.order.maker_amount as u128 * a.order.taker_amount as u128;
+    b_price.cmp(&a_price)
+}
+
+impl OrderBook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, entry: BookOrder) -> Result<(), OrderBookError> {
+        if self.get(entry.order_hash).is_some() {
+            return Err(OrderBookError::DuplicateOrder);
+        }
+
+        let orders = self
+            .pairs
+            .entry((entry.order.maker_asset.0, entry.order.taker_asset.0))
+            .or_default();
+        // insert after all orders with the same price to keep time priority
+        let index = orders.partition_point(|o| compare_price(o, &entry) != Ordering::Greater);
+        orders.insert(index, entry);
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, order_hash: Bits256) -> Option<BookOrder> {
+        for orders in self.pairs.values_mut() {
+            if let Some(index) = orders.iter().position(|o| o.order_hash == order_hash) {
+                return Some(orders.remove(index));
+            }
+        }
+        None
+    }
+
+    pub fn get(&self, order_hash: Bits256) -> Option<&BookOrder> {
+        self.pairs
+            .values()
+            .flatten()
+            .find(|o| o.order_hash == order_hash)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // the orders that sell `maker_asset` for `taker_asset`, best first
+    pub fn orders(&self, maker_asset: Bits256, taker_asset: Bits256) -> &[BookOrder] {
+        self.pairs
+            .get(&(maker_asset.0, taker_asset.0))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn all_orders(&self) -> impl Iterator<Item = &BookOrder> {
+        self.pairs.values().flatten()
+    }
+
+    pub fn get_mut(&mut self, order_hash: Bits256) -> Option<&mut BookOrder> {
+        self.pairs
+            .values_mut()
+            .flatten()
+            .find(|o| o.order_hash == order_hash)
+    }
+
+    // drop all orders that cannot be filled anymore
+    pub fn prune(&mut self) {
+        for orders in self.pairs.values_mut() {
+            orders.retain(|o| o.remaining_taker_amount() != 0 && o.maker_balance != 0);
+        }
+        self.pairs.retain(|_, orders| !orders.is_empty());
+    }
+
+    // Walk the book for selling `amount` of `sell_asset` for `buy_asset`.
+    // Balances are shared across orders of the same maker, as such we
+    // track the balance that is left for each maker while walking the book
+    pub fn quote(&self, sell_asset: Bits256, buy_asset: Bits256, amount: u64) -> Quote {
+        let mut quote = Quote::default();
+        let mut remaining = amount;
+        let mut maker_balances: HashMap<[u8; 32], u64> = HashMap::new();
+
+        for entry in self.orders(buy_asset, sell_asset) {
+            if remaining == 0 {
+                break;
+            }
+
+            let order = &entry.order;
+            let maker_balance = maker_balances
+                .entry(order.maker.0)
+                .or_insert(entry.maker_balance);
+
+            // the maximum taker amount that the maker balance can serve
+            let taker_amount_by_balance = (*maker_balance as u128 * order.taker_amount as u128
+                / order.maker_amount as u128)
+                .min(u64::MAX as u128) as u64;
+            let taker_fill_amount = remaining
+                .min(entry.remaining_taker_amount())
+                .min(taker_amount_by_balance);
+
+            // orders without partial fills have to be filled completely
+            if no_partial_fill(order.maker_traits) && taker_fill_amount != order.taker_amount {
+                continue;
+            }
+
+            let maker_fill_amount = compute_maker_fill_amount(
+                taker_fill_amount,
+                order.maker_amount,
+                order.taker_amount,
+            );
+            if maker_fill_amount == 0 {
+                continue;
+            }
+
+            *maker_balance -= maker_fill_amount;
+            remaining -= taker_fill_amount;
+            quote.amount_in += taker_fill_amount;
+            quote.amount_out += maker_fill_amount;
+            quote.fills.push(QuoteFill {
+                order: order.clone(),
+                signature: entry.signature,
+                order_hash: entry.order_hash,
+                taker_fill_amount,
+                maker_fill_amount,
+            });
+        }
+
+        quote
+    }
+}
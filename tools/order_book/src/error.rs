@@ -0,0 +1,47 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderBookError {
+    // the request could not be parsed
+    InvalidInput(String),
+    // the signature does not recover to the maker
+    InvalidSignature,
+    // the order expired at the given block height
+    Expired,
+    // the maker traits are not supported by the book
+    UnsupportedTraits(&'static str),
+    // the order is already in the book
+    DuplicateOrder,
+    // the maker has no balance of the maker asset
+    NoMakerBalance,
+    // the order is already fully filled
+    FullyFilled,
+    // `validate_order` returned a non-zero error code
+    Contract(u64),
+    // the provider or contract call failed
+    Chain(String),
+}
+
+impl fmt::Display for OrderBookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidInput(message) => write!(f, "invalid input: {message}"),
+            Self::InvalidSignature => write!(f, "invalid order signature"),
+            Self::Expired => write!(f, "order expired"),
+            Self::UnsupportedTraits(reason) => write!(f, "unsupported maker traits: {reason}"),
+            Self::DuplicateOrder => write!(f, "order already in book"),
+            Self::NoMakerBalance => write!(f, "maker has no balance of the maker asset"),
+            Self::FullyFilled => write!(f, "order fully filled"),
+            Self::Contract(code) => write!(f, "order rejected by contract with code {code}"),
+            Self::Chain(message) => write!(f, "chain error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for OrderBookError {}
+
+impl From<fuels::types::errors::Error> for OrderBookError {
+    fn from(error: fuels::types::errors::Error) -> Self {
+        Self::Chain(error.to_string())
+    }
+}
@@ -0,0 +1,6 @@
+pub mod api;
+pub mod book;
+pub mod error;
+pub mod service;
+pub mod types;
+pub mod validation;
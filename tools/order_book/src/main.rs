@@ -0,0 +1,64 @@
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use bindings::OneDeltaOrders;
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{ContractId, Provider};
+use fuels::types::SecretKey;
+use order_book::api::router;
+use order_book::service::OrderBookService;
+
+// Serves the order book for an orders deployment, configured via
+// - ORDER_BOOK_NODE_URL       the graphql endpoint of the node
+// - ORDER_BOOK_ORDERS_ID      the orders contract id
+// - ORDER_BOOK_ADDRESS        the socket to listen on (default 127.0.0.1:3030)
+// - ORDER_BOOK_PRIVATE_KEY    optional key used for dry-runs (default random)
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("order book: {message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run() -> Result<(), String> {
+    let node_url = required_var("ORDER_BOOK_NODE_URL")?;
+    let orders_id = required_var("ORDER_BOOK_ORDERS_ID")?;
+    let address =
+        std::env::var("ORDER_BOOK_ADDRESS").unwrap_or_else(|_| "127.0.0.1:3030".to_string());
+
+    let provider = Provider::connect(&node_url)
+        .await
+        .map_err(|e| format!("cannot connect to {node_url}: {e}"))?;
+    let wallet = match std::env::var("ORDER_BOOK_PRIVATE_KEY") {
+        Ok(key) => {
+            let key = key
+                .parse::<SecretKey>()
+                .map_err(|_| "ORDER_BOOK_PRIVATE_KEY is not a valid private key".to_string())?;
+            WalletUnlocked::new_from_private_key(key, Some(provider))
+        }
+        Err(_) => WalletUnlocked::new_random(Some(provider)),
+    };
+
+    let orders_id: ContractId = orders_id
+        .parse()
+        .map_err(|_| format!("ORDER_BOOK_ORDERS_ID is not a valid contract id: {orders_id}"))?;
+    let service = Arc::new(OrderBookService::new(OneDeltaOrders::new(
+        orders_id, wallet,
+    )));
+
+    let listener = tokio::net::TcpListener::bind(&address)
+        .await
+        .map_err(|e| format!("cannot listen on {address}: {e}"))?;
+    println!("order book listening on {address}");
+    axum::serve(listener, router(service))
+        .await
+        .map_err(|e| format!("server error: {e}"))
+}
+
+fn required_var(name: &str) -> Result<String, String> {
+    std::env::var(name).map_err(|_| format!("{name} is not set"))
+}
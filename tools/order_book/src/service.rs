@@ -0,0 +1,225 @@
+use bindings::orders::get_order_hash;
+use bindings::{OneDeltaOrders, Order};
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{ContractId, Execution};
+use fuels::types::{Bits256, B512};
+use tokio::sync::RwLock;
+
+use crate::book::OrderBook;
+use crate::error::OrderBookError;
+use crate::types::{BookOrder, Quote};
+use crate::validation::{is_contract_signer, recover_signer, validate_order_offline, PAUSED};
+
+// chain state of a book order, see `OrderBookService::revalidate`
+enum Revalidated {
+    Valid {
+        filled_taker_amount: u64,
+        maker_balance: u64,
+    },
+    Paused,
+    Stale,
+}
+
+// Order book backed by a 1delta orders deployment.
+// Orders are validated offline and against the chain when submitted,
+// quotes refresh the chain state of the quoted pair beforehand.
+pub struct OrderBookService {
+    orders: OneDeltaOrders<WalletUnlocked>,
+    book: RwLock<OrderBook>,
+}
+
+impl OrderBookService {
+    pub fn new(orders: OneDeltaOrders<WalletUnlocked>) -> Self {
+        Self {
+            orders,
+            book: RwLock::new(OrderBook::new()),
+        }
+    }
+
+    pub fn verifying_contract(&self) -> ContractId {
+        self.orders.contract_id().into()
+    }
+
+    pub fn book(&self) -> &RwLock<OrderBook> {
+        &self.book
+    }
+
+    // validate an order and add it to the book, returns the order hash
+    pub async fn submit(&self, order: Order, signature: B512) -> Result<Bits256, OrderBookError> {
+        let order_hash = get_order_hash(&order, self.verifying_contract());
+        if self.book.read().await.get(order_hash).is_some() {
+            return Err(OrderBookError::DuplicateOrder);
+        }
+
+        ////////////////////////////////////////////////////
+        // offline checks
+        ////////////////////////////////////////////////////
+
+        let block_height = self.block_height().await?;
+        validate_order_offline(&order, block_height)?;
+
+        // contract makers validate signatures themselves, this is covered by `validate_order`
+        if !is_contract_signer(order.maker_traits) {
+            let signer = recover_signer(order_hash, &signature)?;
+            if signer != order.maker && !self.is_delegate(order.maker, signer).await? {
+                return Err(OrderBookError::InvalidSignature);
+            }
+        }
+
+        ////////////////////////////////////////////////////
+        // chain state checks
+        ////////////////////////////////////////////////////
+
+        let (_, error_code, filled_taker_amount) = self
+            .orders
+            .methods()
+            .validate_order(order.clone(), signature)
+            .simulate(Execution::StateReadOnly)
+            .await?
+            .value;
+        if error_code != 0 {
+            return Err(OrderBookError::Contract(error_code));
+        }
+
+        let maker_balance = self.maker_balance(order.maker, order.maker_asset).await?;
+
+        let entry = BookOrder {
+            order,
+            signature,
+            order_hash,
+            filled_taker_amount,
+            maker_balance,
+        };
+        if entry.remaining_taker_amount() == 0 {
+            return Err(OrderBookError::FullyFilled);
+        }
+        if maker_balance == 0 {
+            return Err(OrderBookError::NoMakerBalance);
+        }
+
+        self.book.write().await.insert(entry)?;
+        Ok(order_hash)
+    }
+
+    // Re-validate all orders against the chain and drop orders that are
+    // cancelled, invalidated, expired, filled or unfunded.
+    // Orders are kept while fills are paused
+    pub async fn refresh(&self) -> Result<(), OrderBookError> {
+        let entries: Vec<BookOrder> = self.book.read().await.all_orders().cloned().collect();
+        let results = self.revalidate(entries).await?;
+        apply_revalidated(&mut *self.book.write().await, results);
+        Ok(())
+    }
+
+    // Same as `refresh`, for the orders that sell `maker_asset` for `taker_asset` only
+    pub async fn refresh_pair(
+        &self,
+        maker_asset: Bits256,
+        taker_asset: Bits256,
+    ) -> Result<(), OrderBookError> {
+        let entries = self
+            .book
+            .read()
+            .await
+            .orders(maker_asset, taker_asset)
+            .to_vec();
+        let results = self.revalidate(entries).await?;
+        apply_revalidated(&mut *self.book.write().await, results);
+        Ok(())
+    }
+
+    // the best fills for selling `amount` of `sell_asset` for `buy_asset`
+    pub async fn quote(
+        &self,
+        sell_asset: Bits256,
+        buy_asset: Bits256,
+        amount: u64,
+    ) -> Result<Quote, OrderBookError> {
+        self.refresh_pair(buy_asset, sell_asset).await?;
+        Ok(self.book.read().await.quote(sell_asset, buy_asset, amount))
+    }
+
+    // Read the fill state and maker balance of copies of book entries,
+    // this does not hold the book lock while waiting for the chain
+    async fn revalidate(
+        &self,
+        entries: Vec<BookOrder>,
+    ) -> Result<Vec<(Bits256, Revalidated)>, OrderBookError> {
+        let mut results = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let (_, error_code, filled_taker_amount) = self
+                .orders
+                .methods()
+                .validate_order(entry.order.clone(), entry.signature)
+                .simulate(Execution::StateReadOnly)
+                .await?
+                .value;
+            let result = match error_code {
+                0 => Revalidated::Valid {
+                    filled_taker_amount,
+                    maker_balance: self
+                        .maker_balance(entry.order.maker, entry.order.maker_asset)
+                        .await?,
+                },
+                PAUSED => Revalidated::Paused,
+                _ => Revalidated::Stale,
+            };
+            results.push((entry.order_hash, result));
+        }
+
+        Ok(results)
+    }
+
+    async fn block_height(&self) -> Result<u32, OrderBookError> {
+        let provider = self
+            .orders
+            .account()
+            .provider()
+            .cloned()
+            .ok_or_else(|| OrderBookError::Chain("no provider".to_string()))?;
+        Ok(provider.latest_block_height().await?)
+    }
+
+    async fn maker_balance(&self, maker: Bits256, asset: Bits256) -> Result<u64, OrderBookError> {
+        Ok(self
+            .orders
+            .methods()
+            .get_maker_balance(maker, asset)
+            .simulate(Execution::StateReadOnly)
+            .await?
+            .value)
+    }
+
+    async fn is_delegate(&self, maker: Bits256, signer: Bits256) -> Result<bool, OrderBookError> {
+        Ok(self
+            .orders
+            .methods()
+            .is_order_signer_delegate(maker, signer)
+            .simulate(Execution::StateReadOnly)
+            .await?
+            .value)
+    }
+}
+
+// Apply the results of `revalidate`, entries that left the book
+// in the meantime are skipped
+fn apply_revalidated(book: &mut OrderBook, results: Vec<(Bits256, Revalidated)>) {
+    for (order_hash, result) in results {
+        match result {
+            Revalidated::Valid {
+                filled_taker_amount,
+                maker_balance,
+            } => {
+                if let Some(entry) = book.get_mut(order_hash) {
+                    entry.filled_taker_amount = filled_taker_amount;
+                    entry.maker_balance = maker_balance;
+                }
+            }
+            Revalidated::Paused => {}
+            Revalidated::Stale => {
+                book.remove(order_hash);
+            }
+        }
+    }
+    book.prune();
+}
@@ -0,0 +1,236 @@
+use bindings::types::encode_order_params;
+use bindings::{BatchSwapStep, Order};
+use fuels::types::{AssetId, Bits256, Identity, B512};
+use serde::{Deserialize, Serialize};
+
+use crate::error::OrderBookError;
+
+// the dex id of 1delta orders in the executor
+pub const ONE_DELTA_ORDERS_DEX_ID: u64 = 100;
+
+////////////////////////////////////////////////////
+// JSON types, 32 and 64 byte values are 0x-prefixed hex strings
+////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderJson {
+    pub maker_asset: String,
+    pub taker_asset: String,
+    pub maker_amount: u64,
+    pub taker_amount: u64,
+    pub maker: String,
+    pub nonce: u64,
+    pub maker_traits: u64,
+    pub maker_receiver: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedOrderJson {
+    pub order: OrderJson,
+    pub signature: String,
+}
+
+// a request to sell `amount` of `sell_asset` for `buy_asset`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteRequest {
+    pub sell_asset: String,
+    pub buy_asset: String,
+    pub amount: u64,
+}
+
+// executor swap step without the receiver, which is chosen by the caller
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapStepJson {
+    pub dex_id: u64,
+    pub asset_in: String,
+    pub asset_out: String,
+    pub data: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteFillJson {
+    pub order_hash: String,
+    pub taker_fill_amount: u64,
+    pub maker_fill_amount: u64,
+    pub step: SwapStepJson,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuoteJson {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fills: Vec<QuoteFillJson>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookOrderJson {
+    pub order_hash: String,
+    pub order: OrderJson,
+    pub signature: String,
+    pub filled_taker_amount: u64,
+    pub maker_balance: u64,
+}
+
+////////////////////////////////////////////////////
+// book types
+////////////////////////////////////////////////////
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookOrder {
+    pub order: Order,
+    pub signature: B512,
+    pub order_hash: Bits256,
+    // the taker amount that is already filled
+    pub filled_taker_amount: u64,
+    // the maker's balance of the maker asset in the orders contract
+    pub maker_balance: u64,
+}
+
+impl BookOrder {
+    pub fn remaining_taker_amount(&self) -> u64 {
+        self.order
+            .taker_amount
+            .saturating_sub(self.filled_taker_amount)
+    }
+}
+
+// a single order fill of a quote
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuoteFill {
+    pub order: Order,
+    pub signature: B512,
+    pub order_hash: Bits256,
+    pub taker_fill_amount: u64,
+    pub maker_fill_amount: u64,
+}
+
+impl QuoteFill {
+    // the step data that the executor decodes via `to_order`
+    pub fn step_data(&self) -> Vec<u8> {
+        encode_order_params(&self.order, self.signature).0
+    }
+
+    pub fn to_swap_step(&self, receiver: Identity) -> BatchSwapStep {
+        BatchSwapStep {
+            dex_id: ONE_DELTA_ORDERS_DEX_ID,
+            asset_in: AssetId::from(self.order.taker_asset.0),
+            asset_out: AssetId::from(self.order.maker_asset.0),
+            receiver,
+            data: fuels::types::Bytes(self.step_data()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Quote {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fills: Vec<QuoteFill>,
+}
+
+////////////////////////////////////////////////////
+// conversions
+////////////////////////////////////////////////////
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn decode_hex<const N: usize>(value: &str) -> Result<[u8; N], OrderBookError> {
+    let bytes = hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| OrderBookError::InvalidInput(format!("{value}: {e}")))?;
+    bytes
+        .try_into()
+        .map_err(|_| OrderBookError::InvalidInput(format!("{value}: expected {N} bytes")))
+}
+
+pub fn parse_bits256(value: &str) -> Result<Bits256, OrderBookError> {
+    Ok(Bits256(decode_hex::<32>(value)?))
+}
+
+pub fn parse_b512(value: &str) -> Result<B512, OrderBookError> {
+    let bytes = decode_hex::<64>(value)?;
+    let mut first = [0u8; 32];
+    let mut second = [0u8; 32];
+    first.copy_from_slice(&bytes[..32]);
+    second.copy_from_slice(&bytes[32..]);
+    Ok(B512::from((Bits256(first), Bits256(second))))
+}
+
+pub fn b512_to_hex(value: &B512) -> String {
+    to_hex(&[value.bytes[0].0, value.bytes[1].0].concat())
+}
+
+impl TryFrom<&OrderJson> for Order {
+    type Error = OrderBookError;
+
+    fn try_from(order: &OrderJson) -> Result<Self, Self::Error> {
+        Ok(Order {
+            maker_asset: parse_bits256(&order.maker_asset)?,
+            taker_asset: parse_bits256(&order.taker_asset)?,
+            maker_amount: order.maker_amount,
+            taker_amount: order.taker_amount,
+            maker: parse_bits256(&order.maker)?,
+            nonce: order.nonce,
+            maker_traits: order.maker_traits,
+            maker_receiver: parse_bits256(&order.maker_receiver)?,
+        })
+    }
+}
+
+impl From<&Order> for OrderJson {
+    fn from(order: &Order) -> Self {
+        OrderJson {
+            maker_asset: to_hex(&order.maker_asset.0),
+            taker_asset: to_hex(&order.taker_asset.0),
+            maker_amount: order.maker_amount,
+            taker_amount: order.taker_amount,
+            maker: to_hex(&order.maker.0),
+            nonce: order.nonce,
+            maker_traits: order.maker_traits,
+            maker_receiver: to_hex(&order.maker_receiver.0),
+        }
+    }
+}
+
+impl SignedOrderJson {
+    pub fn parse(&self) -> Result<(Order, B512), OrderBookError> {
+        Ok((Order::try_from(&self.order)?, parse_b512(&self.signature)?))
+    }
+}
+
+impl From<&BookOrder> for BookOrderJson {
+    fn from(entry: &BookOrder) -> Self {
+        BookOrderJson {
+            order_hash: to_hex(&entry.order_hash.0),
+            order: OrderJson::from(&entry.order),
+            signature: b512_to_hex(&entry.signature),
+            filled_taker_amount: entry.filled_taker_amount,
+            maker_balance: entry.maker_balance,
+        }
+    }
+}
+
+impl From<&Quote> for QuoteJson {
+    fn from(quote: &Quote) -> Self {
+        QuoteJson {
+            amount_in: quote.amount_in,
+            amount_out: quote.amount_out,
+            fills: quote
+                .fills
+                .iter()
+                .map(|fill| QuoteFillJson {
+                    order_hash: to_hex(&fill.order_hash.0),
+                    taker_fill_amount: fill.taker_fill_amount,
+                    maker_fill_amount: fill.maker_fill_amount,
+                    step: SwapStepJson {
+                        dex_id: ONE_DELTA_ORDERS_DEX_ID,
+                        asset_in: to_hex(&fill.order.taker_asset.0),
+                        asset_out: to_hex(&fill.order.maker_asset.0),
+                        data: to_hex(&fill.step_data()),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use bindings::Order;
+use fuels::crypto::{Message, Signature};
+use fuels::types::{Bits256, B512};
+
+use crate::error::OrderBookError;
+
+// maker traits flags, see `order_utils`
+pub const CONTRACT_RECEIVER: u64 = 1 << 63;
+pub const NO_PARTIAL_FILL: u64 = 1 << 62;
+pub const CONTRACT_SIGNER: u64 = 1 << 61;
+pub const MAKER_HOOK: u64 = 1 << 60;
+pub const CONDITIONAL: u64 = 1 << 59;
+// the expiry is stored in the lowest 32 bits
+pub const EXPIRY_MASK: u64 = 0xFFFF_FFFF;
+
+// error code of `validate_order` while fills are paused,
+// such orders become fillable again once unpaused
+pub const PAUSED: u64 = 23;
+
+pub fn get_expiry(maker_traits: u64) -> u32 {
+    (maker_traits & EXPIRY_MASK) as u32
+}
+
+pub fn no_partial_fill(maker_traits: u64) -> bool {
+    maker_traits & NO_PARTIAL_FILL != 0
+}
+
+pub fn is_contract_signer(maker_traits: u64) -> bool {
+    maker_traits & CONTRACT_SIGNER != 0
+}
+
+// same rounding as `compute_maker_fill_amount` in `order_utils`
+pub fn compute_maker_fill_amount(
+    taker_fill_amount: u64,
+    maker_amount: u64,
+    taker_amount: u64,
+) -> u64 {
+    (taker_fill_amount as u128 * maker_amount as u128 / taker_amount as u128) as u64
+}
+
+// recover the address that signed the order hash
+pub fn recover_signer(order_hash: Bits256, signature: &B512) -> Result<Bits256, OrderBookError> {
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&signature.bytes[0].0);
+    bytes[32..].copy_from_slice(&signature.bytes[1].0);
+
+    let public_key = Signature::from_bytes(bytes)
+        .recover(&Message::from_bytes(order_hash.0))
+        .map_err(|_| OrderBookError::InvalidSignature)?;
+
+    Ok(Bits256(*public_key.hash()))
+}
+
+// Checks that do not need chain state aside of the block height.
+// The signer is checked separately as it can be a registered delegate
+pub fn validate_order_offline(order: &Order, block_height: u32) -> Result<(), OrderBookError> {
+    if order.maker_amount == 0 || order.taker_amount == 0 {
+        return Err(OrderBookError::InvalidInput(
+            "zero order amount".to_string(),
+        ));
+    }
+
+    if order.maker_asset == order.taker_asset {
+        return Err(OrderBookError::InvalidInput(
+            "maker and taker asset are equal".to_string(),
+        ));
+    }
+
+    // conditional orders can only be filled via `fill_conditional`
+    // which the executor does not support
+    if order.maker_traits & CONDITIONAL != 0 {
+        return Err(OrderBookError::UnsupportedTraits(
+            "conditional orders cannot be routed",
+        ));
+    }
+
+    if get_expiry(order.maker_traits) < block_height {
+        return Err(OrderBookError::Expired);
+    }
+
+    Ok(())
+}
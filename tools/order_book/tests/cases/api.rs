@@ -0,0 +1,94 @@
+use crate::utils::{create_order, deposit_for_maker, setup};
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use http_body_util::BodyExt;
+use order_book::api::{router, ErrorResponse, SubmitResponse};
+use order_book::types::{
+    b512_to_hex, to_hex, BookOrderJson, OrderJson, QuoteJson, QuoteRequest, SignedOrderJson,
+    ONE_DELTA_ORDERS_DEX_ID,
+};
+use serde::de::DeserializeOwned;
+use test_harness::interface::orders::sign_order;
+use test_harness::types::encode_order_params;
+use tower::ServiceExt;
+
+async fn send<T: DeserializeOwned>(app: axum::Router, request: Request<Body>) -> (StatusCode, T) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn post_json(uri: &str, body: &impl serde::Serialize) -> Request<Body> {
+    Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(serde_json::to_vec(body).unwrap()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn can_submit_and_quote_via_http() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+    let app = router(service);
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+
+    let signed_order = SignedOrderJson {
+        order: OrderJson::from(&order),
+        signature: b512_to_hex(&signature),
+    };
+    let (status, submitted): (_, SubmitResponse) =
+        send(app.clone(), post_json("/orders", &signed_order)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // resubmitting conflicts
+    let (status, error): (_, ErrorResponse) =
+        send(app.clone(), post_json("/orders", &signed_order)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(error.error, "order already in book");
+
+    let uri = format!(
+        "/orders?maker_asset={}&taker_asset={}",
+        to_hex(&*token_0_id),
+        to_hex(&*token_1_id)
+    );
+    let (status, book): (_, Vec<BookOrderJson>) =
+        send(app.clone(), Request::get(uri).body(Body::empty()).unwrap()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(book.len(), 1);
+    assert_eq!(book[0].order_hash, submitted.order_hash);
+    assert_eq!(book[0].maker_balance, 10_000);
+
+    let request = QuoteRequest {
+        sell_asset: to_hex(&*token_1_id),
+        buy_asset: to_hex(&*token_0_id),
+        amount: 5_000,
+    };
+    let (status, quote): (_, QuoteJson) = send(app, post_json("/quote", &request)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(quote.amount_in, 5_000);
+    assert_eq!(quote.amount_out, 2_500);
+    assert_eq!(quote.fills[0].order_hash, submitted.order_hash);
+    assert_eq!(quote.fills[0].step.dex_id, ONE_DELTA_ORDERS_DEX_ID);
+    assert_eq!(
+        quote.fills[0].step.data,
+        to_hex(&encode_order_params(&order, signature).0)
+    );
+}
+
+#[tokio::test]
+async fn rejects_malformed_order_via_http() {
+    let (service, _, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let app = router(service);
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signed_order = SignedOrderJson {
+        order: OrderJson::from(&order),
+        signature: "0x1234".to_string(),
+    };
+    let (status, _): (_, ErrorResponse) = send(app, post_json("/orders", &signed_order)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,88 @@
+use crate::utils::{create_order, deposit_for_maker, setup};
+use order_book::error::OrderBookError;
+use order_book::validation::CONDITIONAL;
+use test_harness::interface::orders::sign_order;
+
+// error codes of `validate_order`
+const INVALID_NONCE: u64 = 2;
+
+#[tokio::test]
+async fn rejects_order_signed_by_other_wallet() {
+    let (service, orders, (maker_0, maker_1, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker_1, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+
+    let result = service.submit(order, signature).await;
+    assert_eq!(result, Err(OrderBookError::InvalidSignature));
+}
+
+#[tokio::test]
+async fn rejects_expired_order() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // the deployments already produced blocks beyond height zero
+    let mut order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    order.maker_traits = 0;
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+
+    let result = service.submit(order, signature).await;
+    assert_eq!(result, Err(OrderBookError::Expired));
+}
+
+#[tokio::test]
+async fn rejects_conditional_order() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let mut order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    order.maker_traits |= CONDITIONAL;
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+
+    let result = service.submit(order, signature).await;
+    assert!(matches!(result, Err(OrderBookError::UnsupportedTraits(_))));
+}
+
+#[tokio::test]
+async fn rejects_unfunded_order() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+
+    let result = service.submit(order, signature).await;
+    assert_eq!(result, Err(OrderBookError::NoMakerBalance));
+}
+
+#[tokio::test]
+async fn rejects_invalid_nonce() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 0);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+
+    let result = service.submit(order, signature).await;
+    assert_eq!(result, Err(OrderBookError::Contract(INVALID_NONCE)));
+}
+
+#[tokio::test]
+async fn rejects_duplicate_order() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+
+    service.submit(order.clone(), signature).await.unwrap();
+    let result = service.submit(order, signature).await;
+    assert_eq!(result, Err(OrderBookError::DuplicateOrder));
+}
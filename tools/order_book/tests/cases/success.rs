@@ -0,0 +1,215 @@
+use crate::utils::{create_order, deposit_for_maker, fill, setup};
+use fuels::types::{Bits256, Identity};
+use order_book::types::ONE_DELTA_ORDERS_DEX_ID;
+use test_harness::interface::orders::sign_order;
+use test_harness::types::encode_order_params;
+
+#[tokio::test]
+async fn quotes_best_orders_first() {
+    let (service, orders, (maker_0, maker_1, taker), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // maker_0 sells at 0.5, maker_1 at 0.666
+    let order_0 = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let order_1 = create_order(&maker_1, token_0_id, token_1_id, 10_000, 15_000, 1);
+    let signature_0 = sign_order(&maker_0, &order_0, orders_id).await;
+    let signature_1 = sign_order(&maker_1, &order_1, orders_id).await;
+
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+    deposit_for_maker(&orders, &maker_1, token_0_id, 10_000).await;
+
+    service.submit(order_0.clone(), signature_0).await.unwrap();
+    service.submit(order_1.clone(), signature_1).await.unwrap();
+
+    let quote = service
+        .quote(Bits256(*token_1_id), Bits256(*token_0_id), 30_000)
+        .await
+        .unwrap();
+
+    assert_eq!(quote.amount_in, 30_000);
+    assert_eq!(quote.amount_out, 17_500);
+    assert_eq!(quote.fills.len(), 2);
+    assert_eq!(quote.fills[0].order, order_1);
+    assert_eq!(quote.fills[0].taker_fill_amount, 15_000);
+    assert_eq!(quote.fills[0].maker_fill_amount, 10_000);
+    assert_eq!(quote.fills[1].order, order_0);
+    assert_eq!(quote.fills[1].taker_fill_amount, 15_000);
+    assert_eq!(quote.fills[1].maker_fill_amount, 7_500);
+
+    // the step data is what the executor decodes
+    let receiver = Identity::Address(taker.address().into());
+    let step = quote.fills[0].to_swap_step(receiver);
+    assert_eq!(step.dex_id, ONE_DELTA_ORDERS_DEX_ID);
+    assert_eq!(step.asset_in, token_1_id);
+    assert_eq!(step.asset_out, token_0_id);
+    assert_eq!(step.data, encode_order_params(&order_1, signature_1));
+}
+
+#[tokio::test]
+async fn quoted_fills_match_chain_fills() {
+    let (service, orders, (maker_0, _, taker), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 30_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+    service.submit(order.clone(), signature).await.unwrap();
+
+    let quote = service
+        .quote(Bits256(*token_1_id), Bits256(*token_0_id), 10_001)
+        .await
+        .unwrap();
+    let quoted = &quote.fills[0];
+
+    let (taker_fill_amount, maker_fill_amount) =
+        fill(&orders, &taker, &order, signature, quoted.taker_fill_amount).await;
+
+    assert_eq!(taker_fill_amount, quoted.taker_fill_amount);
+    assert_eq!(maker_fill_amount, quoted.maker_fill_amount);
+}
+
+#[tokio::test]
+async fn refreshes_partially_filled_orders() {
+    let (service, orders, (maker_0, _, taker), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+    let order_hash = service.submit(order.clone(), signature).await.unwrap();
+
+    fill(&orders, &taker, &order, signature, 12_000).await;
+
+    let quote = service
+        .quote(Bits256(*token_1_id), Bits256(*token_0_id), 20_000)
+        .await
+        .unwrap();
+    assert_eq!(quote.amount_in, 8_000);
+    assert_eq!(quote.amount_out, 4_000);
+
+    // fully filled orders leave the book
+    fill(&orders, &taker, &order, signature, 8_000).await;
+    service.refresh().await.unwrap();
+    assert!(service.book().read().await.get(order_hash).is_none());
+}
+
+#[tokio::test]
+async fn shares_maker_balance_across_orders() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // both orders are backed by the same 10k deposit
+    let order_0 = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let order_1 = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 2);
+    let signature_0 = sign_order(&maker_0, &order_0, orders_id).await;
+    let signature_1 = sign_order(&maker_0, &order_1, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+
+    service.submit(order_0, signature_0).await.unwrap();
+    service.submit(order_1, signature_1).await.unwrap();
+
+    let quote = service
+        .quote(Bits256(*token_1_id), Bits256(*token_0_id), 40_000)
+        .await
+        .unwrap();
+    assert_eq!(quote.fills.len(), 1);
+    assert_eq!(quote.amount_in, 20_000);
+    assert_eq!(quote.amount_out, 10_000);
+}
+
+#[tokio::test]
+async fn drops_orders_after_nonce_invalidation() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+    service.submit(order, signature).await.unwrap();
+
+    orders
+        .clone()
+        .with_account(maker_0.clone())
+        .methods()
+        .invalidate_nonce(Bits256(*token_0_id), Bits256(*token_1_id), 1)
+        .call()
+        .await
+        .unwrap();
+
+    let quote = service
+        .quote(Bits256(*token_1_id), Bits256(*token_0_id), 20_000)
+        .await
+        .unwrap();
+    assert!(quote.fills.is_empty());
+    assert!(service.book().read().await.is_empty());
+}
+
+#[tokio::test]
+async fn quotes_refresh_only_the_quoted_pair() {
+    let (service, orders, (maker_0, _, taker), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    // maker_0 sells token_0, the taker sells token_1 in the other direction
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let reverse_order = create_order(&taker, token_1_id, token_0_id, 20_000, 10_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    let reverse_signature = sign_order(&taker, &reverse_order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+    deposit_for_maker(&orders, &taker, token_1_id, 20_000).await;
+
+    service.submit(order, signature).await.unwrap();
+    let reverse_hash = service
+        .submit(reverse_order, reverse_signature)
+        .await
+        .unwrap();
+
+    orders
+        .clone()
+        .with_account(taker.clone())
+        .methods()
+        .invalidate_nonce(Bits256(*token_1_id), Bits256(*token_0_id), 1)
+        .call()
+        .await
+        .unwrap();
+
+    // the reverse pair is not re-validated when quoting
+    service
+        .quote(Bits256(*token_1_id), Bits256(*token_0_id), 20_000)
+        .await
+        .unwrap();
+    assert!(service.book().read().await.get(reverse_hash).is_some());
+
+    service.refresh().await.unwrap();
+    assert!(service.book().read().await.get(reverse_hash).is_none());
+}
+
+#[tokio::test]
+async fn keeps_orders_while_paused() {
+    let (service, orders, (maker_0, _, _), (token_0_id, token_1_id)) = setup().await;
+    let orders_id = orders.contract_id().into();
+
+    let order = create_order(&maker_0, token_0_id, token_1_id, 10_000, 20_000, 1);
+    let signature = sign_order(&maker_0, &order, orders_id).await;
+    deposit_for_maker(&orders, &maker_0, token_0_id, 10_000).await;
+    let order_hash = service.submit(order, signature).await.unwrap();
+
+    // maker_0 deployed the orders and becomes the guardian
+    orders
+        .methods()
+        .initialize(Identity::Address(maker_0.address().into()))
+        .call()
+        .await
+        .unwrap();
+    orders.methods().set_paused(true).call().await.unwrap();
+
+    service.refresh().await.unwrap();
+    assert!(service.book().read().await.get(order_hash).is_some());
+
+    orders.methods().set_paused(false).call().await.unwrap();
+
+    let quote = service
+        .quote(Bits256(*token_1_id), Bits256(*token_0_id), 20_000)
+        .await
+        .unwrap();
+    assert_eq!(quote.amount_out, 10_000);
+}
@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{
+    launch_custom_provider_and_get_wallets, CallParameters, VariableOutputPolicy, WalletsConfig,
+};
+use fuels::types::{AssetId, Bits256, Identity, B512};
+use order_book::service::OrderBookService;
+use test_harness::interface::mock::{add_token, deploy_mock_token_contract, mint_tokens};
+use test_harness::interface::orders::{deploy_one_delta_orders_contract, deposit};
+use test_harness::interface::{MockToken, OneDeltaOrders, Order};
+
+pub const MAX_EXPIRY: u64 = 4_294_967_295;
+pub const MINT_AMOUNT: u64 = 1_000_000_000;
+
+////////////////////////////////////////////////////
+// Create 2 tokens and 3 wallets
+// the makers hold token_0, the taker holds token_1
+// the service reads the chain via the taker wallet
+////////////////////////////////////////////////////
+pub async fn setup() -> (
+    Arc<OrderBookService>,
+    OneDeltaOrders<WalletUnlocked>,
+    (WalletUnlocked, WalletUnlocked, WalletUnlocked),
+    (AssetId, AssetId),
+) {
+    let wallets = launch_custom_provider_and_get_wallets(
+        WalletsConfig::new(Some(3), Some(1), Some(1_000_000_000)),
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+    let (maker_0, maker_1, taker) = (wallets[0].clone(), wallets[1].clone(), wallets[2].clone());
+
+    let (token_contract_id, token_contract) = deploy_mock_token_contract(&maker_0).await;
+
+    let token_0_id = add_token(&token_contract, "TOKEN_A".to_string(), "TKA".to_string(), 9)
+        .await
+        .value;
+    let token_1_id = add_token(&token_contract, "TOKEN_B".to_string(), "TKB".to_string(), 9)
+        .await
+        .value;
+
+    for wallet in [&maker_0, &maker_1] {
+        mint_tokens(
+            &MockToken::new(token_contract_id, wallet.clone()),
+            token_0_id,
+            MINT_AMOUNT,
+        )
+        .await;
+    }
+    mint_tokens(
+        &MockToken::new(token_contract_id, taker.clone()),
+        token_1_id,
+        MINT_AMOUNT,
+    )
+    .await;
+
+    let (_, orders) = deploy_one_delta_orders_contract(&maker_0).await;
+    let service = Arc::new(OrderBookService::new(
+        orders.clone().with_account(taker.clone()),
+    ));
+
+    (
+        service,
+        orders,
+        (maker_0, maker_1, taker),
+        (token_0_id, token_1_id),
+    )
+}
+
+pub fn create_order(
+    maker: &WalletUnlocked,
+    maker_asset: AssetId,
+    taker_asset: AssetId,
+    maker_amount: u64,
+    taker_amount: u64,
+    nonce: u64,
+) -> Order {
+    Order {
+        maker_asset: Bits256(*maker_asset),
+        taker_asset: Bits256(*taker_asset),
+        maker_amount,
+        taker_amount,
+        maker: Bits256(*maker.address().hash()),
+        nonce,
+        maker_traits: MAX_EXPIRY,
+        maker_receiver: Bits256::zeroed(),
+    }
+}
+
+// deposit an amount of the maker asset of an order for its maker
+pub async fn deposit_for_maker(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    maker: &WalletUnlocked,
+    asset_id: AssetId,
+    amount: u64,
+) {
+    deposit(
+        &orders.clone().with_account(maker.clone()),
+        asset_id,
+        amount,
+        Identity::Address(maker.address().into()),
+    )
+    .await;
+}
+
+pub async fn fill(
+    orders: &OneDeltaOrders<WalletUnlocked>,
+    taker: &WalletUnlocked,
+    order: &Order,
+    signature: B512,
+    taker_fill_amount: u64,
+) -> (u64, u64) {
+    orders
+        .clone()
+        .with_account(taker.clone())
+        .methods()
+        .fill(
+            order.clone(),
+            signature,
+            taker_fill_amount,
+            Identity::Address(taker.address().into()),
+            None,
+        )
+        .call_params(
+            CallParameters::default()
+                .with_amount(taker_fill_amount)
+                .with_asset_id(AssetId::from(order.taker_asset.0)),
+        )
+        .unwrap()
+        .with_variable_output_policy(VariableOutputPolicy::EstimateMinimum)
+        .call()
+        .await
+        .unwrap()
+        .value
+}
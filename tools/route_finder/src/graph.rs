@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use bindings::types::{encode_mira_params, encode_mira_params_with_dex_address, PoolId};
+use fuels::types::{AssetId, Bits256, Bytes, ContractId, Identity};
+
+use crate::math::{add_fee, get_amount_in, get_amount_out, subtract_fee};
+
+// the dex id of Mira v1 (and forks) in the executor
+pub const MIRA_V1_DEX_ID: u64 = 0;
+
+// A Mira v1 pool of a specific AMM deployment, stable and volatile
+// pools of the same assets are separate pools
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MiraPool {
+    // the AMM contract, either Mira v1 or a fork of it
+    pub amm: ContractId,
+    pub pool_id: PoolId,
+    pub reserve_0: u64,
+    pub reserve_1: u64,
+    pub decimals_0: u8,
+    pub decimals_1: u8,
+    // the total (lp + protocol) fee in basis points
+    pub fee: u64,
+}
+
+impl MiraPool {
+    pub fn is_stable(&self) -> bool {
+        self.pool_id.2
+    }
+
+    pub fn contains(&self, asset: AssetId) -> bool {
+        self.pool_id.0 == asset || self.pool_id.1 == asset
+    }
+
+    // the other asset of the pool
+    pub fn other(&self, asset: AssetId) -> AssetId {
+        if self.pool_id.0 == asset {
+            self.pool_id.1
+        } else {
+            self.pool_id.0
+        }
+    }
+
+    // (reserve_in, reserve_out, decimals_in, decimals_out)
+    fn oriented(&self, asset_in: AssetId) -> (u64, u64, u8, u8) {
+        if self.pool_id.0 == asset_in {
+            (
+                self.reserve_0,
+                self.reserve_1,
+                self.decimals_0,
+                self.decimals_1,
+            )
+        } else {
+            (
+                self.reserve_1,
+                self.reserve_0,
+                self.decimals_1,
+                self.decimals_0,
+            )
+        }
+    }
+
+    // same as `get_amounts_out` in the Mira math
+    pub fn amount_out(&self, asset_in: AssetId, amount_in: u64) -> Option<u64> {
+        let (reserve_in, reserve_out, decimals_in, decimals_out) = self.oriented(asset_in);
+        let amount_out = get_amount_out(
+            self.is_stable(),
+            reserve_in,
+            reserve_out,
+            decimals_in,
+            decimals_out,
+            subtract_fee(amount_in, self.fee),
+        )?;
+        // the swap fails if the pool is drained
+        (amount_out < reserve_out).then_some(amount_out)
+    }
+
+    // same as `get_amounts_in` in the Mira math
+    pub fn amount_in(&self, asset_out: AssetId, amount_out: u64) -> Option<u64> {
+        let (reserve_in, reserve_out, decimals_in, decimals_out) =
+            self.oriented(self.other(asset_out));
+        let amount_in = get_amount_in(
+            self.is_stable(),
+            reserve_in,
+            reserve_out,
+            decimals_in,
+            decimals_out,
+            amount_out,
+        )?;
+        add_fee(amount_in, self.fee)
+    }
+
+    // swap with the new reserves, used for quoting consecutive trades on the same pool.
+    // The full input is added to the reserves, which is exact without protocol fees
+    pub fn apply_swap(&mut self, asset_in: AssetId, amount_in: u64, amount_out: u64) {
+        if self.pool_id.0 == asset_in {
+            self.reserve_0 += amount_in;
+            self.reserve_1 -= amount_out;
+        } else {
+            self.reserve_1 += amount_in;
+            self.reserve_0 -= amount_out;
+        }
+    }
+
+    // the step data expected by the executor, forked deployments
+    // are encoded with the AMM address
+    pub fn step_data(&self, mira_amm: ContractId) -> Bytes {
+        if self.amm == mira_amm {
+            encode_mira_params(self.fee, self.is_stable())
+        } else {
+            encode_mira_params_with_dex_address(self.fee, self.is_stable(), Bits256(*self.amm))
+        }
+    }
+
+    // the receiver of the input funds of a swap on this pool
+    pub fn input_receiver(&self) -> Identity {
+        Identity::ContractId(self.amm)
+    }
+}
+
+// Undirected multigraph of assets with pools as edges
+#[derive(Debug, Default, Clone)]
+pub struct PoolGraph {
+    pools: Vec<MiraPool>,
+    // asset -> indexes of the pools that contain the asset
+    edges: HashMap<AssetId, Vec<usize>>,
+}
+
+impl PoolGraph {
+    pub fn new(pools: Vec<MiraPool>) -> Self {
+        let mut graph = Self::default();
+        for pool in pools {
+            graph.add_pool(pool);
+        }
+        graph
+    }
+
+    // adds a pool, pools without liquidity are skipped
+    pub fn add_pool(&mut self, pool: MiraPool) {
+        if pool.reserve_0 == 0 || pool.reserve_1 == 0 {
+            return;
+        }
+        let index = self.pools.len();
+        self.edges.entry(pool.pool_id.0).or_default().push(index);
+        self.edges.entry(pool.pool_id.1).or_default().push(index);
+        self.pools.push(pool);
+    }
+
+    pub fn pools(&self) -> &[MiraPool] {
+        &self.pools
+    }
+
+    pub fn pool(&self, index: usize) -> &MiraPool {
+        &self.pools[index]
+    }
+
+    pub fn pool_mut(&mut self, index: usize) -> &mut MiraPool {
+        &mut self.pools[index]
+    }
+
+    // the indexes of all pools that contain an asset
+    pub fn pools_of(&self, asset: AssetId) -> &[usize] {
+        self.edges
+            .get(&asset)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,5 @@
+pub mod graph;
+pub mod loader;
+pub mod math;
+pub mod route;
+pub mod split;
@@ -0,0 +1,78 @@
+use bindings::types::PoolId;
+use bindings::{CreatePoolEvent, MiraAMM};
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::programs::calls::Execution;
+use fuels::tx::Receipt;
+use fuels::types::errors::Result;
+
+use crate::graph::{MiraPool, PoolGraph};
+
+// the pool ids of all `CreatePoolEvent`s in the receipts, e.g. of `create_pool` calls
+pub fn pool_ids_from_receipts(
+    amm: &MiraAMM<WalletUnlocked>,
+    receipts: &[Receipt],
+) -> Result<Vec<PoolId>> {
+    Ok(amm
+        .log_decoder()
+        .decode_logs_with_type::<CreatePoolEvent>(receipts)?
+        .into_iter()
+        .map(|event| event.pool_id)
+        .collect())
+}
+
+// the total fees charged by an AMM as (volatile, stable) in basis points
+pub async fn get_total_fees(amm: &MiraAMM<WalletUnlocked>) -> Result<(u64, u64)> {
+    let (lp_fee_volatile, lp_fee_stable, protocol_fee_volatile, protocol_fee_stable) = amm
+        .methods()
+        .fees()
+        .simulate(Execution::StateReadOnly)
+        .await?
+        .value;
+    Ok((
+        lp_fee_volatile + protocol_fee_volatile,
+        lp_fee_stable + protocol_fee_stable,
+    ))
+}
+
+// load the current state of the pools of an AMM, unknown pools are skipped
+pub async fn load_pools(
+    amm: &MiraAMM<WalletUnlocked>,
+    pool_ids: &[PoolId],
+) -> Result<Vec<MiraPool>> {
+    let (volatile_fee, stable_fee) = get_total_fees(amm).await?;
+
+    let mut pools = Vec::with_capacity(pool_ids.len());
+    for pool_id in pool_ids {
+        let metadata = amm
+            .methods()
+            .pool_metadata(*pool_id)
+            .simulate(Execution::StateReadOnly)
+            .await?
+            .value;
+        if let Some(metadata) = metadata {
+            pools.push(MiraPool {
+                amm: amm.contract_id().into(),
+                pool_id: *pool_id,
+                reserve_0: metadata.reserve_0,
+                reserve_1: metadata.reserve_1,
+                decimals_0: metadata.decimals_0,
+                decimals_1: metadata.decimals_1,
+                fee: if pool_id.2 { stable_fee } else { volatile_fee },
+            });
+        }
+    }
+    Ok(pools)
+}
+
+// build the graph over multiple AMM deployments (Mira v1 and forks)
+pub async fn load_graph(
+    deployments: &[(MiraAMM<WalletUnlocked>, Vec<PoolId>)],
+) -> Result<PoolGraph> {
+    let mut graph = PoolGraph::default();
+    for (amm, pool_ids) in deployments {
+        for pool in load_pools(amm, pool_ids).await? {
+            graph.add_pool(pool);
+        }
+    }
+    Ok(graph)
+}
@@ -0,0 +1,159 @@
+// Port of the Mira v1 pool math (`mira_v1/math/src/pool_math.sw`)
+// so that routes can be quoted without dry-running scripts.
+use fuels::types::U256;
+
+const BASIS_POINTS_DENOMINATOR: u64 = 10_000;
+
+fn one_e_18() -> U256 {
+    U256::exp10(18)
+}
+
+pub fn pow_decimals(decimals: u8) -> U256 {
+    U256::exp10(decimals as usize)
+}
+
+fn adjust(amount: U256, pow_decimals: U256) -> U256 {
+    amount * one_e_18() / pow_decimals
+}
+
+fn rounding_up_division(nominator: U256, denominator: U256) -> U256 {
+    let result = nominator / denominator;
+    if nominator % denominator == U256::zero() {
+        result
+    } else {
+        result + 1
+    }
+}
+
+fn k(x: U256, y: U256, pow_decimals_x: U256, pow_decimals_y: U256) -> U256 {
+    let x = x * one_e_18() / pow_decimals_x;
+    let y = y * one_e_18() / pow_decimals_y;
+    let a = (x * y) / one_e_18();
+    let b = (x * x) / one_e_18() + (y * y) / one_e_18();
+    a * b
+}
+
+fn f(x_0: U256, y: U256) -> U256 {
+    x_0 * (y * y / one_e_18() * y / one_e_18()) + (x_0 * x_0 / one_e_18() * x_0 / one_e_18()) * y
+}
+
+fn d(x_0: U256, y: U256) -> U256 {
+    U256::from(3) * x_0 * (y * y / one_e_18()) / one_e_18()
+        + (x_0 * x_0 / one_e_18() * x_0 / one_e_18())
+}
+
+fn get_y(x_0: U256, xy: U256, y: U256) -> U256 {
+    let mut y = y;
+    for _ in 0..255 {
+        let y_prev = y;
+        let k = f(x_0, y);
+        if k < xy {
+            y = y + (xy - k) / d(x_0, y);
+        } else {
+            y = y - (k - xy) / d(x_0, y);
+        }
+        if y > y_prev {
+            if y - y_prev <= U256::one() {
+                return y;
+            }
+        } else if y_prev - y <= U256::one() {
+            return y;
+        }
+    }
+    y
+}
+
+// the output amount after the fee was deducted from the input amount
+pub fn get_amount_out(
+    is_stable: bool,
+    reserve_in: u64,
+    reserve_out: u64,
+    decimals_in: u8,
+    decimals_out: u8,
+    input_amount: u64,
+) -> Option<u64> {
+    if reserve_in == 0 || reserve_out == 0 {
+        return None;
+    }
+    let (reserve_in, reserve_out, input_amount) = (
+        U256::from(reserve_in),
+        U256::from(reserve_out),
+        U256::from(input_amount),
+    );
+    let amount_out = if is_stable {
+        let (pow_decimals_in, pow_decimals_out) =
+            (pow_decimals(decimals_in), pow_decimals(decimals_out));
+        let xy = k(reserve_in, reserve_out, pow_decimals_in, pow_decimals_out);
+
+        let amount_in_adjusted = adjust(input_amount, pow_decimals_in);
+        let reserve_in_adjusted = adjust(reserve_in, pow_decimals_in);
+        let reserve_out_adjusted = adjust(reserve_out, pow_decimals_out);
+        let y = reserve_out_adjusted.checked_sub(get_y(
+            amount_in_adjusted + reserve_in_adjusted,
+            xy,
+            reserve_out_adjusted,
+        ))?;
+        y * pow_decimals_out / one_e_18()
+    } else {
+        input_amount * reserve_out / (reserve_in + input_amount)
+    };
+    u64::try_from(amount_out).ok()
+}
+
+// the input amount before the fee is added
+pub fn get_amount_in(
+    is_stable: bool,
+    reserve_in: u64,
+    reserve_out: u64,
+    decimals_in: u8,
+    decimals_out: u8,
+    output_amount: u64,
+) -> Option<u64> {
+    // the pool cannot be drained
+    if reserve_in == 0 || output_amount >= reserve_out {
+        return None;
+    }
+    let (reserve_in, reserve_out, output_amount) = (
+        U256::from(reserve_in),
+        U256::from(reserve_out),
+        U256::from(output_amount),
+    );
+    let amount_in = if is_stable {
+        let (pow_decimals_in, pow_decimals_out) =
+            (pow_decimals(decimals_in), pow_decimals(decimals_out));
+        let xy = k(reserve_in, reserve_out, pow_decimals_in, pow_decimals_out);
+
+        let amount_out_adjusted = adjust(output_amount, pow_decimals_out);
+        let reserve_in_adjusted = adjust(reserve_in, pow_decimals_in);
+        let reserve_out_adjusted = adjust(reserve_out, pow_decimals_out);
+        let y = get_y(
+            reserve_out_adjusted - amount_out_adjusted,
+            xy,
+            reserve_in_adjusted,
+        )
+        .checked_sub(reserve_in_adjusted)?;
+        rounding_up_division(y * pow_decimals_in, one_e_18())
+    } else {
+        rounding_up_division(output_amount * reserve_in, reserve_out - output_amount)
+    };
+    u64::try_from(amount_in).ok()
+}
+
+fn calculate_fee_to_subtract(amount: u64, fee: u64) -> u64 {
+    let nominator = U256::from(amount) * U256::from(fee);
+    rounding_up_division(nominator, U256::from(BASIS_POINTS_DENOMINATOR)).as_u64()
+}
+
+fn calculate_fee_to_add(amount: u64, fee: u64) -> u64 {
+    let nominator = U256::from(amount) * U256::from(fee);
+    let denominator = U256::from(BASIS_POINTS_DENOMINATOR - fee);
+    rounding_up_division(nominator, denominator).as_u64()
+}
+
+pub fn subtract_fee(amount: u64, fee: u64) -> u64 {
+    amount - calculate_fee_to_subtract(amount, fee)
+}
+
+pub fn add_fee(amount: u64, fee: u64) -> Option<u64> {
+    amount.checked_add(calculate_fee_to_add(amount, fee))
+}
@@ -0,0 +1,247 @@
+use bindings::BatchSwapStep;
+use fuels::types::{AssetId, ContractId, Identity};
+
+use crate::graph::{PoolGraph, MIRA_V1_DEX_ID};
+
+// a path entry of the batch swap scripts:
+// (amount, limit, transfer_in, steps), where for exact in the amount is the input
+// and the limit the minimum output, for exact out the other way around
+pub type SwapPath = (u64, u64, bool, Vec<BatchSwapStep>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeType {
+    ExactIn,
+    ExactOut,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hop {
+    // the index of the pool in the graph
+    pub pool: usize,
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Route {
+    pub hops: Vec<Hop>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotedRoute {
+    pub route: Route,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+impl Route {
+    pub fn asset_in(&self) -> AssetId {
+        self.hops[0].asset_in
+    }
+
+    pub fn asset_out(&self) -> AssetId {
+        self.hops[self.hops.len() - 1].asset_out
+    }
+
+    pub fn quote_exact_in(&self, graph: &PoolGraph, amount_in: u64) -> Option<u64> {
+        self.hops.iter().try_fold(amount_in, |amount, hop| {
+            graph.pool(hop.pool).amount_out(hop.asset_in, amount)
+        })
+    }
+
+    pub fn quote_exact_out(&self, graph: &PoolGraph, amount_out: u64) -> Option<u64> {
+        self.hops.iter().rev().try_fold(amount_out, |amount, hop| {
+            graph.pool(hop.pool).amount_in(hop.asset_out, amount)
+        })
+    }
+
+    // quote and apply the swaps to the graph, used to quote
+    // multiple paths that are executed one after another
+    pub fn simulate_exact_in(&self, graph: &mut PoolGraph, amount_in: u64) -> Option<u64> {
+        let mut amount = amount_in;
+        for hop in &self.hops {
+            let pool = graph.pool_mut(hop.pool);
+            let amount_out = pool.amount_out(hop.asset_in, amount)?;
+            pool.apply_swap(hop.asset_in, amount, amount_out);
+            amount = amount_out;
+        }
+        Some(amount)
+    }
+
+    // like the exact out script, all amounts are computed before swapping
+    pub fn simulate_exact_out(&self, graph: &mut PoolGraph, amount_out: u64) -> Option<u64> {
+        let mut amounts = vec![amount_out];
+        for hop in self.hops.iter().rev() {
+            let amount = graph
+                .pool(hop.pool)
+                .amount_in(hop.asset_out, amounts[amounts.len() - 1])?;
+            amounts.push(amount);
+        }
+        // amounts are in reverse swap order
+        for (hop, amounts) in self.hops.iter().rev().zip(amounts.windows(2)) {
+            graph
+                .pool_mut(hop.pool)
+                .apply_swap(hop.asset_in, amounts[1], amounts[0]);
+        }
+        amounts.last().copied()
+    }
+
+    // The steps in swap order, each step pays out to the input receiver of the next
+    // step and the last one to the receiver.
+    // `mira_amm` is the Mira deployment configured in the script, all other AMMs
+    // are encoded as forks
+    pub fn to_swap_steps(
+        &self,
+        graph: &PoolGraph,
+        receiver: Identity,
+        mira_amm: ContractId,
+    ) -> Vec<BatchSwapStep> {
+        self.hops
+            .iter()
+            .enumerate()
+            .map(|(i, hop)| {
+                let pool = graph.pool(hop.pool);
+                let step_receiver = match self.hops.get(i + 1) {
+                    Some(next) => graph.pool(next.pool).input_receiver(),
+                    None => receiver,
+                };
+                BatchSwapStep {
+                    dex_id: MIRA_V1_DEX_ID,
+                    asset_in: hop.asset_in,
+                    asset_out: hop.asset_out,
+                    receiver: step_receiver,
+                    data: pool.step_data(mira_amm),
+                }
+            })
+            .collect()
+    }
+
+    // path for `batch_swap_exact_in_script`, the funds are transferred in
+    pub fn to_exact_in_path(
+        &self,
+        graph: &PoolGraph,
+        amount_in: u64,
+        minimum_out: u64,
+        receiver: Identity,
+        mira_amm: ContractId,
+    ) -> SwapPath {
+        (
+            amount_in,
+            minimum_out,
+            true,
+            self.to_swap_steps(graph, receiver, mira_amm),
+        )
+    }
+
+    // path for `batch_swap_exact_out_script`, which expects the steps
+    // in reverse order, starting with the step that pays the receiver
+    pub fn to_exact_out_path(
+        &self,
+        graph: &PoolGraph,
+        amount_out: u64,
+        maximum_in: u64,
+        receiver: Identity,
+        mira_amm: ContractId,
+    ) -> SwapPath {
+        let mut steps = self.to_swap_steps(graph, receiver, mira_amm);
+        steps.reverse();
+        (amount_out, maximum_in, true, steps)
+    }
+}
+
+// All routes from `asset_in` to `asset_out` with at most `max_hops` pools
+// that do not visit an asset twice
+pub fn find_routes(
+    graph: &PoolGraph,
+    asset_in: AssetId,
+    asset_out: AssetId,
+    max_hops: usize,
+) -> Vec<Route> {
+    let mut routes = Vec::new();
+    let mut hops = Vec::new();
+    let mut visited = vec![asset_in];
+    search(
+        graph,
+        asset_in,
+        asset_out,
+        max_hops,
+        &mut hops,
+        &mut visited,
+        &mut routes,
+    );
+    routes
+}
+
+fn search(
+    graph: &PoolGraph,
+    current: AssetId,
+    asset_out: AssetId,
+    max_hops: usize,
+    hops: &mut Vec<Hop>,
+    visited: &mut Vec<AssetId>,
+    routes: &mut Vec<Route>,
+) {
+    if hops.len() == max_hops {
+        return;
+    }
+    for &pool in graph.pools_of(current) {
+        let next = graph.pool(pool).other(current);
+        if visited.contains(&next) {
+            continue;
+        }
+        hops.push(Hop {
+            pool,
+            asset_in: current,
+            asset_out: next,
+        });
+        if next == asset_out {
+            routes.push(Route { hops: hops.clone() });
+        } else {
+            visited.push(next);
+            search(graph, next, asset_out, max_hops, hops, visited, routes);
+            visited.pop();
+        }
+        hops.pop();
+    }
+}
+
+// Quote all routes and sort them, the best first.
+// For exact in, `amount` is the input and routes are ranked by output,
+// for exact out, `amount` is the output and routes are ranked by input
+pub fn best_routes(
+    graph: &PoolGraph,
+    asset_in: AssetId,
+    asset_out: AssetId,
+    amount: u64,
+    trade_type: TradeType,
+    max_hops: usize,
+) -> Vec<QuotedRoute> {
+    let mut quoted: Vec<QuotedRoute> = find_routes(graph, asset_in, asset_out, max_hops)
+        .into_iter()
+        .filter_map(|route| match trade_type {
+            TradeType::ExactIn => route
+                .quote_exact_in(graph, amount)
+                .filter(|amount_out| *amount_out != 0)
+                .map(|amount_out| QuotedRoute {
+                    route,
+                    amount_in: amount,
+                    amount_out,
+                }),
+            TradeType::ExactOut => {
+                route
+                    .quote_exact_out(graph, amount)
+                    .map(|amount_in| QuotedRoute {
+                        route,
+                        amount_in,
+                        amount_out: amount,
+                    })
+            }
+        })
+        .collect();
+
+    match trade_type {
+        TradeType::ExactIn => quoted.sort_by(|a, b| b.amount_out.cmp(&a.amount_out)),
+        TradeType::ExactOut => quoted.sort_by(|a, b| a.amount_in.cmp(&b.amount_in)),
+    }
+    quoted
+}
@@ -0,0 +1,393 @@
+// Splits a trade across parallel paths.
+// Allocation is greedy over small chunks: every chunk goes to the candidate
+// with the best total result, which approximates equal marginal prices across
+// the curves. Totals are simulated sequentially in execution order so that
+// paths sharing a pool see the reserves left by the previous paths.
+use bindings::BatchSwapStep;
+use fuels::types::{AssetId, Bytes, ContractId, Identity};
+
+use crate::graph::PoolGraph;
+use crate::route::{Route, SwapPath, TradeType};
+
+// the dex id of 1delta orders in the executor
+pub const ONE_DELTA_ORDERS_DEX_ID: u64 = 100;
+
+const BASIS_POINTS_DENOMINATOR: u64 = 10_000;
+
+// A single RFQ order as used by the executor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LadderLevel {
+    // the order step data, see `encode_order_params`
+    pub data: Bytes,
+    // the taker amount that can still be filled, bounded by
+    // the remaining order amount and the maker balance
+    pub available: u64,
+}
+
+impl LadderLevel {
+    pub fn new(data: Bytes, available: u64) -> Self {
+        Self { data, available }
+    }
+
+    // (maker_amount, taker_amount) of the order, the first two fields of the data
+    pub fn rate(&self) -> (u64, u64) {
+        let mut maker_amount = [0u8; 8];
+        let mut taker_amount = [0u8; 8];
+        maker_amount.copy_from_slice(&self.data.0[..8]);
+        taker_amount.copy_from_slice(&self.data.0[8..16]);
+        (
+            u64::from_be_bytes(maker_amount),
+            u64::from_be_bytes(taker_amount),
+        )
+    }
+
+    // same rounding as `compute_maker_fill_amount` in `order_utils`
+    fn amount_out(&self, amount_in: u64) -> u64 {
+        let (maker_amount, taker_amount) = self.rate();
+        (amount_in as u128 * maker_amount as u128 / taker_amount as u128) as u64
+    }
+
+    // same as `quote_order_exact_out` in the executor
+    fn amount_in(&self, amount_out: u64) -> u64 {
+        let (maker_amount, taker_amount) = self.rate();
+        let amount_in =
+            (amount_out as u128 * taker_amount as u128 / maker_amount as u128) as u64 + 1;
+        amount_in.min(taker_amount)
+    }
+
+    // the largest output an exact out fill can request within `available`
+    fn max_amount_out(&self) -> u64 {
+        let (maker_amount, taker_amount) = self.rate();
+        if self.available >= taker_amount {
+            maker_amount
+        } else {
+            // leave room for the rounding up of the input
+            self.amount_out(self.available.saturating_sub(1))
+        }
+    }
+}
+
+// RFQ orders for one pair, filled best price first
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ladder {
+    pub asset_in: AssetId,
+    pub asset_out: AssetId,
+    pub levels: Vec<LadderLevel>,
+}
+
+impl Ladder {
+    pub fn new(asset_in: AssetId, asset_out: AssetId, mut levels: Vec<LadderLevel>) -> Self {
+        levels.retain(|level| {
+            let (maker_amount, taker_amount) = level.rate();
+            maker_amount != 0 && taker_amount != 0 && level.available != 0
+        });
+        levels.sort_by(|a, b| {
+            let (maker_a, taker_a) = a.rate();
+            let (maker_b, taker_b) = b.rate();
+            (maker_b as u128 * taker_a as u128).cmp(&(maker_a as u128 * taker_b as u128))
+        });
+        Self {
+            asset_in,
+            asset_out,
+            levels,
+        }
+    }
+
+    // (level, amount_in, amount_out) per filled level,
+    // None if the ladder cannot absorb the input
+    pub fn fills_exact_in(&self, amount_in: u64) -> Option<Vec<(usize, u64, u64)>> {
+        let mut fills = Vec::new();
+        let mut remaining = amount_in;
+        for (i, level) in self.levels.iter().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+            let fill_in = remaining.min(level.available);
+            fills.push((i, fill_in, level.amount_out(fill_in)));
+            remaining -= fill_in;
+        }
+        (remaining == 0).then_some(fills)
+    }
+
+    // (level, amount_in, amount_out) per filled level,
+    // None if the ladder cannot deliver the output
+    pub fn fills_exact_out(&self, amount_out: u64) -> Option<Vec<(usize, u64, u64)>> {
+        let mut fills = Vec::new();
+        let mut remaining = amount_out;
+        for (i, level) in self.levels.iter().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+            let fill_out = remaining.min(level.max_amount_out());
+            if fill_out == 0 {
+                continue;
+            }
+            fills.push((i, level.amount_in(fill_out), fill_out));
+            remaining -= fill_out;
+        }
+        (remaining == 0).then_some(fills)
+    }
+
+    pub fn quote_exact_in(&self, amount_in: u64) -> Option<u64> {
+        self.fills_exact_in(amount_in)
+            .map(|fills| fills.iter().map(|fill| fill.2).sum())
+    }
+
+    pub fn quote_exact_out(&self, amount_out: u64) -> Option<u64> {
+        self.fills_exact_out(amount_out)
+            .map(|fills| fills.iter().map(|fill| fill.1).sum())
+    }
+
+    fn swap_step(&self, level: usize, receiver: Identity) -> BatchSwapStep {
+        BatchSwapStep {
+            dex_id: ONE_DELTA_ORDERS_DEX_ID,
+            asset_in: self.asset_in,
+            asset_out: self.asset_out,
+            receiver,
+            data: self.levels[level].data.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Candidate {
+    Route(Route),
+    Ladder(Ladder),
+}
+
+impl Candidate {
+    pub fn asset_in(&self) -> AssetId {
+        match self {
+            Candidate::Route(route) => route.asset_in(),
+            Candidate::Ladder(ladder) => ladder.asset_in,
+        }
+    }
+
+    pub fn asset_out(&self) -> AssetId {
+        match self {
+            Candidate::Route(route) => route.asset_out(),
+            Candidate::Ladder(ladder) => ladder.asset_out,
+        }
+    }
+
+    // (amount_in, amount_out), pools are updated with the swaps
+    fn simulate(
+        &self,
+        graph: &mut PoolGraph,
+        amount: u64,
+        trade_type: TradeType,
+    ) -> Option<(u64, u64)> {
+        match (self, trade_type) {
+            (Candidate::Route(route), TradeType::ExactIn) => route
+                .simulate_exact_in(graph, amount)
+                .map(|amount_out| (amount, amount_out)),
+            (Candidate::Route(route), TradeType::ExactOut) => route
+                .simulate_exact_out(graph, amount)
+                .map(|amount_in| (amount_in, amount)),
+            (Candidate::Ladder(ladder), TradeType::ExactIn) => ladder
+                .quote_exact_in(amount)
+                .map(|amount_out| (amount, amount_out)),
+            (Candidate::Ladder(ladder), TradeType::ExactOut) => ladder
+                .quote_exact_out(amount)
+                .map(|amount_in| (amount_in, amount)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allocation {
+    // the index of the candidate
+    pub candidate: usize,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Split {
+    pub trade_type: TradeType,
+    // in execution order, only candidates with a non-zero amount
+    pub allocations: Vec<Allocation>,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+// Simulates all candidates in order with the given amounts,
+// amounts are inputs for exact in and outputs for exact out
+fn simulate(
+    graph: &PoolGraph,
+    candidates: &[Candidate],
+    amounts: &[u64],
+    trade_type: TradeType,
+) -> Option<Split> {
+    let mut graph = graph.clone();
+    let mut allocations = Vec::new();
+    for (candidate, &amount) in amounts.iter().enumerate() {
+        if amount == 0 {
+            continue;
+        }
+        let (amount_in, amount_out) =
+            candidates[candidate].simulate(&mut graph, amount, trade_type)?;
+        if amount_out == 0 {
+            return None;
+        }
+        allocations.push(Allocation {
+            candidate,
+            amount_in,
+            amount_out,
+        });
+    }
+    Some(Split {
+        trade_type,
+        amount_in: allocations.iter().map(|a| a.amount_in).sum(),
+        amount_out: allocations.iter().map(|a| a.amount_out).sum(),
+        allocations,
+    })
+}
+
+// Allocates `amount` in `chunks` parts, each to the candidate that gives
+// the best total. More chunks get closer to the optimum at a quadratic cost
+fn optimize(
+    graph: &PoolGraph,
+    candidates: &[Candidate],
+    amount: u64,
+    chunks: u64,
+    trade_type: TradeType,
+) -> Option<Split> {
+    if amount == 0 || candidates.is_empty() {
+        return None;
+    }
+    let chunk = amount.div_ceil(chunks.max(1));
+    let mut amounts = vec![0u64; candidates.len()];
+    let mut remaining = amount;
+    let mut best: Option<Split> = None;
+
+    while remaining != 0 {
+        let size = chunk.min(remaining);
+        let mut chunk_best: Option<(usize, Split)> = None;
+        for candidate in 0..candidates.len() {
+            amounts[candidate] += size;
+            let split = simulate(graph, candidates, &amounts, trade_type);
+            amounts[candidate] -= size;
+
+            let Some(split) = split else {
+                continue;
+            };
+            let better = match &chunk_best {
+                None => true,
+                Some((_, current)) => match trade_type {
+                    TradeType::ExactIn => split.amount_out > current.amount_out,
+                    TradeType::ExactOut => split.amount_in < current.amount_in,
+                },
+            };
+            if better {
+                chunk_best = Some((candidate, split));
+            }
+        }
+        let (candidate, split) = chunk_best?;
+        amounts[candidate] += size;
+        remaining -= size;
+        best = Some(split);
+    }
+    best
+}
+
+// Maximises the output for `amount_in` across candidates from the same
+// input to the same output asset
+pub fn optimize_exact_in(
+    graph: &PoolGraph,
+    candidates: &[Candidate],
+    amount_in: u64,
+    chunks: u64,
+) -> Option<Split> {
+    optimize(graph, candidates, amount_in, chunks, TradeType::ExactIn)
+}
+
+// Minimises the input for `amount_out` across candidates from the same
+// input to the same output asset
+pub fn optimize_exact_out(
+    graph: &PoolGraph,
+    candidates: &[Candidate],
+    amount_out: u64,
+    chunks: u64,
+) -> Option<Split> {
+    optimize(graph, candidates, amount_out, chunks, TradeType::ExactOut)
+}
+
+// the exact in script requires the output to be strictly above the minimum
+fn minimum_out(amount_out: u64, slippage_bps: u64) -> u64 {
+    let minimum = amount_out as u128
+        * BASIS_POINTS_DENOMINATOR.saturating_sub(slippage_bps) as u128
+        / BASIS_POINTS_DENOMINATOR as u128;
+    (minimum as u64).saturating_sub(1)
+}
+
+fn maximum_in(amount_in: u64, slippage_bps: u64) -> u64 {
+    let maximum = amount_in as u128 * (BASIS_POINTS_DENOMINATOR + slippage_bps) as u128
+        / BASIS_POINTS_DENOMINATOR as u128;
+    maximum.min(u64::MAX as u128) as u64
+}
+
+impl Split {
+    // Paths for `batch_swap_exact_in_script` or `batch_swap_exact_out_script`
+    // depending on the trade type, in the order that was simulated.
+    // Every filled ladder level is its own path
+    pub fn to_swap_paths(
+        &self,
+        graph: &PoolGraph,
+        candidates: &[Candidate],
+        slippage_bps: u64,
+        receiver: Identity,
+        mira_amm: ContractId,
+    ) -> Vec<SwapPath> {
+        let mut paths = Vec::new();
+        for allocation in &self.allocations {
+            match (&candidates[allocation.candidate], self.trade_type) {
+                (Candidate::Route(route), TradeType::ExactIn) => {
+                    paths.push(route.to_exact_in_path(
+                        graph,
+                        allocation.amount_in,
+                        minimum_out(allocation.amount_out, slippage_bps),
+                        receiver,
+                        mira_amm,
+                    ));
+                }
+                (Candidate::Route(route), TradeType::ExactOut) => {
+                    paths.push(route.to_exact_out_path(
+                        graph,
+                        allocation.amount_out,
+                        maximum_in(allocation.amount_in, slippage_bps),
+                        receiver,
+                        mira_amm,
+                    ));
+                }
+                (Candidate::Ladder(ladder), TradeType::ExactIn) => {
+                    for (level, amount_in, amount_out) in ladder
+                        .fills_exact_in(allocation.amount_in)
+                        .unwrap_or_default()
+                    {
+                        paths.push((
+                            amount_in,
+                            minimum_out(amount_out, slippage_bps),
+                            true,
+                            vec![ladder.swap_step(level, receiver)],
+                        ));
+                    }
+                }
+                (Candidate::Ladder(ladder), TradeType::ExactOut) => {
+                    for (level, amount_in, amount_out) in ladder
+                        .fills_exact_out(allocation.amount_out)
+                        .unwrap_or_default()
+                    {
+                        paths.push((
+                            amount_out,
+                            maximum_in(amount_in, slippage_bps),
+                            true,
+                            vec![ladder.swap_step(level, receiver)],
+                        ));
+                    }
+                }
+            }
+        }
+        paths
+    }
+}
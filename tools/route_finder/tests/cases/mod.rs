@@ -0,0 +1,3 @@
+pub mod revert;
+pub mod split;
+pub mod success;
@@ -0,0 +1,52 @@
+use crate::utils::{ladder_level, setup, THIN_POOL_LIQUIDITY};
+use fuels::types::AssetId;
+use route_finder::graph::PoolGraph;
+use route_finder::route::{best_routes, find_routes, TradeType};
+use route_finder::split::{optimize_exact_in, optimize_exact_out, Candidate, Ladder};
+
+#[tokio::test]
+async fn no_route_for_unknown_asset() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, _) = fixture.tokens;
+
+    assert!(find_routes(&graph, token_0_id, AssetId::zeroed(), 3).is_empty());
+}
+
+#[tokio::test]
+async fn skips_routes_that_cannot_deliver_the_output() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, token_2_id) = fixture.tokens;
+
+    // the thin Mira pool cannot pay out more than its reserves
+    let quoted = best_routes(
+        &graph,
+        token_0_id,
+        token_2_id,
+        THIN_POOL_LIQUIDITY,
+        TradeType::ExactOut,
+        1,
+    );
+    assert_eq!(quoted.len(), 1);
+    assert_eq!(
+        graph.pool(quoted[0].route.hops[0].pool).amm,
+        fixture.fork.id
+    );
+}
+
+#[test]
+fn no_split_beyond_ladder_capacity() {
+    let asset_in = AssetId::new([1u8; 32]);
+    let asset_out = AssetId::new([2u8; 32]);
+    let graph = PoolGraph::default();
+
+    let candidates = vec![Candidate::Ladder(Ladder::new(
+        asset_in,
+        asset_out,
+        vec![ladder_level(60_000, 50_000, 50_000)],
+    ))];
+
+    assert!(optimize_exact_in(&graph, &candidates, 50_001, 10).is_none());
+    assert!(optimize_exact_out(&graph, &candidates, 60_001, 10).is_none());
+}
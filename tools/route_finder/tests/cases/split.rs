@@ -0,0 +1,182 @@
+use crate::utils::{ladder_level, mock_pool, setup, swap_exact_in, swap_exact_out};
+use fuels::types::{AssetId, ContractId, Identity};
+use route_finder::graph::PoolGraph;
+use route_finder::route::{best_routes, find_routes, TradeType};
+use route_finder::split::{
+    optimize_exact_in, optimize_exact_out, Candidate, Ladder, ONE_DELTA_ORDERS_DEX_ID,
+};
+use test_harness::utils::common::asset_balance;
+
+const CHUNKS: u64 = 20;
+
+#[tokio::test]
+async fn exact_in_split_beats_best_route_and_matches_execution() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, token_2_id) = fixture.tokens;
+    let receiver = Identity::Address(fixture.wallet.address().into());
+    let amount_in = 200_000;
+
+    let candidates: Vec<Candidate> = find_routes(&graph, token_0_id, token_2_id, 2)
+        .into_iter()
+        .map(Candidate::Route)
+        .collect();
+    let split = optimize_exact_in(&graph, &candidates, amount_in, CHUNKS).unwrap();
+
+    let best = &best_routes(
+        &graph,
+        token_0_id,
+        token_2_id,
+        amount_in,
+        TradeType::ExactIn,
+        2,
+    )[0];
+    assert!(split.allocations.len() > 1);
+    assert!(split.amount_out > best.amount_out);
+    assert_eq!(split.amount_in, amount_in);
+
+    let paths = split.to_swap_paths(&graph, &candidates, 0, receiver, fixture.mira_amm());
+    assert_eq!(paths.len(), split.allocations.len());
+
+    let balance_before = asset_balance(&fixture.wallet, &token_2_id).await;
+    swap_exact_in(&fixture, token_0_id, paths).await;
+    let balance_after = asset_balance(&fixture.wallet, &token_2_id).await;
+
+    // paths that share pools are simulated in execution order
+    assert_eq!(balance_after - balance_before, split.amount_out);
+}
+
+#[tokio::test]
+async fn exact_out_split_beats_best_route_and_matches_execution() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, token_2_id) = fixture.tokens;
+    let receiver = Identity::Address(fixture.wallet.address().into());
+    let amount_out = 200_000;
+
+    let candidates: Vec<Candidate> = find_routes(&graph, token_0_id, token_2_id, 2)
+        .into_iter()
+        .map(Candidate::Route)
+        .collect();
+    let split = optimize_exact_out(&graph, &candidates, amount_out, CHUNKS).unwrap();
+
+    let best = &best_routes(
+        &graph,
+        token_0_id,
+        token_2_id,
+        amount_out,
+        TradeType::ExactOut,
+        2,
+    )[0];
+    assert!(split.allocations.len() > 1);
+    assert!(split.amount_in < best.amount_in);
+    assert_eq!(split.amount_out, amount_out);
+
+    let paths = split.to_swap_paths(&graph, &candidates, 0, receiver, fixture.mira_amm());
+
+    let token_0_before = asset_balance(&fixture.wallet, &token_0_id).await;
+    let token_2_before = asset_balance(&fixture.wallet, &token_2_id).await;
+    swap_exact_out(&fixture, token_0_id, paths).await;
+    let token_0_after = asset_balance(&fixture.wallet, &token_0_id).await;
+    let token_2_after = asset_balance(&fixture.wallet, &token_2_id).await;
+
+    assert_eq!(token_0_before - token_0_after, split.amount_in);
+    assert_eq!(token_2_after - token_2_before, amount_out);
+}
+
+#[test]
+fn exact_in_split_fills_better_priced_ladder_first() {
+    let asset_in = AssetId::new([1u8; 32]);
+    let asset_out = AssetId::new([2u8; 32]);
+    let receiver = Identity::ContractId(ContractId::zeroed());
+    let graph = PoolGraph::new(vec![mock_pool(asset_in, asset_out, 1_000_000, 30)]);
+    let amount_in = 200_000;
+
+    let candidates = vec![
+        Candidate::Route(find_routes(&graph, asset_in, asset_out, 1).remove(0)),
+        Candidate::Ladder(Ladder::new(
+            asset_in,
+            asset_out,
+            vec![ladder_level(60_000, 50_000, 50_000)],
+        )),
+    ];
+    let split = optimize_exact_in(&graph, &candidates, amount_in, CHUNKS).unwrap();
+
+    let ladder = split
+        .allocations
+        .iter()
+        .find(|allocation| allocation.candidate == 1)
+        .unwrap();
+    assert_eq!(ladder.amount_in, 50_000);
+    assert_eq!(ladder.amount_out, 60_000);
+    assert!(split.amount_out > graph.pool(0).amount_out(asset_in, amount_in).unwrap());
+
+    // the ladder level is its own path with a single order step
+    let paths = split.to_swap_paths(&graph, &candidates, 0, receiver, ContractId::zeroed());
+    let order_path = paths
+        .iter()
+        .find(|path| path.3[0].dex_id == ONE_DELTA_ORDERS_DEX_ID)
+        .unwrap();
+    assert_eq!(order_path.0, 50_000);
+    // the exact in script checks the output strictly above the minimum
+    assert_eq!(order_path.1, 60_000 - 1);
+    assert_eq!(order_path.3.len(), 1);
+}
+
+#[test]
+fn exact_out_split_fills_better_priced_ladder_first() {
+    let asset_in = AssetId::new([1u8; 32]);
+    let asset_out = AssetId::new([2u8; 32]);
+    let receiver = Identity::ContractId(ContractId::zeroed());
+    let graph = PoolGraph::new(vec![mock_pool(asset_in, asset_out, 1_000_000, 30)]);
+    let amount_out = 200_000;
+
+    let candidates = vec![
+        Candidate::Route(find_routes(&graph, asset_in, asset_out, 1).remove(0)),
+        Candidate::Ladder(Ladder::new(
+            asset_in,
+            asset_out,
+            vec![ladder_level(60_000, 50_000, 50_000)],
+        )),
+    ];
+    let split = optimize_exact_out(&graph, &candidates, amount_out, CHUNKS).unwrap();
+
+    let ladder = split
+        .allocations
+        .iter()
+        .find(|allocation| allocation.candidate == 1)
+        .unwrap();
+    assert_eq!(ladder.amount_out, 60_000);
+    assert_eq!(ladder.amount_in, 50_000);
+    assert!(split.amount_in < graph.pool(0).amount_in(asset_out, amount_out).unwrap());
+
+    // 1% slippage on the input
+    let paths = split.to_swap_paths(&graph, &candidates, 100, receiver, ContractId::zeroed());
+    let order_path = paths
+        .iter()
+        .find(|path| path.3[0].dex_id == ONE_DELTA_ORDERS_DEX_ID)
+        .unwrap();
+    assert_eq!(order_path.0, 60_000);
+    assert_eq!(order_path.1, 50_500);
+}
+
+#[test]
+fn ladder_levels_are_filled_best_price_first() {
+    let asset_in = AssetId::new([1u8; 32]);
+    let asset_out = AssetId::new([2u8; 32]);
+
+    let ladder = Ladder::new(
+        asset_in,
+        asset_out,
+        vec![
+            ladder_level(10_000, 10_000, 10_000),
+            ladder_level(12_000, 10_000, 10_000),
+            ladder_level(20_000, 10_000, 0),
+        ],
+    );
+    // the empty level is dropped
+    assert_eq!(ladder.levels.len(), 2);
+
+    let fills = ladder.fills_exact_in(15_000).unwrap();
+    assert_eq!(fills, vec![(0, 10_000, 12_000), (1, 5_000, 5_000)]);
+}
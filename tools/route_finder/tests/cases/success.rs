@@ -0,0 +1,188 @@
+use crate::utils::{setup, swap_exact_in, swap_exact_out};
+use fuels::types::Identity;
+use route_finder::graph::MIRA_V1_DEX_ID;
+use route_finder::route::{best_routes, find_routes, TradeType};
+use test_harness::utils::common::asset_balance;
+
+#[tokio::test]
+async fn finds_routes_up_to_max_hops() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, token_2_id) = fixture.tokens;
+
+    assert_eq!(graph.pools().len(), 5);
+
+    // Mira and fork direct pools
+    let routes = find_routes(&graph, token_0_id, token_2_id, 1);
+    assert_eq!(routes.len(), 2);
+
+    // plus the volatile and stable 0-1 pools into 1-2
+    let routes = find_routes(&graph, token_0_id, token_2_id, 2);
+    assert_eq!(routes.len(), 4);
+    assert!(routes.iter().all(|route| route.asset_in() == token_0_id
+        && route.asset_out() == token_2_id
+        && route.hops.len() <= 2));
+}
+
+#[tokio::test]
+async fn best_exact_in_route_matches_execution() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, token_2_id) = fixture.tokens;
+    let receiver = Identity::Address(fixture.wallet.address().into());
+    let amount_in = 10_000;
+
+    let quoted = best_routes(
+        &graph,
+        token_0_id,
+        token_2_id,
+        amount_in,
+        TradeType::ExactIn,
+        2,
+    );
+    assert_eq!(quoted.len(), 4);
+    assert!(quoted
+        .windows(2)
+        .all(|pair| pair[0].amount_out >= pair[1].amount_out));
+
+    let best = &quoted[0];
+    let path = best.route.to_exact_in_path(
+        &graph,
+        amount_in,
+        best.amount_out - 1,
+        receiver,
+        fixture.mira_amm(),
+    );
+
+    let balance_before = asset_balance(&fixture.wallet, &token_2_id).await;
+    swap_exact_in(&fixture, token_0_id, vec![path]).await;
+    let balance_after = asset_balance(&fixture.wallet, &token_2_id).await;
+
+    assert_eq!(balance_after - balance_before, best.amount_out);
+}
+
+#[tokio::test]
+async fn fork_route_matches_execution() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, token_2_id) = fixture.tokens;
+    let receiver = Identity::Address(fixture.wallet.address().into());
+    let amount_in = 10_000;
+
+    let quoted = best_routes(
+        &graph,
+        token_0_id,
+        token_2_id,
+        amount_in,
+        TradeType::ExactIn,
+        1,
+    );
+    let via_fork = quoted
+        .iter()
+        .find(|q| graph.pool(q.route.hops[0].pool).amm == fixture.fork.id)
+        .unwrap();
+
+    // the deep fork pool beats the thin Mira pool
+    assert_eq!(via_fork, &quoted[0]);
+
+    let path = via_fork.route.to_exact_in_path(
+        &graph,
+        amount_in,
+        via_fork.amount_out - 1,
+        receiver,
+        fixture.mira_amm(),
+    );
+    // forks are encoded with their address
+    assert_eq!(path.3[0].dex_id, MIRA_V1_DEX_ID);
+    assert_eq!(path.3[0].data.0.len(), 35);
+
+    let balance_before = asset_balance(&fixture.wallet, &token_2_id).await;
+    swap_exact_in(&fixture, token_0_id, vec![path]).await;
+    let balance_after = asset_balance(&fixture.wallet, &token_2_id).await;
+
+    assert_eq!(balance_after - balance_before, via_fork.amount_out);
+}
+
+#[tokio::test]
+async fn multi_hop_exact_in_route_matches_execution() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, token_1_id, token_2_id) = fixture.tokens;
+    let receiver = Identity::Address(fixture.wallet.address().into());
+    let amount_in = 10_000;
+
+    // the 2 hop route via the stable pool
+    let quoted = best_routes(
+        &graph,
+        token_0_id,
+        token_2_id,
+        amount_in,
+        TradeType::ExactIn,
+        2,
+    );
+    let via_stable = quoted
+        .iter()
+        .find(|q| q.route.hops.len() == 2 && graph.pool(q.route.hops[0].pool).is_stable())
+        .unwrap();
+    assert_eq!(via_stable.route.hops[0].asset_out, token_1_id);
+
+    let path = via_stable.route.to_exact_in_path(
+        &graph,
+        amount_in,
+        via_stable.amount_out - 1,
+        receiver,
+        fixture.mira_amm(),
+    );
+    // the first hop pays into Mira for the second hop
+    assert_eq!(path.3[0].receiver, Identity::ContractId(fixture.amm.id));
+    assert_eq!(path.3[1].receiver, receiver);
+
+    let balance_before = asset_balance(&fixture.wallet, &token_2_id).await;
+    swap_exact_in(&fixture, token_0_id, vec![path]).await;
+    let balance_after = asset_balance(&fixture.wallet, &token_2_id).await;
+
+    assert_eq!(balance_after - balance_before, via_stable.amount_out);
+}
+
+#[tokio::test]
+async fn best_exact_out_route_matches_execution() {
+    let fixture = setup().await;
+    let graph = fixture.graph().await;
+    let (token_0_id, _, token_2_id) = fixture.tokens;
+    let receiver = Identity::Address(fixture.wallet.address().into());
+    let amount_out = 10_000;
+
+    let quoted = best_routes(
+        &graph,
+        token_0_id,
+        token_2_id,
+        amount_out,
+        TradeType::ExactOut,
+        2,
+    );
+    assert!(quoted
+        .windows(2)
+        .all(|pair| pair[0].amount_in <= pair[1].amount_in));
+
+    // use a 2 hop route to cover the reversed step order
+    let best = quoted.iter().find(|q| q.route.hops.len() == 2).unwrap();
+    let path = best.route.to_exact_out_path(
+        &graph,
+        amount_out,
+        best.amount_in,
+        receiver,
+        fixture.mira_amm(),
+    );
+    // the first step is the last swap
+    assert_eq!(path.3[0].asset_out, token_2_id);
+    assert_eq!(path.3[1].asset_in, token_0_id);
+
+    let token_0_before = asset_balance(&fixture.wallet, &token_0_id).await;
+    let token_2_before = asset_balance(&fixture.wallet, &token_2_id).await;
+    swap_exact_out(&fixture, token_0_id, vec![path]).await;
+    let token_0_after = asset_balance(&fixture.wallet, &token_0_id).await;
+    let token_2_after = asset_balance(&fixture.wallet, &token_2_id).await;
+
+    assert_eq!(token_0_before - token_0_after, best.amount_in);
+    assert_eq!(token_2_after - token_2_before, amount_out);
+}
@@ -0,0 +1,292 @@
+use fuels::accounts::wallet::WalletUnlocked;
+use fuels::prelude::{
+    Contract, LoadConfiguration, StorageConfiguration, TxPolicies, VariableOutputPolicy,
+};
+use fuels::types::{AssetId, Bytes, ContractId, Identity, Salt};
+use route_finder::graph::{MiraPool, PoolGraph};
+use route_finder::loader::{load_graph, pool_ids_from_receipts};
+use route_finder::route::SwapPath;
+use route_finder::split::LadderLevel;
+use test_harness::data_structures::{MiraAMMContract, WalletAssetConfiguration};
+use test_harness::interface::amm::{create_pool, initialize_ownership};
+use test_harness::interface::mock::{
+    add_token, deploy_logger_contract, deploy_mock_token_contract, get_sub_id, mint_tokens,
+};
+use test_harness::interface::scripts::get_transaction_inputs_outputs;
+use test_harness::interface::{
+    AddLiquidityScript, AddLiquidityScriptConfigurables, BatchSwapExactInScript,
+    BatchSwapExactInScriptConfigurables, BatchSwapExactOutScript,
+    BatchSwapExactOutScriptConfigurables, Logger, MiraAMM,
+};
+use test_harness::paths::{
+    ADD_LIQUIDITY_SCRIPT_BINARY_PATH, AMM_CONTRACT_BINARY_PATH,
+    BATCH_SWAP_EXACT_IN_SCRIPT_BINARY_PATH, BATCH_SWAP_EXACT_OUTPUT_SCRIPT_BINARY_PATH,
+};
+use test_harness::setup::common::{deploy_amm, setup_wallet_and_provider};
+use test_harness::types::PoolId;
+use test_harness::utils::common::order_sub_ids;
+
+pub const POOL_LIQUIDITY: u64 = 1_000_000;
+pub const THIN_POOL_LIQUIDITY: u64 = 100_000;
+
+pub struct Fixture {
+    pub wallet: WalletUnlocked,
+    pub amm: MiraAMMContract,
+    pub fork: MiraAMMContract,
+    pub logger: Logger<WalletUnlocked>,
+    pub swap_exact_in_script: BatchSwapExactInScript<WalletUnlocked>,
+    pub swap_exact_out_script: BatchSwapExactOutScript<WalletUnlocked>,
+    pub tokens: (AssetId, AssetId, AssetId),
+    // pool ids as logged by `create_pool` of Mira and of the fork
+    pub amm_pool_ids: Vec<PoolId>,
+    pub fork_pool_ids: Vec<PoolId>,
+    pub deadline: u32,
+}
+
+impl Fixture {
+    pub fn mira_amm(&self) -> ContractId {
+        self.amm.id
+    }
+
+    pub async fn graph(&self) -> PoolGraph {
+        load_graph(&[
+            (self.amm.instance.clone(), self.amm_pool_ids.clone()),
+            (self.fork.instance.clone(), self.fork_pool_ids.clone()),
+        ])
+        .await
+        .unwrap()
+    }
+}
+
+pub async fn swap_exact_in(fixture: &Fixture, asset_in: AssetId, paths: Vec<SwapPath>) {
+    let amount_in = paths.iter().map(|path| path.0).sum();
+    let variable_outputs = paths.len();
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&fixture.wallet, &vec![(asset_in, amount_in)]).await;
+    fixture
+        .swap_exact_in_script
+        .main(paths, fixture.deadline, vec![])
+        .with_contracts(&[
+            &fixture.amm.instance,
+            &fixture.fork.instance,
+            &fixture.logger,
+        ])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(variable_outputs))
+        .call()
+        .await
+        .unwrap();
+}
+
+pub async fn swap_exact_out(fixture: &Fixture, asset_in: AssetId, paths: Vec<SwapPath>) {
+    let maximum_in = paths.iter().map(|path| path.1).sum();
+    let variable_outputs = paths.len();
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(&fixture.wallet, &vec![(asset_in, maximum_in)]).await;
+    fixture
+        .swap_exact_out_script
+        .main(paths, fixture.deadline, vec![])
+        .with_contracts(&[
+            &fixture.amm.instance,
+            &fixture.fork.instance,
+            &fixture.logger,
+        ])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(variable_outputs))
+        .call()
+        .await
+        .unwrap();
+}
+
+// a pool that only exists in memory, for tests without a chain
+pub fn mock_pool(asset_0: AssetId, asset_1: AssetId, reserve: u64, fee: u64) -> MiraPool {
+    MiraPool {
+        amm: ContractId::zeroed(),
+        pool_id: (asset_0, asset_1, false),
+        reserve_0: reserve,
+        reserve_1: reserve,
+        decimals_0: 9,
+        decimals_1: 9,
+        fee,
+    }
+}
+
+// order step data with the amounts set, the rest is not read for quoting
+pub fn ladder_level(maker_amount: u64, taker_amount: u64, available: u64) -> LadderLevel {
+    let mut data = Vec::new();
+    data.extend_from_slice(&maker_amount.to_be_bytes());
+    data.extend_from_slice(&taker_amount.to_be_bytes());
+    data.extend_from_slice(&[0u8; 32 + 8 + 8 + 32 + 64]);
+    LadderLevel::new(Bytes(data), available)
+}
+
+// deploys the Mira AMM binary a second time as a fork
+async fn deploy_fork(wallet: &WalletUnlocked) -> MiraAMMContract {
+    let configuration = LoadConfiguration::default()
+        .with_storage_configuration(StorageConfiguration::default())
+        .with_salt(Salt::from([1u8; 32]));
+
+    let contract_id = Contract::load_from(AMM_CONTRACT_BINARY_PATH, configuration)
+        .unwrap()
+        .deploy(wallet, TxPolicies::default())
+        .await
+        .unwrap();
+
+    let instance = MiraAMM::new(contract_id.clone(), wallet.clone());
+
+    MiraAMMContract {
+        instance,
+        id: contract_id.into(),
+    }
+}
+
+async fn add_liquidity(
+    wallet: &WalletUnlocked,
+    amm: &MiraAMMContract,
+    pool_id: PoolId,
+    amount: u64,
+    deadline: u32,
+) {
+    let configurables = AddLiquidityScriptConfigurables::default()
+        .with_MIRA_AMM_CONTRACT_ID(amm.id)
+        .unwrap();
+    let script = AddLiquidityScript::new(wallet.clone(), ADD_LIQUIDITY_SCRIPT_BINARY_PATH)
+        .with_configurables(configurables);
+
+    let (inputs, outputs) =
+        get_transaction_inputs_outputs(wallet, &vec![(pool_id.0, amount), (pool_id.1, amount)])
+            .await;
+
+    script
+        .main(
+            pool_id,
+            amount,
+            amount,
+            0,
+            0,
+            wallet.address().into(),
+            deadline,
+        )
+        .with_contracts(&[&amm.instance])
+        .with_inputs(inputs)
+        .with_outputs(outputs)
+        .with_variable_output_policy(VariableOutputPolicy::Exactly(2))
+        .call()
+        .await
+        .unwrap();
+}
+
+////////////////////////////////////////////////////
+// Create 3 tokens, Mira and a fork of it with the pools
+// Mira: 0-1 volatile, 0-1 stable, 1-2 volatile, 0-2 volatile (thin)
+// fork: 0-2 volatile
+////////////////////////////////////////////////////
+pub async fn setup() -> Fixture {
+    let (wallet, _asset_ids, provider) =
+        setup_wallet_and_provider(&WalletAssetConfiguration::default()).await;
+
+    let amm = deploy_amm(&wallet).await;
+    initialize_ownership(&amm.instance, Identity::Address(wallet.address().into())).await;
+    let fork = deploy_fork(&wallet).await;
+    initialize_ownership(&fork.instance, Identity::Address(wallet.address().into())).await;
+
+    let (token_contract_id, token_contract) = deploy_mock_token_contract(&wallet).await;
+    let (logger_contract_id, logger) = deploy_logger_contract(&wallet).await;
+
+    let mut tokens = Vec::new();
+    for (name, symbol) in [("TOKEN_A", "TKA"), ("TOKEN_B", "TKB"), ("TOKEN_C", "TKC")] {
+        let token_id = add_token(&token_contract, name.to_string(), symbol.to_string(), 9)
+            .await
+            .value;
+        mint_tokens(&token_contract, token_id, 1_000_000_000).await;
+        tokens.push(token_id);
+    }
+    tokens.sort();
+    let [token_0_id, token_1_id, token_2_id] = tokens[..] else {
+        unreachable!("three assets were added")
+    };
+
+    ////////////////////////////////////////////////////
+    // create dex pools and add liquidity
+    ////////////////////////////////////////////////////
+
+    let deadline = provider.latest_block_height().await.unwrap() + 100;
+
+    let pools = [
+        (&amm, (token_0_id, token_1_id), false, POOL_LIQUIDITY),
+        (&amm, (token_0_id, token_1_id), true, POOL_LIQUIDITY),
+        (&amm, (token_1_id, token_2_id), false, POOL_LIQUIDITY),
+        (&amm, (token_0_id, token_2_id), false, THIN_POOL_LIQUIDITY),
+        (&fork, (token_0_id, token_2_id), false, POOL_LIQUIDITY),
+    ];
+    let mut amm_pool_ids = Vec::new();
+    let mut fork_pool_ids = Vec::new();
+    for (dex, (asset_0, asset_1), is_stable, liquidity) in pools {
+        let (sub_id_0, sub_id_1) = order_sub_ids(
+            (asset_0, asset_1),
+            (
+                get_sub_id(&token_contract, asset_0).await.value.unwrap(),
+                get_sub_id(&token_contract, asset_1).await.value.unwrap(),
+            ),
+        );
+        let response = create_pool(
+            &dex.instance,
+            &token_contract,
+            token_contract_id,
+            sub_id_0,
+            token_contract_id,
+            sub_id_1,
+            is_stable,
+        )
+        .await;
+
+        // discover the pools via their events
+        let pool_ids = pool_ids_from_receipts(&dex.instance, &response.receipts).unwrap();
+        assert_eq!(pool_ids, vec![response.value]);
+
+        add_liquidity(&wallet, dex, response.value, liquidity, deadline).await;
+        if dex.id == amm.id {
+            amm_pool_ids.extend(pool_ids);
+        } else {
+            fork_pool_ids.extend(pool_ids);
+        }
+    }
+
+    ////////////////////////////////////////////////////
+    // batch swap scripts configured with Mira
+    ////////////////////////////////////////////////////
+
+    let swap_exact_in_script =
+        BatchSwapExactInScript::new(wallet.clone(), BATCH_SWAP_EXACT_IN_SCRIPT_BINARY_PATH)
+            .with_configurables(
+                BatchSwapExactInScriptConfigurables::default()
+                    .with_MIRA_AMM_CONTRACT_ID(amm.id)
+                    .unwrap()
+                    .with_LOGGER_CONTRACT_ID(logger_contract_id)
+                    .unwrap(),
+            );
+    let swap_exact_out_script =
+        BatchSwapExactOutScript::new(wallet.clone(), BATCH_SWAP_EXACT_OUTPUT_SCRIPT_BINARY_PATH)
+            .with_configurables(
+                BatchSwapExactOutScriptConfigurables::default()
+                    .with_MIRA_AMM_CONTRACT_ID(amm.id)
+                    .unwrap()
+                    .with_LOGGER_CONTRACT_ID(logger_contract_id)
+                    .unwrap(),
+            );
+
+    Fixture {
+        wallet,
+        amm,
+        fork,
+        logger,
+        swap_exact_in_script,
+        swap_exact_out_script,
+        tokens: (token_0_id, token_1_id, token_2_id),
+        amm_pool_ids,
+        fork_pool_ids,
+        deadline,
+    }
+}
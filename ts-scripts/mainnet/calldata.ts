@@ -42,7 +42,7 @@ export async function getComposerRequest(path: Vec<ActionInput>, deadline: BigNu
     return request
 }
 
-export async function getSwapExactInScope(path: any[] = [], deadline = 0) {
+export async function getSwapExactInScope(path: any[] = [], deadline = 0, minimumTotals: any[] = []) {
     const wallet0 = Wallet.fromPrivateKey("0x001", MockProvider as any)
 
     const SwapExactInScript = new BatchSwapExactInScript(wallet0)
@@ -50,7 +50,7 @@ export async function getSwapExactInScope(path: any[] = [], deadline = 0) {
         MIRA_AMM_CONTRACT_ID: { bits: MainnetData.MIRA_AMM_ID },
         ONE_DELTA_ORDERS_CONTRACT_ID: { bits: MainnetData.one_delta_orders },
     })
-    const invocationScope = SwapExactInScript.functions.main(path, deadline);
+    const invocationScope = SwapExactInScript.functions.main(path, deadline, minimumTotals);
     const miraAmm = new Contract(MainnetData.MIRA_AMM_ID, MIRA_ABI, MockProvider as any)
     const rfqmm = new Contract(MainnetData.one_delta_orders, RFQ_ABI, MockProvider as any)
 
@@ -71,7 +71,7 @@ export async function getSwapExactOutScope() {
         MIRA_AMM_CONTRACT_ID: { bits: MainnetData.MIRA_AMM_ID },
         ONE_DELTA_ORDERS_CONTRACT_ID: { bits: MainnetData.one_delta_orders },
     })
-    const invocationScope = SwapExactInScript.functions.main([], 0);
+    const invocationScope = SwapExactInScript.functions.main([], 0, []);
     const miraAmm = new Contract(MainnetData.MIRA_AMM_ID, MIRA_ABI, MockProvider as any)
 
     // Create the transaction request, this can be picked off the invocation
@@ -2,16 +2,26 @@ import { Provider, Wallet } from "fuels";
 import { MainnetData } from "../../contexts";
 import { MNEMONIC } from "../../../env";
 import { OneDeltaOrdersFactory } from "../../typegen/OneDeltaOrdersFactory";
+import { addressInput } from "../../utils";
 
 async function main() {
     const provider = await Provider.create(MainnetData.RPC);
 
     const wallet = Wallet.fromMnemonic(MNEMONIC!, undefined, undefined, provider);
 
-    const Orders = await OneDeltaOrdersFactory.deploy(wallet)
+    const deployTx = await OneDeltaOrdersFactory.deploy(wallet, {
+        configurableConstants: {
+            INITIAL_OWNER: addressInput(wallet.address)
+        }
+    })
+    const { contract: Orders } = await deployTx.waitForResult()
 
-    const OrdersAddress = Orders.contractId
-    console.log(OrdersAddress)
+    console.log(Orders.id.b256Address)
+
+    // only the deployer can set the guardian
+    await Orders.functions
+    .initialize(addressInput(wallet.address))
+    .call()
 }
 
 main()
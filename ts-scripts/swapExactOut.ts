@@ -67,7 +67,8 @@ async function main() {
 
     const request = await SwapExactOutScript.functions.main(
         path,
-        99999999
+        99999999,
+        []
     ).addContracts(
         [miraAmm]
     ).txParams(txParams).getTransactionRequest()
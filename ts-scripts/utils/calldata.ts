@@ -8,7 +8,7 @@ import { txParams } from "./constants";
 import { assetIdInput } from "../utils";
 
 
-export async function getSwapExactInScriptCall(path: any, deadline: any) {
+export async function getSwapExactInScriptCall(path: any, deadline: any, minimumTotals: any[] = []) {
     const wallet0 = Wallet.fromPrivateKey("0x001", MockProvider as any)
 
     const SwapExactInScript = new BatchSwapExactInScriptLoader(wallet0)
@@ -16,10 +16,10 @@ export async function getSwapExactInScriptCall(path: any, deadline: any) {
 
     const abiInterface = new Interface(SCRIPT_ABI)
 
-    const invocationScope = SwapExactInScript.functions.main([], 0);
+    const invocationScope = SwapExactInScript.functions.main([], 0, []);
     const functionName = "main"
 
-    const frag = abiInterface.getFunction(functionName).encodeArguments([path, deadline])
+    const frag = abiInterface.getFunction(functionName).encodeArguments([path, deadline, minimumTotals])
 
     // Create the transaction request, this can be picked off the invocation
     // scope so the script bytecode is preset on the transaction
@@ -7,6 +7,43 @@ export function encodeMiraParams(fee: bigint | BN | string, isStable: boolean) {
     return toU8Array(hex)
 }
 
+/** Versioned step data, see the executor */
+export const STEP_DATA_VERSION_1 = 1
+export const STEP_FLAG_STABLE = 1
+export const STEP_FLAG_DEX_ADDRESS = 2
+export const STEP_FLAG_MINIMUM_OUT = 4
+/** Maximum price as input per output scaled by 1e18 */
+export const STEP_FLAG_MAXIMUM_PRICE = 8
+
+/** Versioned Mira params, forks are given with their address */
+export function encodeMiraParamsV1(fee: bigint | BN | string, isStable: boolean, dexAddress?: string) {
+    let flags = 0
+    if (isStable) flags |= STEP_FLAG_STABLE
+    if (dexAddress) flags |= STEP_FLAG_DEX_ADDRESS
+    let hex = toHex(STEP_DATA_VERSION_1, 1) + crop(toHex(flags, 1)) + crop(toHex(fee.toString(), 2))
+    if (dexAddress) hex += crop(toHex(dexAddress, 32))
+    return toU8Array(hex)
+}
+
+/** Versioned order params from the 160 byte order encoding */
+export function encodeOrderParamsV1(orderData: Uint8Array | number[]) {
+    return [STEP_DATA_VERSION_1, 0, ...Array.from(orderData)]
+}
+
+/** Adds the limit fields to versioned step data, they are the last fields */
+export function withStepLimits(data: Uint8Array | number[], minimumOut?: bigint | BN | string, maximumPrice?: bigint | BN | string) {
+    const result = Array.from(data)
+    if (minimumOut !== undefined) {
+        result[1] |= STEP_FLAG_MINIMUM_OUT
+        result.push(...toU8Array(toHex(minimumOut.toString(), 8)))
+    }
+    if (maximumPrice !== undefined) {
+        result[1] |= STEP_FLAG_MAXIMUM_PRICE
+        result.push(...toU8Array(toHex(maximumPrice.toString(), 32)))
+    }
+    return result
+}
+
 function crop(hex: string) {
     return hex.replace("0x", "")
 }